@@ -6,6 +6,8 @@
 #[macro_use]
 extern crate criterion;
 
+use std::hint::black_box;
+
 use criterion::{BatchSize, Criterion};
 use curve25519_dalek::{RistrettoPoint, Scalar};
 use itertools::izip;
@@ -219,6 +221,171 @@ fn verify_batch_proof(c: &mut Criterion) {
     group.finish();
 }
 
+#[allow(non_snake_case)]
+#[allow(non_upper_case_globals)]
+fn verify_batch_proof_precomputed(c: &mut Criterion) {
+    let mut group = c.benchmark_group("verify_batch_proof_precomputed");
+    let mut rng = ChaCha12Rng::seed_from_u64(8675309);
+
+    for n in N_VALUES {
+        for m in M_VALUES {
+            // Generate parameters and their precomputation
+            let params = TriptychParameters::new(n, m).unwrap();
+            let precomputation = params.build_precomputation();
+
+            for batch in BATCH_SIZES {
+                let label = format!(
+                    "Verify batch proof with precomputation: n = {}, m = {} (N = {}), {}-batch",
+                    n,
+                    m,
+                    params.get_N(),
+                    batch
+                );
+                group.bench_function(&label, |b| {
+                    // Generate data
+                    let (witnesses, statements, transcripts) = generate_data(&params, batch, &mut rng);
+
+                    // Generate the proofs
+                    let proofs = izip!(witnesses.iter(), statements.iter(), transcripts.clone().iter_mut())
+                        .map(|(w, s, t)| TriptychProof::prove_with_rng_vartime(w, s, &mut rng, t).unwrap())
+                        .collect::<Vec<TriptychProof>>();
+
+                    // Start the benchmark
+                    b.iter_batched_ref(
+                        || transcripts.clone(),
+                        |t| {
+                            // Verify the proofs in a batch
+                            assert!(
+                                TriptychProof::verify_batch_precomputed(&statements, &proofs, t, &precomputation)
+                                    .is_ok()
+                            );
+                        },
+                        BatchSize::SmallInput,
+                    )
+                });
+            }
+        }
+    }
+    group.finish();
+}
+
+#[allow(non_snake_case)]
+#[allow(non_upper_case_globals)]
+fn deserialize_proof(c: &mut Criterion) {
+    let mut group = c.benchmark_group("deserialize_proof");
+    let mut rng = ChaCha12Rng::seed_from_u64(8675309);
+
+    for n in N_VALUES {
+        for m in M_VALUES {
+            // Generate parameters
+            let params = TriptychParameters::new(n, m).unwrap();
+
+            // Generate data
+            let (witnesses, statements, transcripts) = generate_data(&params, 1, &mut rng);
+
+            // Generate and serialize the proof
+            let serialized = TriptychProof::prove_with_rng_vartime(
+                &witnesses[0],
+                &statements[0],
+                &mut rng,
+                &mut transcripts[0].clone(),
+            )
+            .unwrap()
+            .to_bytes();
+
+            let label = format!("Deserialize proof: n = {}, m = {} (N = {})", n, m, params.get_N());
+            group.bench_function(&label, |b| {
+                b.iter(|| {
+                    // Deserialize the proof
+                    assert!(TriptychProof::from_bytes(&serialized).is_ok());
+                })
+            });
+
+            let label = format!(
+                "Deserialize proof without decompression: n = {}, m = {} (N = {})",
+                n,
+                m,
+                params.get_N()
+            );
+            group.bench_function(&label, |b| {
+                b.iter(|| {
+                    // Deserialize the proof without decompressing its group elements
+                    assert!(TriptychProof::from_bytes_unchecked_points(&serialized).is_ok());
+                })
+            });
+        }
+    }
+    group.finish();
+}
+
+#[allow(non_snake_case)]
+#[allow(non_upper_case_globals)]
+fn compare_parameters(c: &mut Criterion) {
+    let mut group = c.benchmark_group("compare_parameters");
+
+    for n in N_VALUES {
+        for m in M_VALUES {
+            // Generate separate but equal parameters
+            let params = TriptychParameters::new(n, m).unwrap();
+            let other_params = TriptychParameters::new(n, m).unwrap();
+
+            let label = format!("Compare parameters: n = {}, m = {} (N = {})", n, m, params.get_N());
+            group.bench_function(&label, |b| {
+                b.iter(|| {
+                    // Compare the parameters
+                    assert!(black_box(&params) == black_box(&other_params));
+                })
+            });
+        }
+    }
+    group.finish();
+}
+
+#[cfg(feature = "rayon")]
+#[allow(non_snake_case)]
+#[allow(non_upper_case_globals)]
+fn verify_batch_proof_parallel(c: &mut Criterion) {
+    let mut group = c.benchmark_group("verify_batch_proof_parallel");
+    let mut rng = ChaCha12Rng::seed_from_u64(8675309);
+
+    for n in N_VALUES {
+        for m in M_VALUES {
+            // Generate parameters
+            let params = TriptychParameters::new(n, m).unwrap();
+
+            for batch in BATCH_SIZES {
+                let label = format!(
+                    "Verify batch proof in parallel: n = {}, m = {} (N = {}), {}-batch",
+                    n,
+                    m,
+                    params.get_N(),
+                    batch
+                );
+                group.bench_function(&label, |b| {
+                    // Generate data
+                    let (witnesses, statements, transcripts) = generate_data(&params, batch, &mut rng);
+
+                    // Generate the proofs
+                    let proofs = izip!(witnesses.iter(), statements.iter(), transcripts.clone().iter_mut())
+                        .map(|(w, s, t)| TriptychProof::prove_with_rng_vartime(w, s, &mut rng, t).unwrap())
+                        .collect::<Vec<TriptychProof>>();
+
+                    // Start the benchmark
+                    b.iter_batched_ref(
+                        || transcripts.clone(),
+                        |t| {
+                            // Verify the proofs in a batch
+                            assert!(TriptychProof::verify_batch_parallel(&statements, &proofs, t).is_ok());
+                        },
+                        BatchSize::SmallInput,
+                    )
+                });
+            }
+        }
+    }
+    group.finish();
+}
+
 criterion_group! {
     name = generate;
     config = Criterion::default();
@@ -228,7 +395,29 @@ criterion_group! {
 criterion_group! {
     name = verify;
     config = Criterion::default();
-    targets = verify_proof, verify_batch_proof
+    targets = verify_proof, verify_batch_proof, verify_batch_proof_precomputed
+}
+
+criterion_group! {
+    name = deserialize;
+    config = Criterion::default();
+    targets = deserialize_proof
+}
+
+criterion_group! {
+    name = compare;
+    config = Criterion::default();
+    targets = compare_parameters
+}
+
+#[cfg(feature = "rayon")]
+criterion_group! {
+    name = verify_parallel;
+    config = Criterion::default();
+    targets = verify_batch_proof_parallel
 }
 
-criterion_main!(generate, verify);
+#[cfg(feature = "rayon")]
+criterion_main!(generate, verify, deserialize, compare, verify_parallel);
+#[cfg(not(feature = "rayon"))]
+criterion_main!(generate, verify, deserialize, compare);
@@ -0,0 +1,50 @@
+// Copyright (c) 2024, The Tari Project
+// SPDX-License-Identifier: BSD-3-Clause
+
+//! Triptych is a linkable ring signature, so a common use is to sign a message.
+//!
+//! The library can bind the message into the proof transcript for you.
+//!
+//! This example shows how to sign and verify a message with Triptych.
+#[cfg(test)]
+mod test {
+    use curve25519_dalek::RistrettoPoint;
+    use rand_core::OsRng;
+    use triptych::*;
+
+    #[allow(non_snake_case)]
+    #[test]
+    fn signature() {
+        // It's important to use a cryptographically-secure random number generator!
+        let mut rng = OsRng;
+
+        // Parameters that will define the number of verification keys used in the signature: 2^3 == 8
+        let params = TriptychParameters::new(2, 3).unwrap();
+
+        // Generate a random witness, which includes the signing key and an index where it will appear
+        let witness = TriptychWitness::random(&params, &mut rng);
+
+        // Generate an input set of random verification keys, placing ours at the chosen index
+        let M = (0..params.get_N())
+            .map(|i| {
+                if i == witness.get_l() {
+                    witness.compute_verification_key()
+                } else {
+                    RistrettoPoint::random(&mut rng)
+                }
+            })
+            .collect::<Vec<RistrettoPoint>>();
+        let input_set = TriptychInputSet::new(&M).unwrap();
+
+        // Generate the statement, which includes the verification key vector and linking tag
+        let statement = TriptychStatement::new(&params, &input_set, &witness.compute_linking_tag()).unwrap();
+
+        // Sign the message; there's no need to set up a transcript
+        let message = b"An example of signing with Triptych";
+        let signature = TriptychProof::sign(&witness, &statement, message).unwrap();
+
+        // The signature should verify against the same message, but not a different one
+        assert!(signature.verify_signature(&statement, message).is_ok());
+        assert!(signature.verify_signature(&statement, b"A different message").is_err());
+    }
+}
@@ -13,4 +13,7 @@ fuzz_target!(|data: &[u8]| {
 	if let Ok(proof) = TriptychProof::from_bytes(data) {
 		assert_eq!(&proof.to_bytes(), data);
 	}
+	if let Ok(proof) = TriptychProof::from_bytes_versioned(data) {
+		assert_eq!(&proof.to_bytes_versioned(), data);
+	}
 });
@@ -0,0 +1,41 @@
+// Copyright (c) 2024, The Tari Project
+// SPDX-License-Identifier: BSD-3-Clause
+
+
+#![no_main]
+
+use std::sync::OnceLock;
+
+use curve25519_dalek::Scalar;
+use libfuzzer_sys::fuzz_target;
+use triptych::{
+    proof::TriptychProof,
+    testing::deterministic_ring,
+    Transcript,
+    TriptychParameters,
+    TriptychStatement,
+    TriptychWitness,
+};
+
+// Generate a fixed valid statement only once
+fn statement() -> &'static TriptychStatement {
+	static STATEMENT: OnceLock<TriptychStatement> = OnceLock::new();
+
+	STATEMENT.get_or_init(|| {
+		let params = TriptychParameters::new(2, 4).unwrap();
+		let r = Scalar::from(8675309u64);
+		let witness = TriptychWitness::new(&params, 0, &r).unwrap();
+		let input_set = deterministic_ring(&params, 0, witness.get_l(), &r).unwrap();
+
+		TriptychStatement::new(&params, &input_set, &witness.compute_linking_tag()).unwrap()
+	})
+}
+
+// Test verification of arbitrary proofs against a fixed statement
+fuzz_target!(|data: &[u8]| {
+	// If deserialization succeeds, verification must not panic, and should fail
+	if let Ok(proof) = TriptychProof::from_bytes(data) {
+		let mut transcript = Transcript::new(b"Fuzz transcript");
+		assert!(proof.verify(statement(), &mut transcript).is_err());
+	}
+});
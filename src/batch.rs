@@ -0,0 +1,203 @@
+// Copyright (c) 2024, The Tari Project
+// SPDX-License-Identifier: BSD-3-Clause
+
+use alloc::{vec, vec::Vec};
+
+use curve25519_dalek::Scalar;
+
+use crate::gray::GrayIterator;
+
+/// The responses of a proof that are used in the final batch verification check.
+///
+/// Base and parallel proofs share these responses, and differ only in the additional terms for parallel proofs.
+#[allow(non_snake_case)]
+pub(crate) struct ProofResponses<'a> {
+    /// The partial `f` matrix, which omits the first column.
+    pub(crate) f: &'a [Vec<Scalar>],
+    pub(crate) z_A: &'a Scalar,
+    pub(crate) z_C: &'a Scalar,
+    pub(crate) z: &'a Scalar,
+}
+
+/// Scalars for the final check in batch verification.
+///
+/// The scalars for points specific to each proof are kept in batch order, and those for points common to all proofs are
+/// summed. For each proof, the points specific to it are `A, B, C, D, J, X, Y`, followed by any additional points
+/// added by the caller.
+#[allow(non_snake_case)]
+pub(crate) struct BatchScalars {
+    pub(crate) proof: Vec<Scalar>,
+    pub(crate) G: Scalar,
+    pub(crate) CommitmentG: Vec<Scalar>,
+    pub(crate) CommitmentH: Scalar,
+    pub(crate) M: Vec<Scalar>,
+    pub(crate) U: Scalar,
+}
+
+impl BatchScalars {
+    /// Generate empty [`BatchScalars`] for `commitment_size` commitment generators and an input set of size
+    /// `input_set_size`, with space for `capacity` scalars.
+    pub(crate) fn new(commitment_size: usize, input_set_size: usize, capacity: usize) -> Self {
+        Self {
+            proof: Vec::with_capacity(capacity),
+            G: Scalar::ZERO,
+            CommitmentG: vec![Scalar::ZERO; commitment_size],
+            CommitmentH: Scalar::ZERO,
+            M: vec![Scalar::ZERO; input_set_size],
+            U: Scalar::ZERO,
+        }
+    }
+
+    /// Accumulate the scalars for a proof with base `n`, exponent `m`, and `responses`.
+    ///
+    /// The `xi_powers` must contain the challenge powers for the proof, and `weights` its verification equation
+    /// weights. For each input set index, `per_key` is called with the index and the corresponding `f` product, so
+    /// callers can accumulate additional terms.
+    ///
+    /// On success, returns the sum of the `f` products. If the proof is malformed, returns the reason.
+    #[allow(non_snake_case)]
+    pub(crate) fn accumulate<F: FnMut(usize, Scalar)>(
+        &mut self,
+        n: u32,
+        m: u32,
+        responses: &ProofResponses<'_>,
+        xi_powers: &[Scalar],
+        weights: &[Scalar; 4],
+        mut per_key: F,
+    ) -> Result<Scalar, &'static str> {
+        // Reconstruct the remaining `f` terms
+        let f = (0..m)
+            .map(|j| {
+                let mut f_j = Vec::with_capacity(n as usize);
+                f_j.push(xi_powers[1] - responses.f[j as usize].iter().sum::<Scalar>());
+                f_j.extend(responses.f[j as usize].iter());
+                f_j
+            })
+            .collect::<Vec<Vec<Scalar>>>();
+
+        // Check that `f` does not contain zero, which breaks batch inversion
+        for f_row in &f {
+            if f_row.contains(&Scalar::ZERO) {
+                return Err("proof `f` matrix contained 0");
+            }
+        }
+
+        // Get the weights and challenge for convenience
+        let [w1, w2, w3, w4] = *weights;
+        let xi = xi_powers[1];
+
+        // G
+        self.G -= w3 * responses.z;
+
+        // CommitmentG
+        for (CommitmentG_scalar, f_item) in self
+            .CommitmentG
+            .iter_mut()
+            .zip(f.iter().flatten().map(|f| w1 * f + w2 * f * (xi - f)))
+        {
+            *CommitmentG_scalar += f_item;
+        }
+
+        // CommitmentH
+        self.CommitmentH += w1 * responses.z_A + w2 * responses.z_C;
+
+        // A
+        self.proof.push(-w1);
+
+        // B
+        self.proof.push(-w1 * xi_powers[1]);
+
+        // C
+        self.proof.push(-w2 * xi_powers[1]);
+
+        // D
+        self.proof.push(-w2);
+
+        // J
+        self.proof.push(-w4 * responses.z);
+
+        // X
+        for xi_power in &xi_powers[0..(m as usize)] {
+            self.proof.push(-w3 * xi_power);
+        }
+
+        // Y
+        for xi_power in &xi_powers[0..(m as usize)] {
+            self.proof.push(-w4 * xi_power);
+        }
+
+        // Set up the initial `f` product and Gray iterator
+        let mut f_product = f.iter().map(|f_row| f_row[0]).product::<Scalar>();
+        let gray_iterator = GrayIterator::new(n, m).ok_or("coefficient decomposition failed")?;
+
+        // Invert each element of `f` for efficiency
+        let mut f_inverse_flat = f.iter().flatten().copied().collect::<Vec<Scalar>>();
+        Scalar::batch_invert(&mut f_inverse_flat);
+        let f_inverse = f_inverse_flat.chunks_exact(n as usize).collect::<Vec<&[Scalar]>>();
+
+        // M
+        let mut f_product_sum = Scalar::ZERO;
+        for (index, (M_scalar, (gray_index, gray_old, gray_new))) in self.M.iter_mut().zip(gray_iterator).enumerate() {
+            // Update the `f` product
+            f_product *= f_inverse[gray_index][gray_old as usize] * f[gray_index][gray_new as usize];
+
+            *M_scalar += w3 * f_product;
+            f_product_sum += f_product;
+            per_key(index, f_product);
+        }
+
+        // U
+        self.U += w4 * f_product_sum;
+
+        Ok(f_product_sum)
+    }
+
+    /// Combine these [`BatchScalars`] with `other`, which must be for proofs that follow these in the batch.
+    #[cfg(feature = "rayon")]
+    pub(crate) fn merge(mut self, other: Self) -> Self {
+        self.proof.extend(other.proof);
+        self.G += other.G;
+        for (item, other_item) in self.CommitmentG.iter_mut().zip(other.CommitmentG) {
+            *item += other_item;
+        }
+        self.CommitmentH += other.CommitmentH;
+        for (item, other_item) in self.M.iter_mut().zip(other.M) {
+            *item += other_item;
+        }
+        self.U += other.U;
+
+        self
+    }
+
+    /// Produce the scalar vectors for the final check using a
+    /// [`TriptychPrecomputation`](`crate::TriptychPrecomputation`).
+    ///
+    /// The first vector is for the fixed generators, in the order of
+    /// [`TriptychParameters::fixed_points`](`crate::TriptychParameters::fixed_points`). The second is for the proof
+    /// points followed by the input set verification keys.
+    pub(crate) fn into_precomputed_scalars(self) -> (Vec<Scalar>, Vec<Scalar>) {
+        let mut fixed = Vec::with_capacity(self.CommitmentG.len().saturating_add(3));
+        fixed.push(self.G);
+        fixed.extend(self.CommitmentG);
+        fixed.push(self.CommitmentH);
+        fixed.push(self.U);
+
+        let mut dynamic = self.proof;
+        dynamic.extend(self.M);
+
+        (fixed, dynamic)
+    }
+
+    /// Produce the scalar vector for the final check, putting the common elements `G, CommitmentG, CommitmentH, M, U`
+    /// last.
+    pub(crate) fn into_scalars(self) -> Vec<Scalar> {
+        let mut scalars = self.proof;
+        scalars.push(self.G);
+        scalars.extend(self.CommitmentG);
+        scalars.push(self.CommitmentH);
+        scalars.extend(self.M);
+        scalars.push(self.U);
+
+        scalars
+    }
+}
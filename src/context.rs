@@ -0,0 +1,123 @@
+// Copyright (c) 2024, The Tari Project
+// SPDX-License-Identifier: BSD-3-Clause
+
+use curve25519_dalek::RistrettoPoint;
+
+use crate::{domains, Transcript};
+
+/// Typed context that is bound into a Triptych proof.
+///
+/// Provers and verifiers must use identical transcripts, which is easy to get wrong when each side appends data to a
+/// [`Transcript`] directly. A [`ProofContext`] is built from typed values instead, and always produces the same
+/// [`Transcript`] for the same sequence of bindings. Each binding includes its type, so values of different types
+/// cannot produce the same transcript.
+///
+/// Use [`TriptychProof::prove_with_context`](`crate::TriptychProof::prove_with_context`) and
+/// [`TriptychProof::verify_with_context`](`crate::TriptychProof::verify_with_context`) with a context, or produce a
+/// transcript directly using [`ProofContext::to_transcript`].
+#[derive(Clone)]
+pub struct ProofContext {
+    transcript: Transcript,
+}
+
+impl ProofContext {
+    /// Generate a new empty [`ProofContext`] with an application-specific domain separator `label`.
+    pub fn new(label: &'static [u8]) -> Self {
+        let mut transcript = Transcript::new(label);
+        transcript.append_message(b"dom-sep", domains::TRANSCRIPT_PROOF_CONTEXT.as_bytes());
+        transcript.append_u64(b"version", domains::VERSION);
+
+        Self { transcript }
+    }
+
+    /// Generate a new empty [`ProofContext`] with an application domain separator `domain` and protocol `version`.
+    ///
+    /// This is useful when composing Triptych proofs into a larger protocol, where the domain separator may not be
+    /// known at compile time. The domain separator and version are bound before any other data, and before the
+    /// library's own proof transcript data. Provers and verifiers must use the same domain separator and version.
+    pub fn new_with_domain(domain: &[u8], version: u64) -> Self {
+        let mut context = Self::new(domains::TRANSCRIPT_PROOF_CONTEXT.as_bytes());
+        context.transcript.append_message(b"domain", domain);
+        context.transcript.append_u64(b"domain-version", version);
+
+        context
+    }
+
+    /// Bind a `u64` `value` to this [`ProofContext`] with label `label`.
+    pub fn bind_u64(&mut self, label: &'static [u8], value: u64) -> &mut Self {
+        self.transcript.append_message(b"type", b"u64");
+        self.transcript.append_u64(label, value);
+
+        self
+    }
+
+    /// Bind a byte slice `value` to this [`ProofContext`] with label `label`.
+    pub fn bind_bytes(&mut self, label: &'static [u8], value: &[u8]) -> &mut Self {
+        self.transcript.append_message(b"type", b"bytes");
+        self.transcript.append_message(label, value);
+
+        self
+    }
+
+    /// Bind a [`RistrettoPoint`] `point` to this [`ProofContext`] with label `label`.
+    pub fn bind_point(&mut self, label: &'static [u8], point: &RistrettoPoint) -> &mut Self {
+        self.transcript.append_message(b"type", b"point");
+        self.transcript.append_message(label, point.compress().as_bytes());
+
+        self
+    }
+
+    /// Produce a [`Transcript`] from this [`ProofContext`].
+    ///
+    /// This does not modify the context, so it can be called any number of times to produce identical transcripts.
+    pub fn to_transcript(&self) -> Transcript {
+        self.transcript.clone()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use curve25519_dalek::RistrettoPoint;
+    use rand_chacha::ChaCha12Rng;
+    use rand_core::SeedableRng;
+
+    use crate::{context::ProofContext, transcript_fingerprint};
+
+    #[test]
+    fn test_proof_context() {
+        let mut rng = ChaCha12Rng::seed_from_u64(8675309);
+        let point = RistrettoPoint::random(&mut rng);
+
+        // Identical bindings produce identical transcripts
+        let mut context = ProofContext::new(b"Test context");
+        context
+            .bind_u64(b"height", 1)
+            .bind_bytes(b"message", b"hello")
+            .bind_point(b"key", &point);
+        let mut other = ProofContext::new(b"Test context");
+        other
+            .bind_u64(b"height", 1)
+            .bind_bytes(b"message", b"hello")
+            .bind_point(b"key", &point);
+        assert_eq!(
+            transcript_fingerprint(&context.to_transcript()),
+            transcript_fingerprint(&other.to_transcript())
+        );
+
+        // Producing a transcript does not modify the context
+        assert_eq!(
+            transcript_fingerprint(&context.to_transcript()),
+            transcript_fingerprint(&context.to_transcript())
+        );
+
+        // Values with the same encoding but different types produce different transcripts
+        let mut u64_context = ProofContext::new(b"Test context");
+        u64_context.bind_u64(b"value", 1);
+        let mut bytes_context = ProofContext::new(b"Test context");
+        bytes_context.bind_bytes(b"value", &1u64.to_le_bytes());
+        assert_ne!(
+            transcript_fingerprint(&u64_context.to_transcript()),
+            transcript_fingerprint(&bytes_context.to_transcript())
+        );
+    }
+}
@@ -0,0 +1,532 @@
+// Copyright (c) 2024, The Tari Project
+// SPDX-License-Identifier: BSD-3-Clause
+
+//! A C-compatible interface for generating and verifying Triptych proofs.
+//!
+//! All functions take [`TriptychParameters`] in their `borsh` serialization, so any parameters can be used, including
+//! those with custom, seeded, application-specific, or rotated generators.
+//! Verification keys and linking tags are passed as contiguous arrays of 32-byte compressed encodings, and proofs use
+//! the canonical serialization format of [`TriptychProof::to_bytes`].
+//!
+//! Each proof is a signature on a caller-supplied message, as produced by [`TriptychProof::sign`] and checked by
+//! [`TriptychProof::verify_signature`], so proofs interoperate with Rust callers using those functions or
+//! [`verify_serialized`](`crate::verify_serialized`).
+//!
+//! No function panics across the interface; any panic is caught and reported as
+//! [`TriptychStatus::Panic`](`crate::ffi::TriptychStatus::Panic`).
+
+extern crate std;
+
+use alloc::vec::Vec;
+use core::slice;
+use std::panic::{catch_unwind, AssertUnwindSafe};
+
+use curve25519_dalek::{ristretto::CompressedRistretto, RistrettoPoint, Scalar};
+use zeroize::Zeroizing;
+
+use crate::{
+    proof::ProofError,
+    verify_serialized,
+    TriptychInputSet,
+    TriptychParameters,
+    TriptychProof,
+    TriptychStatement,
+    TriptychWitness,
+};
+
+// Size of encoded keys, tags, and scalars in bytes
+const ENCODING_BYTES: usize = 32;
+
+/// Status codes returned by the C-compatible interface.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum TriptychStatus {
+    /// The operation succeeded.
+    Success = 0,
+    /// A required pointer was null.
+    NullPointer = 1,
+    /// The parameters were invalid.
+    InvalidParameters = 2,
+    /// An input could not be decoded or was invalid.
+    InvalidInput = 3,
+    /// An output buffer was too small.
+    BufferTooSmall = 4,
+    /// Proof generation failed.
+    ProvingFailed = 5,
+    /// Proof verification failed.
+    VerificationFailed = 6,
+    /// An unexpected panic was caught.
+    Panic = 7,
+}
+
+/// Get a slice from a pointer and length, allowing a null pointer only for an empty slice.
+///
+/// # Safety
+///
+/// If `ptr` is not null, it must be valid for reads of `len` elements.
+unsafe fn as_slice<'a, T>(ptr: *const T, len: usize) -> Result<&'a [T], TriptychStatus> {
+    if len == 0 {
+        return Ok(&[]);
+    }
+    if ptr.is_null() {
+        return Err(TriptychStatus::NullPointer);
+    }
+
+    Ok(slice::from_raw_parts(ptr, len))
+}
+
+/// Decode a compressed point.
+fn decode_point(bytes: &[u8]) -> Result<RistrettoPoint, TriptychStatus> {
+    CompressedRistretto::from_slice(bytes)
+        .map_err(|_| TriptychStatus::InvalidInput)?
+        .decompress()
+        .ok_or(TriptychStatus::InvalidInput)
+}
+
+/// Decode serialized parameters.
+///
+/// # Safety
+///
+/// If `params` is not null, it must be valid for reads of `params_len` bytes.
+unsafe fn decode_params(params: *const u8, params_len: usize) -> Result<TriptychParameters, TriptychStatus> {
+    borsh::from_slice(as_slice(params, params_len)?).map_err(|_| TriptychStatus::InvalidParameters)
+}
+
+/// Convert a verification error to a status code.
+fn verification_status(error: &ProofError) -> TriptychStatus {
+    match error {
+        ProofError::InvalidVerificationKey { .. } |
+        ProofError::InvalidLinkingTag |
+        ProofError::InvalidStatement { .. } |
+        ProofError::FailedDeserialization => TriptychStatus::InvalidInput,
+        _ => TriptychStatus::VerificationFailed,
+    }
+}
+
+/// Build an input set from raw data.
+///
+/// # Safety
+///
+/// If `keys` is not null, it must be valid for reads of `keys_len * 32` bytes.
+unsafe fn build_input_set(keys: *const u8, keys_len: usize) -> Result<TriptychInputSet, TriptychStatus> {
+    let keys_bytes = as_slice(
+        keys,
+        keys_len
+            .checked_mul(ENCODING_BYTES)
+            .ok_or(TriptychStatus::InvalidInput)?,
+    )?;
+    #[allow(non_snake_case)]
+    let M = keys_bytes
+        .chunks_exact(ENCODING_BYTES)
+        .map(decode_point)
+        .collect::<Result<Vec<RistrettoPoint>, TriptychStatus>>()?;
+
+    TriptychInputSet::new(&M).map_err(|_| TriptychStatus::InvalidInput)
+}
+
+/// Run a closure, converting any panic into a status code.
+fn guard<F: FnOnce() -> Result<(), TriptychStatus>>(f: F) -> TriptychStatus {
+    match catch_unwind(AssertUnwindSafe(f)) {
+        Ok(Ok(())) => TriptychStatus::Success,
+        Ok(Err(status)) => status,
+        Err(_) => TriptychStatus::Panic,
+    }
+}
+
+/// Generate a Triptych proof.
+///
+/// The parameters are the `params_len` bytes of serialized parameters at `params`. The input set is given by
+/// `keys_len` compressed verification keys at `keys`. The signing key is the canonical 32-byte scalar encoding at
+/// `signing_key`, and its verification key must appear at index `index` of the input set. The proof is a signature on
+/// the `message_len` bytes at `message`.
+///
+/// The proof length depends only on the parameters. If `proof` is null, no proof is generated; the required length is
+/// written to `proof_len`, and no other input is read. Otherwise, on input, `proof_len` must contain the capacity of
+/// the `proof` buffer. If the buffer is too small, returns [`TriptychStatus::BufferTooSmall`] and writes the required
+/// length to `proof_len` before generating a proof. On success, the proof is written to `proof` and its length to
+/// `proof_len`.
+///
+/// The buffers used to hold the signing key are zeroized after use, but copies made by the compiler cannot be cleared.
+/// The caller is responsible for its own copy.
+///
+/// # Safety
+///
+/// Each non-null pointer must be valid for the corresponding reads or writes: `params_len` bytes at `params`,
+/// `keys_len * 32` bytes at `keys`, 32 bytes at `signing_key`, `message_len` bytes at `message`, a `usize` at
+/// `proof_len`, and `*proof_len` bytes at `proof`.
+#[no_mangle]
+#[allow(clippy::too_many_arguments)]
+pub unsafe extern "C" fn triptych_prove(
+    params: *const u8,
+    params_len: usize,
+    keys: *const u8,
+    keys_len: usize,
+    index: u32,
+    signing_key: *const u8,
+    message: *const u8,
+    message_len: usize,
+    proof: *mut u8,
+    proof_len: *mut usize,
+) -> TriptychStatus {
+    guard(|| {
+        if proof_len.is_null() {
+            return Err(TriptychStatus::NullPointer);
+        }
+
+        let params = decode_params(params, params_len)?;
+
+        // Check the buffer capacity before doing any work, or report the required length if there is no buffer
+        let required = TriptychProof::serialized_size_for(params.get_n(), params.get_m())
+            .ok_or(TriptychStatus::InvalidParameters)?;
+        let capacity = *proof_len;
+        *proof_len = required;
+        if proof.is_null() {
+            return Ok(());
+        }
+        if capacity < required {
+            return Err(TriptychStatus::BufferTooSmall);
+        }
+
+        if signing_key.is_null() {
+            return Err(TriptychStatus::NullPointer);
+        }
+        let input_set = build_input_set(keys, keys_len)?;
+
+        // Decode the signing key into zeroizing memory
+        let mut r_bytes = Zeroizing::new([0u8; ENCODING_BYTES]);
+        r_bytes.copy_from_slice(slice::from_raw_parts(signing_key, ENCODING_BYTES));
+        let r = Zeroizing::new(
+            Option::<Scalar>::from(Scalar::from_canonical_bytes(*r_bytes)).ok_or(TriptychStatus::InvalidInput)?,
+        );
+        let witness = TriptychWitness::new(&params, index, &r).map_err(|_| TriptychStatus::InvalidInput)?;
+
+        #[allow(non_snake_case)]
+        let J = witness.compute_linking_tag();
+        let statement = TriptychStatement::new(&params, &input_set, &J).map_err(|_| TriptychStatus::InvalidInput)?;
+
+        let message = as_slice(message, message_len)?;
+        let result = TriptychProof::sign(&witness, &statement, message)
+            .map_err(|_| TriptychStatus::ProvingFailed)?
+            .to_bytes();
+
+        // The length was checked against the capacity above
+        if result.len() != required {
+            return Err(TriptychStatus::ProvingFailed);
+        }
+        slice::from_raw_parts_mut(proof, result.len()).copy_from_slice(&result);
+
+        Ok(())
+    })
+}
+
+/// Verify a Triptych proof.
+///
+/// The parameters are the `params_len` bytes of serialized parameters at `params`. The input set is given by
+/// `keys_len` compressed verification keys at `keys`, and the compressed linking tag is at `tag`. The proof of
+/// `proof_len` bytes at `proof` must be a signature on the `message_len` bytes at `message`.
+///
+/// This uses [`verify_serialized`](`crate::verify_serialized`) with the message as its transcript label.
+///
+/// Returns [`TriptychStatus::Success`] only if the proof is valid.
+///
+/// # Safety
+///
+/// Each non-null pointer must be valid for the corresponding reads: `params_len` bytes at `params`, `keys_len * 32`
+/// bytes at `keys`, 32 bytes at `tag`, `message_len` bytes at `message`, and `proof_len` bytes at `proof`.
+#[no_mangle]
+#[allow(clippy::too_many_arguments)]
+pub unsafe extern "C" fn triptych_verify(
+    params: *const u8,
+    params_len: usize,
+    keys: *const u8,
+    keys_len: usize,
+    tag: *const u8,
+    message: *const u8,
+    message_len: usize,
+    proof: *const u8,
+    proof_len: usize,
+) -> TriptychStatus {
+    guard(|| {
+        if tag.is_null() {
+            return Err(TriptychStatus::NullPointer);
+        }
+
+        let params = decode_params(params, params_len)?;
+        let keys = as_slice(keys.cast::<[u8; ENCODING_BYTES]>(), keys_len)?;
+        let tag = *tag.cast::<[u8; ENCODING_BYTES]>();
+        let message = as_slice(message, message_len)?;
+        let proof = as_slice(proof, proof_len)?;
+
+        verify_serialized(&params, keys, tag, proof, message).map_err(|error| verification_status(&error))
+    })
+}
+
+/// Verify a batch of Triptych proofs that share an input set.
+///
+/// The parameters are the `params_len` bytes of serialized parameters at `params`. The input set is given by
+/// `keys_len` compressed verification keys at `keys`. For each of the `batch_len` proofs, `tags` contains a compressed
+/// linking tag, `messages` and `message_lens` describe the signed message, and `proofs` and `proof_lens` describe the
+/// serialized proof.
+///
+/// Returns [`TriptychStatus::Success`] only if all proofs are valid. An empty batch is valid by definition.
+///
+/// # Safety
+///
+/// Each non-null pointer must be valid for the corresponding reads: `params_len` bytes at `params`, `keys_len * 32`
+/// bytes at `keys`, `batch_len * 32` bytes at `tags`, and `batch_len` elements at each of `messages`, `message_lens`,
+/// `proofs`, and `proof_lens`. Each message and proof pointer must be valid for reads of its corresponding length.
+#[no_mangle]
+#[allow(clippy::too_many_arguments)]
+pub unsafe extern "C" fn triptych_verify_batch(
+    params: *const u8,
+    params_len: usize,
+    keys: *const u8,
+    keys_len: usize,
+    batch_len: usize,
+    tags: *const u8,
+    messages: *const *const u8,
+    message_lens: *const usize,
+    proofs: *const *const u8,
+    proof_lens: *const usize,
+) -> TriptychStatus {
+    guard(|| {
+        let params = decode_params(params, params_len)?;
+        let input_set = build_input_set(keys, keys_len)?;
+
+        let tags = as_slice(
+            tags,
+            batch_len
+                .checked_mul(ENCODING_BYTES)
+                .ok_or(TriptychStatus::InvalidInput)?,
+        )?;
+        let messages = as_slice(messages, batch_len)?;
+        let message_lens = as_slice(message_lens, batch_len)?;
+        let proofs = as_slice(proofs, batch_len)?;
+        let proof_lens = as_slice(proof_lens, batch_len)?;
+
+        let mut statements = Vec::with_capacity(batch_len);
+        let mut transcripts = Vec::with_capacity(batch_len);
+        let mut batch = Vec::with_capacity(batch_len);
+        for (i, tag) in tags.chunks_exact(ENCODING_BYTES).enumerate() {
+            #[allow(non_snake_case)]
+            let J = decode_point(tag)?;
+            statements.push(TriptychStatement::new(&params, &input_set, &J).map_err(|_| TriptychStatus::InvalidInput)?);
+            transcripts.push(TriptychProof::signature_transcript(as_slice(
+                messages[i],
+                message_lens[i],
+            )?));
+            batch.push(
+                TriptychProof::from_bytes(as_slice(proofs[i], proof_lens[i])?)
+                    .map_err(|_| TriptychStatus::InvalidInput)?,
+            );
+        }
+
+        TriptychProof::verify_batch(&statements, &batch, &mut transcripts)
+            .map_err(|_| TriptychStatus::VerificationFailed)
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use alloc::{vec, vec::Vec};
+    use core::ptr;
+
+    use curve25519_dalek::RistrettoPoint;
+    use rand_chacha::ChaCha12Rng;
+    use rand_core::SeedableRng;
+
+    use super::{triptych_prove, triptych_verify, triptych_verify_batch, TriptychStatus};
+    use crate::{TriptychInputSet, TriptychParameters, TriptychProof, TriptychStatement, TriptychWitness};
+
+    #[test]
+    #[allow(clippy::too_many_lines, non_snake_case, non_upper_case_globals)]
+    fn test_ffi() {
+        const n: u32 = 2;
+        const m: u32 = 4;
+        let mut rng = ChaCha12Rng::seed_from_u64(8675309);
+        let params = TriptychParameters::new_for_application(n, m, b"Test application").unwrap();
+        let params_bytes = borsh::to_vec(&params).unwrap();
+        let witness = TriptychWitness::random(&params, &mut rng);
+
+        // Generate the input set, and encode it along with the signing key and linking tag
+        let M = (0..params.get_N())
+            .map(|i| {
+                if i == witness.get_l() {
+                    witness.compute_verification_key()
+                } else {
+                    RistrettoPoint::random(&mut rng)
+                }
+            })
+            .collect::<Vec<RistrettoPoint>>();
+        let keys = M.iter().flat_map(|key| key.compress().to_bytes()).collect::<Vec<u8>>();
+        let keys_len = params.get_N() as usize;
+        let signing_key = witness.get_r().to_bytes();
+        let tag = witness.compute_linking_tag().compress().to_bytes();
+        let message = b"message";
+
+        // A null buffer queries the required size
+        let mut proof_len = 0;
+        let status = unsafe {
+            triptych_prove(
+                params_bytes.as_ptr(),
+                params_bytes.len(),
+                ptr::null(),
+                0,
+                0,
+                ptr::null(),
+                ptr::null(),
+                0,
+                ptr::null_mut(),
+                &mut proof_len,
+            )
+        };
+        assert_eq!(status, TriptychStatus::Success);
+        assert_eq!(Some(proof_len), TriptychProof::serialized_size_for(n, m));
+
+        // A buffer that is too small reports the required size
+        let mut proof = vec![0u8; 1];
+        let mut proof_len = proof.len();
+        let status = unsafe {
+            triptych_prove(
+                params_bytes.as_ptr(),
+                params_bytes.len(),
+                keys.as_ptr(),
+                keys_len,
+                witness.get_l(),
+                signing_key.as_ptr(),
+                message.as_ptr(),
+                message.len(),
+                proof.as_mut_ptr(),
+                &mut proof_len,
+            )
+        };
+        assert_eq!(status, TriptychStatus::BufferTooSmall);
+
+        // Generate a proof
+        proof.resize(proof_len, 0);
+        let status = unsafe {
+            triptych_prove(
+                params_bytes.as_ptr(),
+                params_bytes.len(),
+                keys.as_ptr(),
+                keys_len,
+                witness.get_l(),
+                signing_key.as_ptr(),
+                message.as_ptr(),
+                message.len(),
+                proof.as_mut_ptr(),
+                &mut proof_len,
+            )
+        };
+        assert_eq!(status, TriptychStatus::Success);
+
+        // Verify the proof singly and as a batch
+        let status = unsafe {
+            triptych_verify(
+                params_bytes.as_ptr(),
+                params_bytes.len(),
+                keys.as_ptr(),
+                keys_len,
+                tag.as_ptr(),
+                message.as_ptr(),
+                message.len(),
+                proof.as_ptr(),
+                proof_len,
+            )
+        };
+        assert_eq!(status, TriptychStatus::Success);
+        let status = unsafe {
+            triptych_verify_batch(
+                params_bytes.as_ptr(),
+                params_bytes.len(),
+                keys.as_ptr(),
+                keys_len,
+                1,
+                tag.as_ptr(),
+                &message.as_ptr(),
+                &message.len(),
+                &proof.as_ptr(),
+                &proof_len,
+            )
+        };
+        assert_eq!(status, TriptychStatus::Success);
+
+        // The proof is a signature on the message
+        let statement = TriptychStatement::new(
+            &params,
+            &TriptychInputSet::new(&M).unwrap(),
+            &witness.compute_linking_tag(),
+        )
+        .unwrap();
+        assert!(TriptychProof::from_bytes(&proof)
+            .unwrap()
+            .verify_signature(&statement, message)
+            .is_ok());
+
+        // Different parameters fail
+        let evil_params_bytes = borsh::to_vec(&TriptychParameters::new(n, m).unwrap()).unwrap();
+        let status = unsafe {
+            triptych_verify(
+                evil_params_bytes.as_ptr(),
+                evil_params_bytes.len(),
+                keys.as_ptr(),
+                keys_len,
+                tag.as_ptr(),
+                message.as_ptr(),
+                message.len(),
+                proof.as_ptr(),
+                proof_len,
+            )
+        };
+        assert_eq!(status, TriptychStatus::VerificationFailed);
+
+        // Invalid parameters are rejected
+        let status = unsafe {
+            triptych_verify(
+                params_bytes.as_ptr(),
+                params_bytes.len() - 1,
+                keys.as_ptr(),
+                keys_len,
+                tag.as_ptr(),
+                message.as_ptr(),
+                message.len(),
+                proof.as_ptr(),
+                proof_len,
+            )
+        };
+        assert_eq!(status, TriptychStatus::InvalidParameters);
+
+        // A different message fails
+        let evil_message = b"evil message";
+        let status = unsafe {
+            triptych_verify(
+                params_bytes.as_ptr(),
+                params_bytes.len(),
+                keys.as_ptr(),
+                keys_len,
+                tag.as_ptr(),
+                evil_message.as_ptr(),
+                evil_message.len(),
+                proof.as_ptr(),
+                proof_len,
+            )
+        };
+        assert_eq!(status, TriptychStatus::VerificationFailed);
+
+        // Null pointers are rejected
+        let status = unsafe {
+            triptych_verify(
+                params_bytes.as_ptr(),
+                params_bytes.len(),
+                keys.as_ptr(),
+                keys_len,
+                ptr::null(),
+                message.as_ptr(),
+                message.len(),
+                proof.as_ptr(),
+                proof_len,
+            )
+        };
+        assert_eq!(status, TriptychStatus::NullPointer);
+    }
+}
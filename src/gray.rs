@@ -7,8 +7,34 @@ use core::num::NonZeroU32;
 use crypto_bigint::{NonZero, U64};
 
 /// An iterator for arbitrary-base Gray codes.
+///
+/// Triptych represents an index into an input set of size `N = n**m` as an `m`-digit Gray code in base `n`, where
+/// consecutive indexes differ in a single digit. This iterator yields the digit change between each consecutive pair of
+/// codes, and [`GrayIterator::decompose`] produces the code for a given index.
+///
+/// Iterating through codes takes variable time, and should only be used for public data.
+///
+/// ```
+/// use triptych::gray::GrayIterator;
+///
+/// const n: u32 = 3;
+/// const m: u32 = 2;
+///
+/// // Track the Gray code through each change
+/// let mut digits = vec![0; m as usize];
+/// for (i, (index, old, new)) in GrayIterator::new(n, m).unwrap().enumerate() {
+///     assert_eq!(digits[index], old);
+///     digits[index] = new;
+///
+///     // The tracked code matches the decomposition
+///     assert_eq!(
+///         digits,
+///         GrayIterator::decompose_vartime(n, m, i as u32).unwrap()
+///     );
+/// }
+/// ```
 #[allow(non_snake_case)]
-pub(crate) struct GrayIterator {
+pub struct GrayIterator {
     N: u32, // base
     M: u32, // number of digits
     // state information
@@ -25,7 +51,7 @@ impl GrayIterator {
     /// Operations using this iterator run in variable time, so don't use this for secret data.
     /// If you need to get the Gray code decomposition for a secret value, use `decompose` directly.
     #[allow(non_snake_case)]
-    pub(crate) fn new(N: u32, M: u32) -> Option<Self> {
+    pub fn new(N: u32, M: u32) -> Option<Self> {
         // Check inputs
         if N <= 1 || M == 0 {
             return None;
@@ -45,8 +71,10 @@ impl GrayIterator {
     /// You must provide a valid value `v` based on the supplied parameters `N` and `M`.
     /// If anything goes wrong, returns `None`.
     /// Otherwise, returns the Gray code as a `u32` digit vector.
+    ///
+    /// This runs in variable time, so don't use this for secret data; use [`GrayIterator::decompose`] instead.
     #[allow(non_snake_case)]
-    pub(crate) fn decompose_vartime(N: u32, M: u32, mut v: u32) -> Option<Vec<u32>> {
+    pub fn decompose_vartime(N: u32, M: u32, mut v: u32) -> Option<Vec<u32>> {
         if N <= 1 || M == 0 {
             return None;
         }
@@ -76,8 +104,26 @@ impl GrayIterator {
     /// You must provide a valid value `v` based on the supplied parameters `N` and `M`.
     /// If anything goes wrong, returns `None`.
     /// Otherwise, returns the Gray code as a `u32` digit vector.
+    ///
+    /// This is the function to use for secret data, like the index of a witness:
+    ///
+    /// ```
+    /// # #[cfg(feature = "rand")]
+    /// # {
+    /// use rand_core::OsRng;
+    /// use triptych::{gray::GrayIterator, TriptychParameters, TriptychWitness};
+    ///
+    /// let params = TriptychParameters::new(3, 2).unwrap();
+    /// let witness = TriptychWitness::random(&params, &mut OsRng);
+    ///
+    /// // Map the witness index to its digits
+    /// let digits = GrayIterator::decompose(params.get_n(), params.get_m(), witness.get_l()).unwrap();
+    /// assert_eq!(digits.len(), params.get_m() as usize);
+    /// assert!(digits.iter().all(|digit| *digit < params.get_n()));
+    /// # }
+    /// ```
     #[allow(non_snake_case)]
-    pub(crate) fn decompose(N: u32, M: u32, v: u32) -> Option<Vec<u32>> {
+    pub fn decompose(N: u32, M: u32, v: u32) -> Option<Vec<u32>> {
         if N <= 1 || M == 0 {
             return None;
         }
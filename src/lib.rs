@@ -25,6 +25,10 @@
 //! It's possible to use the Fiat-Shamir transformation to produce a non-interactive protocol that can additionally bind
 //! an arbitrary message into the transcript. This produces the linkable ring signature.
 //!
+//! To sign several messages with a single proof, bind all of them into the transcript before proving.
+//! The protocol must not be used to produce multiple responses to the same commitments under different challenges:
+//! by special soundness, any two such responses reveal the signer's index and secret key.
+//!
 //! This library also supports [parallel proving functionality](`crate::parallel`).
 //!
 //! # Implementation notes
@@ -40,14 +44,23 @@
 //!
 //! | Feature | Default? | Description |
 //! | :--- | :---: | :--- |
-//! | `borsh` | | Adds proof serialization and deserialization via [`borsh`](https://crates.io/crates/borsh) |
+//! | `base64` | | Adds proof encoding and decoding as base64 strings via [`base64`](https://crates.io/crates/base64) |
+//! | `borsh` | | Adds proof, parameter, and statement serialization and deserialization via [`borsh`](https://crates.io/crates/borsh) |
+//! | `ffi` | | Adds a C-compatible interface for generating and verifying proofs |
 //! | `hazmat` | | Adds variable-time prover functionality that should only be used if you absolutely know what you're doing |
+//! | `hex` | | Adds proof encoding and decoding as hexadecimal strings via [`hex`](https://crates.io/crates/hex) |
+//! | `merkle` | | Adds input sets hashed using a Merkle tree, which support efficient key replacement |
 //! | `rand` | ✓ | Adds additional prover functionality that supplies a cryptographically-secure random number generator |
-//! | `serde` | | Adds proof serialization and deserialization via [`serde`](https://crates.io/crates/serde) |
+//! | `rayon` | | Adds batch verification that processes proofs in parallel using [`rayon`](https://crates.io/crates/rayon) |
+//! | `serde` | | Adds proof, parameter, and statement serialization and deserialization via [`serde`](https://crates.io/crates/serde) |
 //! | `std` | ✓ | Adds corresponding dependency features |
+//! | `testing` | | Adds deterministic data generation for tests and benchmarks |
 //!
 //! The underlying [curve library](https://crates.io/crates/curve25519-dalek) chooses an arithmetic backend based on CPU feature detection.
 //! Using a nightly compiler broadens the backend set, and may provide better performance.
+//! The [BLAKE3 library](https://crates.io/crates/blake3) similarly uses SIMD implementations when available; with the
+//! `std` feature, these are detected at runtime.
+//! Generator derivation hashes only short inputs, so its cost is dominated by curve operations rather than hashing.
 //! You can examine performance using the benchmarks: either `cargo bench --all-features` or `cargo +nightly bench
 //! --all-features`.
 //!
@@ -55,6 +68,8 @@
 //! This is used for `borsh` serialization and deserialization, or can be accessed directly.
 //! This functionality has an associated fuzzer that can be run using a nightly compiler: `cargo +nightly fuzz run
 //! proofs`.
+//! A separate fuzzer checks that verifying arbitrary proofs against a fixed statement never panics: `cargo +nightly
+//! fuzz run verify`.
 //!
 //! # Warning
 //!
@@ -116,21 +131,49 @@ extern crate alloc;
 
 pub use merlin::Transcript;
 
+/// Shared batch verification functionality.
+pub(crate) mod batch;
+/// Typed context for binding data into Triptych proofs.
+pub mod context;
+pub use context::ProofContext;
 /// Iterated arbitrary-base Gray code functionality.
-pub(crate) mod gray;
+pub mod gray;
+/// Linking tag functionality for detecting reused signing keys.
+pub mod linking;
+pub use linking::{linking_tag_for, LinkingTagSet};
 /// Public parameters used for generating and verifying Triptych proofs.
 pub mod parameters;
-pub use parameters::TriptychParameters;
+pub use parameters::{TriptychParameters, TriptychPrecomputation};
+/// Merkle tree functionality for input set hashing.
+#[cfg(feature = "merkle")]
+pub(crate) mod merkle;
 /// Triptych proofs.
 pub mod proof;
-pub use proof::TriptychProof;
+#[cfg(feature = "rand")]
+pub use proof::self_test;
+pub use proof::{
+    transcript_fingerprint,
+    verify_serialized,
+    CompressedTriptychProof,
+    TriptychProof,
+    TriptychProofCommitments,
+    TriptychProverState,
+};
 /// Triptych proof statements.
 pub mod statement;
-pub use statement::{TriptychInputSet, TriptychStatement};
+pub use statement::{DecompressionCache, InputSetHasher, PaddingStrategy, TriptychInputSet, TriptychStatement};
+/// Deterministic data generation for tests and benchmarks.
+#[cfg(any(test, feature = "testing"))]
+pub mod testing;
 /// Triptych proof transcripts.
 pub(crate) mod transcript;
 /// Various utility functionality.
 pub(crate) mod util;
+#[cfg(feature = "hazmat")]
+pub use util::OperationTiming;
+/// Reusable Triptych proof verifiers.
+pub mod verifier;
+pub use verifier::TriptychVerifier;
 /// Triptych proof witnesses.
 pub mod witness;
 pub use witness::TriptychWitness;
@@ -138,11 +181,20 @@ pub use witness::TriptychWitness;
 /// Parallel Triptych functionality.
 pub mod parallel;
 
+/// C-compatible interface.
+#[cfg(feature = "ffi")]
+pub mod ffi;
+
 /// Domain separators used for hashing operations
 pub(crate) mod domains {
     // Version
     pub(crate) const VERSION: u64 = 0;
 
+    // Version byte used for versioned serialization, which must fit the version
+    const _: () = assert!(VERSION < 256);
+    #[allow(clippy::cast_possible_truncation)]
+    pub(crate) const VERSION_BYTE: u8 = VERSION as u8;
+
     // Number of bytes in a transcript hash
     pub(crate) const TRANSCRIPT_HASH_BYTES: usize = 32;
 
@@ -151,12 +203,23 @@ pub(crate) mod domains {
     pub(crate) const TRANSCRIPT_PARALLEL_PARAMETERS: &str = "Parallel Triptych parameters";
     pub(crate) const POINT_G1: &str = "Triptych G1";
     pub(crate) const POINT_U: &str = "Triptych U";
+    pub(crate) const POINT_U_ROTATION: &str = "Triptych U rotation";
+    pub(crate) const POINT_U_APPLICATION: &str = "Triptych U application";
     pub(crate) const POINT_COMMITMENT_G: &str = "Triptych CommitmentG";
     pub(crate) const POINT_COMMITMENT_H: &str = "Triptych CommitmentH";
+    pub(crate) const GENERATOR_SEED: &str = "Triptych generator seed";
+    #[cfg(any(test, feature = "testing"))]
+    pub(crate) const POINT_DETERMINISTIC_RING: &str = "Triptych deterministic ring";
 
     // Statement
     pub(crate) const TRANSCRIPT_INPUT_SET: &str = "Triptych input set";
     pub(crate) const TRANSCRIPT_PARALLEL_INPUT_SET: &str = "Parallel Triptych input set";
+    #[cfg(feature = "merkle")]
+    pub(crate) const TRANSCRIPT_MERKLE_INPUT_SET: &str = "Triptych Merkle input set";
+    #[cfg(feature = "merkle")]
+    pub(crate) const MERKLE_LEAF: &str = "Triptych Merkle leaf";
+    #[cfg(feature = "merkle")]
+    pub(crate) const MERKLE_NODE: &str = "Triptych Merkle node";
     pub(crate) const TRANSCRIPT_STATEMENT: &str = "Triptych statement";
     pub(crate) const TRANSCRIPT_PARALLEL_STATEMENT: &str = "Parallel Triptych statement";
 
@@ -165,4 +228,10 @@ pub(crate) mod domains {
     pub(crate) const TRANSCRIPT_PARALLEL_PROOF: &str = "Parallel Triptych proof";
     pub(crate) const TRANSCRIPT_VERIFIER_WEIGHTS: &str = "Triptych verifier weights";
     pub(crate) const TRANSCRIPT_PARALLEL_VERIFIER_WEIGHTS: &str = "Parallel Triptych verifier weights";
+    pub(crate) const TRANSCRIPT_FINGERPRINT: &str = "Triptych transcript fingerprint";
+    pub(crate) const AUDIT_DIGEST: &str = "Triptych audit digest";
+    pub(crate) const TRANSCRIPT_PROOF_CONTEXT: &str = "Triptych proof context";
+    pub(crate) const TRANSCRIPT_SIGNATURE: &str = "Triptych signature";
+    #[cfg(feature = "hazmat")]
+    pub(crate) const DETERMINISTIC_PROVER_RNG: &str = "Triptych deterministic prover RNG";
 }
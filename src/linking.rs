@@ -0,0 +1,171 @@
+// Copyright (c) 2024, The Tari Project
+// SPDX-License-Identifier: BSD-3-Clause
+
+use alloc::collections::BTreeSet;
+
+use curve25519_dalek::{ristretto::CompressedRistretto, RistrettoPoint, Scalar};
+use zeroize::Zeroizing;
+
+use crate::{TriptychParameters, TriptychStatement};
+
+/// Compute the linking tag for a signing key `r` using [`TriptychParameters`] `params`.
+///
+/// This is the linking tag `J` satisfying `r*J = U`, and is identical to
+/// [`TriptychWitness::compute_linking_tag`](`crate::TriptychWitness::compute_linking_tag`) for a witness with the same
+/// parameters and signing key. If `r` is zero, this is the identity group element, which is not a valid linking tag.
+///
+/// A linking tag cannot be checked against a verification key without the signing key; this is what prevents a proof
+/// from revealing its signer.
+pub fn linking_tag_for(params: &TriptychParameters, r: &Scalar) -> RistrettoPoint {
+    *Zeroizing::new(r.invert()) * params.get_U()
+}
+
+/// A set of linking tags, used to detect signing keys that are used more than once.
+///
+/// Two verified proofs with the same linking tag were produced using the same signing key. A [`LinkingTagSet`] stores
+/// the compressed encodings of linking tags, so callers can detect such links without managing encodings themselves.
+/// Each linking tag is compressed only once when inserted or checked.
+#[derive(Clone, Debug, Default)]
+pub struct LinkingTagSet {
+    tags: BTreeSet<[u8; 32]>,
+}
+
+impl LinkingTagSet {
+    /// Generate a new empty [`LinkingTagSet`].
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Insert a linking tag `J` into this [`LinkingTagSet`].
+    ///
+    /// Returns `true` if the tag is new, and `false` if it was already present, which indicates a link.
+    #[allow(non_snake_case)]
+    pub fn insert(&mut self, J: &RistrettoPoint) -> bool {
+        self.tags.insert(J.compress().to_bytes())
+    }
+
+    /// Insert a compressed linking tag `J` into this [`LinkingTagSet`].
+    ///
+    /// Returns `true` if the tag is new, and `false` if it was already present, which indicates a link.
+    #[allow(non_snake_case)]
+    pub fn insert_compressed(&mut self, J: &CompressedRistretto) -> bool {
+        self.tags.insert(J.to_bytes())
+    }
+
+    /// Determine if a linking tag `J` is in this [`LinkingTagSet`].
+    #[allow(non_snake_case)]
+    pub fn contains(&self, J: &RistrettoPoint) -> bool {
+        self.tags.contains(J.compress().as_bytes())
+    }
+
+    /// Insert the linking tags of all `statements` into this [`LinkingTagSet`].
+    ///
+    /// All tags are inserted. Returns `true` if every tag was new, and `false` if any tag was already present or
+    /// appeared more than once in `statements`, which indicates a link.
+    pub fn extend_from_statements(&mut self, statements: &[TriptychStatement]) -> bool {
+        let mut all_new = true;
+        for statement in statements {
+            all_new &= self.insert(statement.get_J());
+        }
+
+        all_new
+    }
+
+    /// Get the number of linking tags in this [`LinkingTagSet`].
+    pub fn len(&self) -> usize {
+        self.tags.len()
+    }
+
+    /// Determine if this [`LinkingTagSet`] is empty.
+    pub fn is_empty(&self) -> bool {
+        self.tags.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use alloc::vec::Vec;
+
+    use curve25519_dalek::{traits::IsIdentity, Scalar};
+    use rand_chacha::ChaCha12Rng;
+    use rand_core::SeedableRng;
+
+    use crate::{
+        linking::{linking_tag_for, LinkingTagSet},
+        testing::deterministic_ring,
+        Transcript,
+        TriptychParameters,
+        TriptychProof,
+        TriptychStatement,
+        TriptychWitness,
+    };
+
+    #[test]
+    #[allow(non_snake_case)]
+    fn test_linking_tag_for() {
+        let mut rng = ChaCha12Rng::seed_from_u64(8675309);
+        let params = TriptychParameters::new(2, 2).unwrap();
+
+        // The linking tag matches the witness computation and satisfies the protocol relation
+        let witness = TriptychWitness::random(&params, &mut rng);
+        let J = linking_tag_for(&params, witness.get_r());
+        assert_eq!(J, witness.compute_linking_tag());
+        assert_eq!(witness.get_r() * J, *params.get_U());
+
+        // Parameters with a different `U` produce a different linking tag
+        let other_params = TriptychParameters::new_for_application(2, 2, b"Test application").unwrap();
+        assert_ne!(linking_tag_for(&other_params, witness.get_r()), J);
+
+        // A zero signing key produces the identity
+        assert!(linking_tag_for(&params, &Scalar::ZERO).is_identity());
+    }
+
+    #[test]
+    fn test_linking_tag_set() {
+        let mut rng = ChaCha12Rng::seed_from_u64(8675309);
+        let params = TriptychParameters::new(2, 2).unwrap();
+
+        // Build statements for two independent witnesses, and a second statement for the first witness
+        let witnesses = [
+            TriptychWitness::random(&params, &mut rng),
+            TriptychWitness::random(&params, &mut rng),
+        ];
+        let statements = [(&witnesses[0], 0), (&witnesses[1], 1), (&witnesses[0], 2)]
+            .iter()
+            .map(|(witness, seed)| {
+                let input_set = deterministic_ring(&params, *seed, witness.get_l(), witness.get_r()).unwrap();
+                TriptychStatement::new(&params, &input_set, &witness.compute_linking_tag()).unwrap()
+            })
+            .collect::<Vec<TriptychStatement>>();
+
+        // Each statement has a valid proof
+        for (witness, statement) in [&witnesses[0], &witnesses[1], &witnesses[0]]
+            .iter()
+            .zip(statements.iter())
+        {
+            let mut transcript = Transcript::new(b"Test transcript");
+            let proof = TriptychProof::prove_with_rng(witness, statement, &mut rng, &mut transcript.clone()).unwrap();
+            assert!(proof.verify(statement, &mut transcript).is_ok());
+        }
+
+        // Independent witnesses are not linked
+        let mut tags = LinkingTagSet::new();
+        assert!(tags.is_empty());
+        assert!(tags.insert(statements[0].get_J()));
+        assert!(tags.insert_compressed(&statements[1].get_J().compress()));
+        assert_eq!(tags.len(), 2);
+
+        // The same witness is linked
+        assert!(tags.contains(statements[2].get_J()));
+        assert!(!tags.insert(statements[2].get_J()));
+        assert_eq!(tags.len(), 2);
+
+        // Statements are linked within a set, but all tags are still inserted
+        let mut tags = LinkingTagSet::new();
+        assert!(tags.extend_from_statements(&statements[..2]));
+        let mut tags = LinkingTagSet::new();
+        assert!(!tags.extend_from_statements(&statements));
+        assert_eq!(tags.len(), 2);
+        assert!(!tags.contains(&(Scalar::ONE * params.get_G())));
+    }
+}
@@ -0,0 +1,194 @@
+// Copyright (c) 2024, The Tari Project
+// SPDX-License-Identifier: BSD-3-Clause
+
+use alloc::{vec, vec::Vec};
+
+use blake3::Hasher;
+use curve25519_dalek::RistrettoPoint;
+
+use crate::domains;
+
+// Size of tree node hashes in bytes
+const NODE_BYTES: usize = 32;
+
+/// A binary Merkle tree over verification keys.
+///
+/// Leaves and internal nodes are hashed with `BLAKE3` using distinct domain separators.
+/// If a level has an odd number of nodes, the last node is promoted to the next level unchanged.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub(crate) struct MerkleTree {
+    // All tree levels, starting with the leaves and ending with the root
+    levels: Vec<Vec<[u8; NODE_BYTES]>>,
+}
+
+impl MerkleTree {
+    /// Build a Merkle tree from verification keys `M`.
+    ///
+    /// If `M` is empty, returns `None`.
+    #[allow(non_snake_case)]
+    pub(crate) fn new(M: &[RistrettoPoint]) -> Option<Self> {
+        if M.is_empty() {
+            return None;
+        }
+
+        let mut levels = vec![M.iter().map(Self::hash_leaf).collect::<Vec<[u8; NODE_BYTES]>>()];
+        while let Some(level) = levels.last().filter(|level| level.len() > 1) {
+            let next = level
+                .chunks(2)
+                .map(|pair| match pair {
+                    [left, right] => Self::hash_node(left, right),
+                    _ => pair[0],
+                })
+                .collect::<Vec<[u8; NODE_BYTES]>>();
+            levels.push(next);
+        }
+
+        Some(Self { levels })
+    }
+
+    /// Get the root of the Merkle tree.
+    pub(crate) fn root(&self) -> &[u8; NODE_BYTES] {
+        // The tree always has a nonempty root level
+        &self.levels[self.levels.len().saturating_sub(1)][0]
+    }
+
+    /// Replace the leaf at `index` with a new verification key `item`, updating only the affected nodes.
+    ///
+    /// If `index` is out of range, returns `None`.
+    pub(crate) fn update(&mut self, index: usize, item: &RistrettoPoint) -> Option<()> {
+        *self.levels.first_mut()?.get_mut(index)? = Self::hash_leaf(item);
+
+        let mut index = index;
+        for level in 1..self.levels.len() {
+            index = index.checked_div(2)?;
+
+            // Get the children of the updated node
+            let children = &self.levels[level.checked_sub(1)?];
+            let left_index = index.checked_mul(2)?;
+            let left = children[left_index];
+            let node = match children.get(left_index.checked_add(1)?) {
+                Some(right) => Self::hash_node(&left, right),
+                None => left,
+            };
+
+            self.levels[level][index] = node;
+        }
+
+        Some(())
+    }
+
+    /// Append a new leaf for the verification key `item`, updating only the affected nodes.
+    ///
+    /// Since the last node of an odd-sized level is promoted unchanged, only the last node of each level changes, and
+    /// a new root level is added if the previous root gains a sibling.
+    ///
+    /// If the tree size overflows, returns `None`.
+    pub(crate) fn push(&mut self, item: &RistrettoPoint) -> Option<()> {
+        let mut level = 0usize;
+        self.levels.first_mut()?.push(Self::hash_leaf(item));
+
+        // Update the last node of each level until we reach the root
+        while self.levels[level].len() > 1 {
+            // Get the children of the last node of the next level
+            let children = &self.levels[level];
+            let index = children.len().checked_sub(1)?.checked_div(2)?;
+            let left_index = index.checked_mul(2)?;
+            let left = children[left_index];
+            let node = match children.get(left_index.checked_add(1)?) {
+                Some(right) => Self::hash_node(&left, right),
+                None => left,
+            };
+
+            level = level.checked_add(1)?;
+            if level == self.levels.len() {
+                self.levels.push(Vec::new());
+            }
+            match self.levels[level].get_mut(index) {
+                Some(parent) => *parent = node,
+                None => self.levels[level].push(node),
+            }
+        }
+
+        Some(())
+    }
+
+    /// Hash a verification key into a leaf.
+    fn hash_leaf(item: &RistrettoPoint) -> [u8; NODE_BYTES] {
+        let mut hasher = Hasher::new();
+        hasher.update(domains::MERKLE_LEAF.as_bytes());
+        hasher.update(&domains::VERSION.to_le_bytes());
+        hasher.update(item.compress().as_bytes());
+
+        hasher.finalize().into()
+    }
+
+    /// Hash two child nodes into a parent node.
+    fn hash_node(left: &[u8; NODE_BYTES], right: &[u8; NODE_BYTES]) -> [u8; NODE_BYTES] {
+        let mut hasher = Hasher::new();
+        hasher.update(domains::MERKLE_NODE.as_bytes());
+        hasher.update(&domains::VERSION.to_le_bytes());
+        hasher.update(left);
+        hasher.update(right);
+
+        hasher.finalize().into()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use alloc::{vec, vec::Vec};
+
+    use curve25519_dalek::RistrettoPoint;
+    use rand_chacha::ChaCha12Rng;
+    use rand_core::SeedableRng;
+
+    use crate::merkle::MerkleTree;
+
+    #[test]
+    #[allow(non_snake_case)]
+    fn test_update() {
+        let mut rng = ChaCha12Rng::seed_from_u64(8675309);
+
+        // Check trees of even and odd size
+        for size in [1, 2, 5, 8] {
+            let mut M = (0..size)
+                .map(|_| RistrettoPoint::random(&mut rng))
+                .collect::<Vec<RistrettoPoint>>();
+            let mut tree = MerkleTree::new(&M).unwrap();
+
+            // Updating each leaf matches rebuilding the tree
+            for index in 0..size {
+                let root = *tree.root();
+                M[index] = RistrettoPoint::random(&mut rng);
+                tree.update(index, &M[index]).unwrap();
+
+                assert_ne!(tree.root(), &root);
+                assert_eq!(tree, MerkleTree::new(&M).unwrap());
+            }
+
+            // Out-of-range updates fail
+            assert!(tree.update(size, &M[0]).is_none());
+        }
+
+        // Empty trees are not allowed
+        assert!(MerkleTree::new(&[]).is_none());
+    }
+
+    #[test]
+    #[allow(non_snake_case)]
+    fn test_push() {
+        let mut rng = ChaCha12Rng::seed_from_u64(8675309);
+
+        // Appending each leaf matches rebuilding the tree, for trees of every size up to a few levels
+        let mut M = vec![RistrettoPoint::random(&mut rng)];
+        let mut tree = MerkleTree::new(&M).unwrap();
+        for _ in 0..17 {
+            let root = *tree.root();
+            M.push(RistrettoPoint::random(&mut rng));
+            tree.push(&M[M.len() - 1]).unwrap();
+
+            assert_ne!(tree.root(), &root);
+            assert_eq!(tree, MerkleTree::new(&M).unwrap());
+        }
+    }
+}
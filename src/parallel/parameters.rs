@@ -1,8 +1,8 @@
 // Copyright (c) 2024, The Tari Project
 // SPDX-License-Identifier: BSD-3-Clause
 
-use alloc::{sync::Arc, vec, vec::Vec};
-use core::iter::once;
+use alloc::{format, string::String, sync::Arc, vec, vec::Vec};
+use core::{hash::Hash, iter::once};
 
 use blake3::Hasher;
 use curve25519_dalek::{
@@ -13,7 +13,13 @@ use curve25519_dalek::{
 };
 use snafu::prelude::*;
 
-use crate::{domains, util::OperationTiming, Transcript};
+use crate::{
+    domains,
+    parallel::TriptychProof,
+    parameters::MAX_EXPONENT,
+    util::{derive_application_U, generator_hasher, generator_seed_prefix, small_multiple_relation, OperationTiming},
+    Transcript,
+};
 
 /// Public parameters used for generating and verifying Triptych proofs.
 ///
@@ -21,8 +27,12 @@ use crate::{domains, util::OperationTiming, Transcript};
 /// `G`, `G1`, and `U` required by the protocol. You can either use [`TriptychParameters::new`] to have these generators
 /// defined securely for you, or use [`TriptychParameters::new_with_generators`] if your use case requires specific
 /// values for these.
+///
+/// Linking tags are defined using `U`, so a signing key produces the same linking tag for all parameters sharing `U`.
+/// If linking tags must not be linkable across applications, use [`TriptychParameters::new_for_application`] to
+/// derive `U` from an application identifier.
 #[allow(non_snake_case)]
-#[derive(Clone, Eq, PartialEq)]
+#[derive(Clone)]
 pub struct TriptychParameters {
     n: u32,
     m: u32,
@@ -43,6 +53,12 @@ pub enum ParameterError {
         /// The reason for the parameter error.
         reason: &'static str,
     },
+    /// The size `n**m` exceeded a maximum.
+    #[snafu(display("The size `n**m` exceeded the maximum of {maximum}"))]
+    ExceededMaximumSize {
+        /// The maximum size.
+        maximum: u32,
+    },
 }
 
 impl TriptychParameters {
@@ -57,26 +73,141 @@ impl TriptychParameters {
     pub fn new(n: u32, m: u32) -> Result<Self, ParameterError> {
         // Use the default base point for `G` (this is arbitrary)
         let G = RISTRETTO_BASEPOINT_POINT;
+        let G1 = Self::default_G1();
+        let U = Self::default_U();
+
+        Self::new_with_generators(n, m, &G, &G1, &U)
+    }
 
+    /// Produce the default group generator `G1` used by [`TriptychParameters::new`].
+    #[allow(non_snake_case)]
+    fn default_G1() -> RistrettoPoint {
+        Self::derive_G1(None)
+    }
+
+    /// Produce the group generator `G1`, using an optional seed `prefix`.
+    #[allow(non_snake_case)]
+    fn derive_G1(prefix: Option<&[u8; 32]>) -> RistrettoPoint {
         // Use `BLAKE3` to generate `G1`
         let mut G1_bytes = [0u8; 64];
-        let mut hasher = Hasher::new();
-        hasher.update(domains::POINT_G1.as_bytes());
-        hasher.update(&domains::VERSION.to_le_bytes());
-        hasher.finalize_xof().fill(&mut G1_bytes);
-        let G1 = RistrettoPoint::from_uniform_bytes(&G1_bytes);
+        generator_hasher(domains::POINT_G1, prefix)
+            .finalize_xof()
+            .fill(&mut G1_bytes);
 
+        RistrettoPoint::from_uniform_bytes(&G1_bytes)
+    }
+
+    /// Produce the default group generator `U` used by [`TriptychParameters::new`].
+    #[allow(non_snake_case)]
+    fn default_U() -> RistrettoPoint {
+        Self::derive_U(None)
+    }
+
+    /// Produce the group generator `U`, using an optional seed `prefix`.
+    #[allow(non_snake_case)]
+    fn derive_U(prefix: Option<&[u8; 32]>) -> RistrettoPoint {
         // Use `BLAKE3` to generate `U`
         let mut U_bytes = [0u8; 64];
-        let mut hasher = Hasher::new();
-        hasher.update(domains::POINT_U.as_bytes());
-        hasher.update(&domains::VERSION.to_le_bytes());
-        hasher.finalize_xof().fill(&mut U_bytes);
-        let U = RistrettoPoint::from_uniform_bytes(&U_bytes);
+        generator_hasher(domains::POINT_U, prefix)
+            .finalize_xof()
+            .fill(&mut U_bytes);
+
+        RistrettoPoint::from_uniform_bytes(&U_bytes)
+    }
+
+    /// Generate new [`TriptychParameters`] for Triptych proofs with a bounded size.
+    ///
+    /// This is identical to [`TriptychParameters::new`], except that it additionally checks that `N = n**m` does not
+    /// exceed `max_N`. If it does, returns [`ParameterError::ExceededMaximumSize`]; this check is made before any
+    /// generators are produced.
+    #[allow(non_snake_case)]
+    pub fn new_bounded(n: u32, m: u32, max_N: u32) -> Result<Self, ParameterError> {
+        // Invalid parameters are caught by the unbounded constructor
+        if n.checked_pow(m).is_some_and(|N| N > max_N) {
+            return Err(ParameterError::ExceededMaximumSize { maximum: max_N });
+        }
+
+        Self::new(n, m)
+    }
+
+    /// Generate new [`TriptychParameters`] for verifying a serialized Triptych proof.
+    ///
+    /// This reads the base `n` and exponent `m` encoded in `bytes`, which must begin with the canonical encoding
+    /// produced by [`TriptychProof::to_bytes`](`crate::parallel::proof::TriptychProof::to_bytes`), and produces
+    /// parameters with the same group generators as [`TriptychParameters::new`]. It is only useful if the prover
+    /// also used those generators.
+    ///
+    /// Producing parameters derives `n * m` group generators, and verification takes time linear in `N = n**m`, so a
+    /// proof encoding could otherwise force a large amount of work before the proof is checked. As with
+    /// [`TriptychParameters::new_bounded`], if `N` exceeds `max_N`, returns [`ParameterError::ExceededMaximumSize`]
+    /// before any generators are produced.
+    ///
+    /// The rest of the proof is not decoded or checked. If the encoded values are not valid, returns a
+    /// [`ParameterError`].
+    #[allow(non_snake_case)]
+    pub fn from_proof_bytes(bytes: &[u8], max_N: u32) -> Result<Self, ParameterError> {
+        let mut dimensions = bytes.chunks_exact(4).take(2).map(|chunk| {
+            let mut dimension_bytes = [0u8; 4];
+            dimension_bytes.copy_from_slice(chunk);
+            u32::from_le_bytes(dimension_bytes)
+        });
+        let (n_minus_1, m) = dimensions
+            .next()
+            .zip(dimensions.next())
+            .ok_or(ParameterError::InvalidParameter {
+                reason: "proof encoding was too short",
+            })?;
+        let n = n_minus_1.checked_add(1).ok_or(ParameterError::InvalidParameter {
+            reason: "`n` overflowed `u32`",
+        })?;
+
+        Self::new_bounded(n, m, max_N)
+    }
+
+    /// Generate new [`TriptychParameters`] for Triptych proofs that are specific to an application.
+    ///
+    /// The base `n > 1` and exponent `m > 1` define the size of verification key vectors, so it must be the case that
+    /// `n**m` does not overflow [`prim@u32`]. If any of these conditions is not met, returns a [`ParameterError`].
+    ///
+    /// This function produces group generators `G`, `G1`, and `U` for you. The generator `G` is the same as for
+    /// [`TriptychParameters::new`], so verification keys can be shared between applications. The generator `U` is
+    /// derived from the application identifier `app_id` using `BLAKE3`. Since linking tags are defined using `U`, the
+    /// same signing key produces linking tags that cannot be linked across applications with distinct identifiers.
+    #[allow(non_snake_case)]
+    pub fn new_for_application(n: u32, m: u32, app_id: &[u8]) -> Result<Self, ParameterError> {
+        // Use the default base point for `G` (this is arbitrary)
+        let G = RISTRETTO_BASEPOINT_POINT;
+        let G1 = Self::default_G1();
+
+        // Use `BLAKE3` to generate `U` from the application identifier
+        let U = derive_application_U(app_id);
 
         Self::new_with_generators(n, m, &G, &G1, &U)
     }
 
+    /// Generate new [`TriptychParameters`] for Triptych proofs with generators derived from a seed.
+    ///
+    /// The base `n > 1` and exponent `m > 1` define the size of verification key vectors, so it must be the case that
+    /// `n**m` does not overflow [`prim@u32`]. If any of these conditions is not met, returns a [`ParameterError`].
+    ///
+    /// This function produces group generators for you. The generator `G` is the same as for
+    /// [`TriptychParameters::new`], so verification keys can be shared. The generators `G1` and `U`, as well as all
+    /// commitment generators, are derived from `seed` using `BLAKE3`. The same seed always produces the same
+    /// parameters, while distinct seeds produce independent generators. Since linking tags are defined using `U`, the
+    /// same signing key produces linking tags that cannot be linked across distinct seeds.
+    #[allow(non_snake_case)]
+    pub fn new_with_seed(n: u32, m: u32, seed: &[u8]) -> Result<Self, ParameterError> {
+        // Use the default base point for `G` (this is arbitrary)
+        let G = RISTRETTO_BASEPOINT_POINT;
+
+        // Every other generator derivation is prefixed by the seed
+        let prefix = generator_seed_prefix(seed);
+        let G1 = Self::derive_G1(Some(&prefix));
+        let U = Self::derive_U(Some(&prefix));
+
+        Self::new_internal(n, m, &G, &G1, &U, Some(&prefix))
+    }
+
     /// Generate new [`TriptychParameters`] for Triptych proofs.
     ///
     /// The base `n > 1` and exponent `m > 1` define the size of verification key vectors, so it must be the case that
@@ -96,6 +227,19 @@ impl TriptychParameters {
         G: &RistrettoPoint,
         G1: &RistrettoPoint,
         U: &RistrettoPoint,
+    ) -> Result<Self, ParameterError> {
+        Self::new_internal(n, m, G, G1, U, None)
+    }
+
+    /// Generate new [`TriptychParameters`], deriving commitment generators using an optional seed `prefix`.
+    #[allow(non_snake_case)]
+    fn new_internal(
+        n: u32,
+        m: u32,
+        G: &RistrettoPoint,
+        G1: &RistrettoPoint,
+        U: &RistrettoPoint,
+        prefix: Option<&[u8; 32]>,
     ) -> Result<Self, ParameterError> {
         // These bounds are required by the protocol
         if n < 2 {
@@ -114,16 +258,13 @@ impl TriptychParameters {
 
         // Use `BLAKE3` to generate `CommitmentH`
         let mut CommitmentH_bytes = [0u8; 64];
-        let mut hasher = Hasher::new();
-        hasher.update(domains::POINT_COMMITMENT_H.as_bytes());
-        hasher.update(&domains::VERSION.to_le_bytes());
-        hasher.finalize_xof().fill(&mut CommitmentH_bytes);
+        generator_hasher(domains::POINT_COMMITMENT_H, prefix)
+            .finalize_xof()
+            .fill(&mut CommitmentH_bytes);
         let CommitmentH = RistrettoPoint::from_uniform_bytes(&CommitmentH_bytes);
 
         // Use `BLAKE3` for the commitment matrix generators
-        let mut hasher = Hasher::new();
-        hasher.update(domains::POINT_COMMITMENT_G.as_bytes());
-        hasher.update(&domains::VERSION.to_le_bytes());
+        let mut hasher = generator_hasher(domains::POINT_COMMITMENT_G, prefix);
         hasher.update(&n.to_le_bytes());
         hasher.update(&m.to_le_bytes());
         let mut hasher_xof = hasher.finalize_xof();
@@ -138,6 +279,65 @@ impl TriptychParameters {
             .collect::<Vec<RistrettoPoint>>();
 
         // Use Merlin for the transcript hash
+        let hash = Self::compute_hash(n, m, G, G1, U, &CommitmentG, &CommitmentH);
+
+        Ok(TriptychParameters {
+            n,
+            m,
+            G: *G,
+            G1: *G1,
+            U: *U,
+            CommitmentG: Arc::new(CommitmentG),
+            CommitmentH,
+            hash,
+        })
+    }
+
+    /// Perform a best-effort check that the group generators of these [`TriptychParameters`] are not trivially related.
+    ///
+    /// The generators `G`, `G1` and `U` must be independent, which cannot be checked. However, this checks for gross
+    /// misuse: no generator may be the identity, or a small multiple (or negated small multiple) of another
+    /// generator, of the commitment generator `CommitmentH`, or of the default generator that
+    /// [`TriptychParameters::new`] uses for a different role. If any such relation is found, returns a
+    /// [`ParameterError`].
+    ///
+    /// Passing this check does not mean that the generators are independent!
+    pub fn check_generators(&self) -> Result<(), ParameterError> {
+        let generators = [self.G, self.G1, self.U];
+        let defaults = [RISTRETTO_BASEPOINT_POINT, Self::default_G1(), Self::default_U()];
+
+        for (i, generator) in generators.iter().enumerate() {
+            let related = generators
+                .iter()
+                .skip(i)
+                .skip(1)
+                .any(|other| small_multiple_relation(generator, other)) ||
+                small_multiple_relation(generator, &self.CommitmentH) ||
+                defaults
+                    .iter()
+                    .enumerate()
+                    .any(|(j, default)| i != j && small_multiple_relation(generator, default));
+            if related {
+                return Err(ParameterError::InvalidParameter {
+                    reason: "group generators were trivially related",
+                });
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Compute a cryptographic hash representation of parameter data using Merlin.
+    #[allow(non_snake_case)]
+    fn compute_hash(
+        n: u32,
+        m: u32,
+        G: &RistrettoPoint,
+        G1: &RistrettoPoint,
+        U: &RistrettoPoint,
+        CommitmentG: &[RistrettoPoint],
+        CommitmentH: &RistrettoPoint,
+    ) -> Vec<u8> {
         let mut transcript = Transcript::new(domains::TRANSCRIPT_PARALLEL_PARAMETERS.as_bytes());
         transcript.append_u64(b"version", domains::VERSION);
         transcript.append_message(b"n", &n.to_le_bytes());
@@ -145,23 +345,47 @@ impl TriptychParameters {
         transcript.append_message(b"G", G.compress().as_bytes());
         transcript.append_message(b"G1", G1.compress().as_bytes());
         transcript.append_message(b"U", U.compress().as_bytes());
-        for item in &CommitmentG {
+        for item in CommitmentG {
             transcript.append_message(b"CommitmentG", item.compress().as_bytes());
         }
         transcript.append_message(b"CommitmentH", CommitmentH.compress().as_bytes());
         let mut hash = vec![0u8; domains::TRANSCRIPT_HASH_BYTES];
         transcript.challenge_bytes(b"hash", &mut hash);
 
-        Ok(TriptychParameters {
-            n,
-            m,
-            G: *G,
-            G1: *G1,
-            U: *U,
-            CommitmentG: Arc::new(CommitmentG),
-            CommitmentH,
-            hash,
-        })
+        hash
+    }
+
+    /// Generate new [`TriptychParameters`] from these, with the group generator `U` rotated for a given `epoch`.
+    ///
+    /// The new generator `U` is derived from the existing one and `epoch` using `BLAKE3`, while all other generators
+    /// are kept. Since linking tags are defined using `U`, a signing key produces a different linking tag for each
+    /// epoch, and linking tags from different epochs cannot be linked to each other. The resulting parameters have a
+    /// distinct hash, so proofs are bound to their epoch.
+    #[allow(non_snake_case)]
+    pub fn with_rotated_U(&self, epoch: u64) -> Self {
+        // Use `BLAKE3` to generate the rotated `U`
+        let mut U_bytes = [0u8; 64];
+        let mut hasher = Hasher::new();
+        hasher.update(domains::POINT_U_ROTATION.as_bytes());
+        hasher.update(&domains::VERSION.to_le_bytes());
+        hasher.update(self.U.compress().as_bytes());
+        hasher.update(&epoch.to_le_bytes());
+        hasher.finalize_xof().fill(&mut U_bytes);
+        let U = RistrettoPoint::from_uniform_bytes(&U_bytes);
+
+        Self {
+            U,
+            hash: Self::compute_hash(
+                self.n,
+                self.m,
+                &self.G,
+                &self.G1,
+                &U,
+                &self.CommitmentG,
+                &self.CommitmentH,
+            ),
+            ..self.clone()
+        }
     }
 
     /// Commit to a matrix.
@@ -243,6 +467,103 @@ impl TriptychParameters {
         self.n.pow(self.m)
     }
 
+    /// Get the largest ring size supported by these [`TriptychParameters`].
+    ///
+    /// This is the verification key vector size `N`; smaller rings must be padded to this size. Use
+    /// [`TriptychParameters::suggest`] to choose parameters for a target ring size.
+    pub fn max_ring_size(&self) -> u32 {
+        self.get_N()
+    }
+
+    /// Suggest a base `n` and exponent `m` whose size `N == n**m` is at least `target_N`.
+    ///
+    /// Of all valid choices covering `target_N`, this returns the one with the smallest serialized proof size, as
+    /// given by [`TriptychProof::serialized_size_for`]. Ties are broken by the smallest `N`, which is cheaper to
+    /// verify. Returns `None` if no valid choice covers `target_N`.
+    #[allow(non_snake_case)]
+    pub fn suggest(target_N: u32) -> Option<(u32, u32)> {
+        // Track the best choice as `(size, N, n, m)`
+        let mut best: Option<(usize, u32, u32, u32)> = None;
+
+        for n in 2..=u32::MAX {
+            // If even `m == 2` overflows, no larger base is valid
+            if n.checked_pow(2).is_none() {
+                break;
+            }
+
+            // Find the smallest exponent that covers the target, if any
+            let Some((m, N)) = (2..=MAX_EXPONENT)
+                .map_while(|m| n.checked_pow(m).map(|N| (m, N)))
+                .find(|(_, N)| *N >= target_N)
+            else {
+                continue;
+            };
+
+            let size = TriptychProof::serialized_size_for(n, m)?;
+            if best.map_or(true, |(best_size, best_N, _, _)| (size, N) < (best_size, best_N)) {
+                best = Some((size, N, n, m));
+            }
+
+            // Larger bases can only increase the proof size once the smallest exponent is reached
+            if m == 2 {
+                break;
+            }
+        }
+
+        best.map(|(_, _, n, m)| (n, m))
+    }
+
+    /// Get the largest number of proofs that can be verified in a single batch using these [`TriptychParameters`].
+    ///
+    /// Batch verification uses a final check whose size grows with the batch size, and this size must not overflow
+    /// [`prim@usize`] on the current target. Larger batches are rejected by the verifier, and should be split.
+    pub fn max_batch_size(&self) -> usize {
+        self.batch_check_sizes()
+            .and_then(|(common, per_proof)| usize::MAX.checked_sub(common)?.checked_div(per_proof))
+            .unwrap_or(0)
+    }
+
+    /// Get the size of the final batch verification check for `batch_size` proofs.
+    ///
+    /// Returns `None` if the size overflows [`prim@usize`].
+    pub(crate) fn batch_check_size(&self, batch_size: usize) -> Option<usize> {
+        let (common, per_proof) = self.batch_check_sizes()?;
+
+        batch_size.checked_mul(per_proof)?.checked_add(common)
+    }
+
+    /// Get the number of common and per-proof elements in the final batch verification check.
+    #[allow(non_snake_case)]
+    fn batch_check_sizes(&self) -> Option<(usize, usize)> {
+        let n = self.n as usize;
+        let m = self.m as usize;
+        let N = self.get_N() as usize;
+
+        let common = n
+            .checked_mul(m)? // CommitmentG
+            .checked_add(N.checked_mul(2)?)? // M, M1
+            .checked_add(4)?; // G, H, CommitmentH, U
+        let per_proof = m
+            .checked_mul(3)? // X, X1, Y
+            .checked_add(6)?; // A, B, C, D, offset, J
+
+        Some((common, per_proof))
+    }
+
+    /// Get a short descriptor that identifies these [`TriptychParameters`], suitable for logging.
+    ///
+    /// The descriptor has the form `triptych-parallel-n2-m8-N256-0123abcd`, and includes the base `n`, exponent `m`,
+    /// size `N`, and the first four bytes of the parameter hash in hexadecimal. Since the hash commits to all
+    /// generators, parameters with the same dimensions but different generators have different descriptors.
+    pub fn descriptor(&self) -> String {
+        let mut descriptor = format!("triptych-parallel-n{}-m{}-N{}-", self.n, self.m, self.get_N());
+        for byte in self.hash.iter().take(4) {
+            descriptor.push_str(&format!("{byte:02x}"));
+        }
+
+        descriptor
+    }
+
     /// Get the value `CommitmentG` from these [`TriptychParameters`].
     #[allow(non_snake_case)]
     pub(crate) fn get_CommitmentG(&self) -> &Vec<RistrettoPoint> {
@@ -259,4 +580,149 @@ impl TriptychParameters {
     pub(crate) fn get_hash(&self) -> &[u8] {
         &self.hash
     }
+
+    /// Determine if these [`TriptychParameters`] are equal to `other` by comparing only their hashes.
+    ///
+    /// The hash binds the dimensions and every group generator, so equal hashes imply equal parameters under the
+    /// collision resistance of the hash. This avoids comparing every commitment generator, and is how the `==`
+    /// operator is implemented.
+    pub fn eq_fast(&self, other: &Self) -> bool {
+        self.hash == other.hash
+    }
+}
+
+impl PartialEq for TriptychParameters {
+    fn eq(&self, other: &Self) -> bool {
+        self.eq_fast(other)
+    }
+}
+
+impl Eq for TriptychParameters {}
+
+impl Hash for TriptychParameters {
+    /// Hash only the precomputed parameter hash, which binds all of its data.
+    ///
+    /// This is consistent with equality, and relies on the collision resistance of the precomputed hash.
+    fn hash<H: core::hash::Hasher>(&self, state: &mut H) {
+        self.hash.hash(state);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use curve25519_dalek::{constants::RISTRETTO_BASEPOINT_POINT, traits::Identity, RistrettoPoint};
+    use rand_chacha::ChaCha12Rng;
+    use rand_core::SeedableRng;
+
+    use crate::parallel::{parameters::ParameterError, TriptychParameters};
+
+    #[test]
+    #[allow(non_snake_case)]
+    fn test_with_rotated_U() {
+        let params = TriptychParameters::new(2, 4).unwrap();
+
+        // Rotation is deterministic
+        let params_1 = params.with_rotated_U(1);
+        assert!(params_1 == params.with_rotated_U(1));
+
+        // Rotation changes only `U` and the hash
+        assert_eq!(params_1.get_G(), params.get_G());
+        assert_eq!(params_1.get_G1(), params.get_G1());
+        assert_eq!(params_1.get_CommitmentG(), params.get_CommitmentG());
+        assert_eq!(params_1.get_CommitmentH(), params.get_CommitmentH());
+        assert_ne!(params_1.get_U(), params.get_U());
+        assert_ne!(params_1.get_hash(), params.get_hash());
+
+        // Each epoch is distinct
+        let params_2 = params.with_rotated_U(2);
+        assert_ne!(params_1.get_U(), params_2.get_U());
+        assert_ne!(params_1.get_hash(), params_2.get_hash());
+
+        // The hash is computed as if the generators were supplied directly
+        assert!(
+            params_1 ==
+                TriptychParameters::new_with_generators(2, 4, params_1.get_G(), params_1.get_G1(), params_1.get_U())
+                    .unwrap()
+        );
+    }
+
+    #[test]
+    fn test_new_bounded() {
+        // Sizes up to the bound are accepted, and match unbounded parameters
+        assert!(TriptychParameters::new_bounded(2, 4, 16).unwrap() == TriptychParameters::new(2, 4).unwrap());
+        assert!(TriptychParameters::new_bounded(2, 4, 17).is_ok());
+
+        // Sizes exceeding the bound are rejected with a distinct error
+        assert!(matches!(
+            TriptychParameters::new_bounded(2, 5, 16),
+            Err(ParameterError::ExceededMaximumSize { maximum: 16 })
+        ));
+
+        // Invalid parameters are still rejected
+        assert!(matches!(
+            TriptychParameters::new_bounded(1, 4, 16),
+            Err(ParameterError::InvalidParameter { .. })
+        ));
+        assert!(matches!(
+            TriptychParameters::new_bounded(2, 32, u32::MAX),
+            Err(ParameterError::InvalidParameter { .. })
+        ));
+    }
+
+    #[test]
+    #[allow(non_snake_case)]
+    fn test_check_generators() {
+        let mut rng = ChaCha12Rng::seed_from_u64(8675309);
+
+        // Default generators pass
+        let params = TriptychParameters::new(2, 4).unwrap();
+        assert!(params.check_generators().is_ok());
+
+        // Random generators pass
+        let G = RistrettoPoint::random(&mut rng);
+        let G1 = RistrettoPoint::random(&mut rng);
+        let U = RistrettoPoint::random(&mut rng);
+        assert!(TriptychParameters::new_with_generators(2, 4, &G, &G1, &U)
+            .unwrap()
+            .check_generators()
+            .is_ok());
+
+        // A small multiple of another generator fails
+        assert!(TriptychParameters::new_with_generators(2, 4, &G, &G1, &(G + G))
+            .unwrap()
+            .check_generators()
+            .is_err());
+        assert!(TriptychParameters::new_with_generators(2, 4, &G, &-G, &U)
+            .unwrap()
+            .check_generators()
+            .is_err());
+        assert!(TriptychParameters::new_with_generators(2, 4, &G, &G1, &G1)
+            .unwrap()
+            .check_generators()
+            .is_err());
+
+        // The identity fails
+        assert!(
+            TriptychParameters::new_with_generators(2, 4, &G, &RistrettoPoint::identity(), &U)
+                .unwrap()
+                .check_generators()
+                .is_err()
+        );
+
+        // A default generator in a different role fails
+        assert!(
+            TriptychParameters::new_with_generators(2, 4, &G, &G1, &RISTRETTO_BASEPOINT_POINT)
+                .unwrap()
+                .check_generators()
+                .is_err()
+        );
+        assert!(TriptychParameters::new_with_generators(2, 4, params.get_G1(), &G1, &U)
+            .unwrap()
+            .check_generators()
+            .is_err());
+        assert!(TriptychParameters::new_with_generators(2, 4, &G, params.get_U(), &U)
+            .unwrap()
+            .check_generators()
+            .is_err());
+    }
 }
@@ -1,7 +1,7 @@
 // Copyright (c) 2024, The Tari Project
 // SPDX-License-Identifier: BSD-3-Clause
 
-use alloc::{vec, vec::Vec};
+use alloc::{collections::BTreeMap, vec, vec::Vec};
 use core::{iter::once, slice, slice::ChunksExact};
 
 #[cfg(feature = "borsh")]
@@ -21,10 +21,12 @@ use subtle::{ConditionallySelectable, ConstantTimeEq};
 use zeroize::Zeroizing;
 
 use crate::{
+    batch::{BatchScalars, ProofResponses},
     domains,
     gray::GrayIterator,
-    parallel::{transcript::ProofTranscript, TriptychStatement, TriptychWitness},
-    util::{delta, NullRng, OperationTiming},
+    parallel::{transcript::ProofTranscript, TriptychParameters, TriptychStatement, TriptychWitness},
+    parameters::MAX_EXPONENT,
+    util::{decode_varint, delta, encode_varint, NullRng, OperationTiming},
     Transcript,
 };
 
@@ -59,6 +61,20 @@ pub enum ProofError {
         /// The reason for the parameter error.
         reason: &'static str,
     },
+    /// The witness parameters did not match those of the statement.
+    #[snafu(display("The witness parameters did not match those of the statement"))]
+    ParameterMismatch,
+    /// The witness signing key did not match the verification key at its index in the input set.
+    #[snafu(display("The witness signing key did not match the verification key at its index in the input set"))]
+    WitnessKeyMismatch,
+    /// The witness auxiliary signing key did not match the auxiliary verification key at its index in the input set.
+    #[snafu(display(
+        "The witness auxiliary signing key did not match the auxiliary verification key at its index in the input set"
+    ))]
+    AuxiliaryKeyMismatch,
+    /// The witness signing key did not match the statement linking tag.
+    #[snafu(display("The witness signing key did not match the statement linking tag"))]
+    LinkingTagMismatch,
     /// A transcript challenge was invalid.
     #[snafu(display("A transcript challenge was invalid"))]
     InvalidChallenge,
@@ -71,6 +87,14 @@ pub enum ProofError {
     /// Batch proof verification failed.
     #[snafu[display("Batch proof verification failed")]]
     FailedBatchVerification,
+    /// Two statements in a batch shared a linking tag.
+    #[snafu(display("Statements {first} and {second} shared a linking tag"))]
+    DuplicateLinkingTag {
+        /// The index of the first statement with the linking tag.
+        first: usize,
+        /// The index of the second statement with the linking tag.
+        second: usize,
+    },
     /// Batch proof verification failed.
     #[snafu[display("Batch proof verification failed")]]
     FailedBatchVerificationWithSingleBlame {
@@ -167,6 +191,30 @@ impl TriptychProof {
         Self::prove_internal(witness, statement, rng, transcript, OperationTiming::Constant)
     }
 
+    /// Generate a Triptych [`TriptychProof`] with a selectable [`OperationTiming`] `timing`.
+    ///
+    /// The proof is generated by supplying a [`TriptychWitness`] `witness` and corresponding [`TriptychStatement`]
+    /// `statement`. If the witness and statement do not share the same parameters, or if the statement is invalid
+    /// for the witness, returns a [`ProofError`].
+    ///
+    /// You must also supply a [`CryptoRngCore`] random number generator `rng` and a [`Transcript`] `transcript`.
+    ///
+    /// Using [`OperationTiming::Constant`] is equivalent to [`TriptychProof::prove_with_rng`], and using
+    /// [`OperationTiming::Variable`] is equivalent to [`TriptychProof::prove_with_rng_vartime`].
+    ///
+    /// This is only available with the `hazmat` feature, since it can select variable-time proving, which may leak
+    /// secret data through timing side channels.
+    #[cfg(feature = "hazmat")]
+    pub fn prove_with_timing<R: CryptoRngCore>(
+        witness: &TriptychWitness,
+        statement: &TriptychStatement,
+        rng: &mut R,
+        transcript: &mut Transcript,
+        timing: OperationTiming,
+    ) -> Result<Self, ProofError> {
+        Self::prove_internal(witness, statement, rng, transcript, timing)
+    }
+
     /// The actual prover functionality.
     #[allow(clippy::too_many_lines, non_snake_case)]
     fn prove_internal<R: CryptoRngCore>(
@@ -178,9 +226,7 @@ impl TriptychProof {
     ) -> Result<Self, ProofError> {
         // Check that the witness and statement have identical parameters
         if witness.get_params() != statement.get_params() {
-            return Err(ProofError::InvalidParameter {
-                reason: "witness and statement parameters did not match",
-            });
+            return Err(ProofError::ParameterMismatch);
         }
 
         // Extract values for convenience
@@ -211,17 +257,13 @@ impl TriptychProof {
         }
 
         if M_l != r * params.get_G() {
-            return Err(ProofError::InvalidParameter {
-                reason: "`M[l] != r * G`",
-            });
+            return Err(ProofError::WitnessKeyMismatch);
         }
         if M1_l - offset != r1 * params.get_G1() {
-            return Err(ProofError::InvalidParameter {
-                reason: "`M1[l] - offset != r1 * G1`",
-            });
+            return Err(ProofError::AuxiliaryKeyMismatch);
         }
         if &(r * J) != params.get_U() {
-            return Err(ProofError::InvalidParameter { reason: "`r * J != U`" });
+            return Err(ProofError::LinkingTagMismatch);
         }
 
         // Set up the transcript
@@ -442,6 +484,45 @@ impl TriptychProof {
         })
     }
 
+    /// Check that this [`TriptychProof`] has dimensions matching the given [`TriptychParameters`].
+    ///
+    /// This runs the same structural checks that verification performs up front, and is useful for validating a proof
+    /// in isolation before adding it to a batch. It does not check that the proof is valid.
+    ///
+    /// If the proof dimensions do not match, returns a [`ProofError`].
+    pub fn validate(&self, params: &TriptychParameters) -> Result<(), ProofError> {
+        if self.X.len() != params.get_m() as usize {
+            return Err(ProofError::InvalidParameter {
+                reason: "proof `X` vector length was not `m`",
+            });
+        }
+        if self.X1.len() != params.get_m() as usize {
+            return Err(ProofError::InvalidParameter {
+                reason: "proof `X1` vector length was not `m`",
+            });
+        }
+        if self.Y.len() != params.get_m() as usize {
+            return Err(ProofError::InvalidParameter {
+                reason: "proof `Y` vector length was not `m`",
+            });
+        }
+        if self.f.len() != params.get_m() as usize {
+            return Err(ProofError::InvalidParameter {
+                reason: "proof `f` matrix did not have `m` rows",
+            });
+        }
+        let columns = params.get_n().checked_sub(1).ok_or(ProofError::InvalidParameter {
+            reason: "proof `f` matrix column count overflowed",
+        })? as usize;
+        if self.f.iter().any(|f_row| f_row.len() != columns) {
+            return Err(ProofError::InvalidParameter {
+                reason: "proof `f` matrix did not have `n - 1` columns",
+            });
+        }
+
+        Ok(())
+    }
+
     /// Verify a Triptych [`TriptychProof`].
     ///
     /// Verification requires that the `statement` and `transcript` match those used when the proof was generated.
@@ -456,6 +537,15 @@ impl TriptychProof {
         )
     }
 
+    /// Verify a Triptych [`TriptychProof`], returning only whether verification succeeded.
+    ///
+    /// This is a convenience for callers who do not need to distinguish between failure reasons; any [`ProofError`]
+    /// that [`TriptychProof::verify`] would return, including one caused by malformed parameters, produces `false`.
+    #[must_use]
+    pub fn verify_bool(&self, statement: &TriptychStatement, transcript: &mut Transcript) -> bool {
+        self.verify(statement, transcript).is_ok()
+    }
+
     /// Verify a batch of Triptych [`TriptychProofs`](`TriptychProof`), identifying a single invalid proof if
     /// verification fails.
     ///
@@ -574,6 +664,77 @@ impl TriptychProof {
         Err(ProofError::FailedBatchVerificationWithFullBlame { indexes: failures })
     }
 
+    /// Verify a batch of Triptych [`TriptychProofs`](`TriptychProof`), collecting linking tags on success.
+    ///
+    /// This has the same requirements as [`TriptychProof::verify_batch`].
+    /// If the entire batch is valid, the compressed linking tags of all `statements` are appended to `sink` in order.
+    /// Otherwise, returns a [`ProofError`] and leaves `sink` unchanged, so only tags from verified proofs are
+    /// collected.
+    pub fn verify_batch_collect_tags(
+        statements: &[TriptychStatement],
+        proofs: &[TriptychProof],
+        transcripts: &mut [Transcript],
+        sink: &mut impl Extend<CompressedRistretto>,
+    ) -> Result<(), ProofError> {
+        Self::verify_batch(statements, proofs, transcripts)?;
+        sink.extend(statements.iter().map(|statement| statement.get_J().compress()));
+
+        Ok(())
+    }
+
+    /// Verify a batch of Triptych [`TriptychProofs`](`TriptychProof`), and check that their linking tags are distinct.
+    ///
+    /// This has the same requirements as [`TriptychProof::verify_batch`].
+    /// If the batch is valid but two statements share a linking tag, returns [`ProofError::DuplicateLinkingTag`] with
+    /// the indexes of the first such pair. This detects a signing key used more than once within the batch.
+    pub fn verify_batch_distinct_tags(
+        statements: &[TriptychStatement],
+        proofs: &[TriptychProof],
+        transcripts: &mut [Transcript],
+    ) -> Result<(), ProofError> {
+        Self::verify_batch(statements, proofs, transcripts)?;
+
+        let mut tags = BTreeMap::new();
+        for (second, statement) in statements.iter().enumerate() {
+            if let Some(first) = tags.insert(statement.get_J().compress().to_bytes(), second) {
+                return Err(ProofError::DuplicateLinkingTag { first, second });
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Verify each Triptych [`TriptychProof`] in a batch individually, yielding results as they are produced.
+    ///
+    /// This has the same requirements as [`TriptychProof::verify_batch`], but each proof is verified separately.
+    /// The returned iterator yields the index and verification result of each proof in order, which is useful for
+    /// reporting progress. This is much slower than [`TriptychProof::verify_batch`], which should be preferred when
+    /// incremental results are not needed.
+    ///
+    /// If the numbers of `statements`, `proofs`, and `transcripts` do not match, the iterator yields a single
+    /// [`ProofError`] at index `0`.
+    pub fn verify_batch_progressive<'a>(
+        statements: &'a [TriptychStatement],
+        proofs: &'a [TriptychProof],
+        transcripts: &'a mut [Transcript],
+    ) -> impl Iterator<Item = (usize, Result<(), ProofError>)> + 'a {
+        // Check that we have the same number of statements, proofs, and transcripts
+        let lengths_match = statements.len() == proofs.len() && statements.len() == transcripts.len();
+        let mismatch = (!lengths_match).then_some((
+            0,
+            Err(ProofError::InvalidParameter {
+                reason: "number of statements, proofs, and transcripts does not match",
+            }),
+        ));
+        let count = if lengths_match { proofs.len() } else { 0 };
+
+        izip!(statements, proofs, transcripts.iter_mut())
+            .take(count)
+            .enumerate()
+            .map(|(index, (statement, proof, transcript))| (index, proof.verify(statement, transcript)))
+            .chain(mismatch)
+    }
+
     /// Verify a batch of Triptych [`TriptychProofs`](`TriptychProof`).
     ///
     /// An empty batch is valid by definition.
@@ -583,6 +744,9 @@ impl TriptychProof {
     /// [`TriptychParameters`](`crate::parameters::TriptychParameters`).
     ///
     /// If any of the above requirements are not met, or if any proof is invalid, returns a [`ProofError`].
+    ///
+    /// Verification only uses public data, so it always runs in variable time. There is no separate variable-time
+    /// verifier.
     #[allow(clippy::too_many_lines, non_snake_case)]
     pub fn verify_batch(
         statements: &[TriptychStatement],
@@ -628,65 +792,18 @@ impl TriptychProof {
 
         // Check that all proof semantics are valid for the statement
         for proof in proofs {
-            if proof.X.len() != params.get_m() as usize {
-                return Err(ProofError::InvalidParameter {
-                    reason: "proof `X` vector length was not `m`",
-                });
-            }
-            if proof.X1.len() != params.get_m() as usize {
-                return Err(ProofError::InvalidParameter {
-                    reason: "proof `X1` vector length was not `m`",
-                });
-            }
-            if proof.Y.len() != params.get_m() as usize {
-                return Err(ProofError::InvalidParameter {
-                    reason: "proof `Y` vector length was not `m`",
-                });
-            }
-            if proof.f.len() != params.get_m() as usize {
-                return Err(ProofError::InvalidParameter {
-                    reason: "proof `f` matrix did not have `m` rows",
-                });
-            }
-            for f_row in &proof.f {
-                if f_row.len() !=
-                    params.get_n().checked_sub(1).ok_or(ProofError::InvalidParameter {
-                        reason: "proof `f` matrix column count overflowed",
-                    })? as usize
-                {
-                    return Err(ProofError::InvalidParameter {
-                        reason: "proof `f` matrix did not have `n - 1` columns",
-                    });
-                }
-            }
+            proof.validate(params)?;
         }
 
         // Determine the size of the final check vector, which must not overflow `usize`
-        let batch_size = u32::try_from(proofs.len()).map_err(|_| ProofError::InvalidParameter {
-            reason: "batch size overflowed `u32`",
-        })?;
-
-        // This is unlikely to overflow; even if it does, the only effect is unnecessary reallocation
-        #[allow(clippy::arithmetic_side_effects)]
-        let final_size = usize::try_from(
-            1 // G
-            + 1 // H
-            + params.get_n() * params.get_m() // CommitmentG
-            + 1 // CommitmentH
-            + 2 * params.get_N() // M, M1
-            + 1 // U
-            + batch_size * (
-                4 // A, B, C, D
-                + 1 // offset
-                + 1 // J
-                + 3 * params.get_m() // X, X1, Y
-            ),
-        )
-        .map_err(|_| ProofError::InvalidParameter {
-            reason: "multiscalar multiplication size overflowed `usize`",
-        })?;
+        let final_size = params
+            .batch_check_size(proofs.len())
+            .ok_or(ProofError::InvalidParameter {
+                reason: "batch size exceeded the maximum for the parameters",
+            })?;
 
-        // Set up the point vector for the final check
+        // Set up the point vector for the final check, putting the common elements last
+        // The order must match that of the scalars from `BatchScalars`, followed by the additional parallel terms
         let points = proofs
             .iter()
             .zip(statements.iter())
@@ -697,30 +814,23 @@ impl TriptychProof {
                     .chain(once(&p.D))
                     .chain(once(s.get_J()))
                     .chain(p.X.iter())
-                    .chain(p.X1.iter())
                     .chain(p.Y.iter())
+                    .chain(p.X1.iter())
                     .chain(once(s.get_offset()))
             })
             .chain(once(params.get_G()))
-            .chain(once(params.get_G1()))
             .chain(params.get_CommitmentG().iter())
             .chain(once(params.get_CommitmentH()))
             .chain(M.iter())
-            .chain(M1.iter())
             .chain(once(params.get_U()))
+            .chain(once(params.get_G1()))
+            .chain(M1.iter())
             .collect::<Vec<&RistrettoPoint>>();
 
-        // Start the scalar vector, putting the common elements last
-        let mut scalars = Vec::with_capacity(final_size);
-
-        // Set up common scalars
-        let mut G_scalar = Scalar::ZERO;
-        let mut H_scalar = Scalar::ZERO;
-        let mut CommitmentG_scalars = vec![Scalar::ZERO; params.get_CommitmentG().len()];
-        let mut CommitmentH_scalar = Scalar::ZERO;
-        let mut M_scalars = vec![Scalar::ZERO; M.len()];
+        // Set up the scalars, including those for the additional common parallel terms
+        let mut batch_scalars = BatchScalars::new(params.get_CommitmentG().len(), M.len(), final_size);
+        let mut G1_scalar = Scalar::ZERO;
         let mut M1_scalars = vec![Scalar::ZERO; M1.len()];
-        let mut U_scalar = Scalar::ZERO;
 
         // Set up a transcript generator for use in weighting
         let mut transcript_weights = Transcript::new(domains::TRANSCRIPT_PARALLEL_VERIFIER_WEIGHTS.as_bytes());
@@ -749,26 +859,8 @@ impl TriptychProof {
 
         // Process each proof
         for (proof, xi_powers) in proofs.iter().zip(xi_powers_all.iter()) {
-            // Reconstruct the remaining `f` terms
-            let f = (0..params.get_m())
-                .map(|j| {
-                    let mut f_j = Vec::with_capacity(params.get_n() as usize);
-                    f_j.push(xi_powers[1] - proof.f[j as usize].iter().sum::<Scalar>());
-                    f_j.extend(proof.f[j as usize].iter());
-                    f_j
-                })
-                .collect::<Vec<Vec<Scalar>>>();
-
-            // Check that `f` does not contain zero, which breaks batch inversion
-            for f_row in &f {
-                if f_row.contains(&Scalar::ZERO) {
-                    return Err(ProofError::InvalidParameter {
-                        reason: "proof `f` matrix contained 0",
-                    });
-                }
-            }
-
             // Generate nonzero weights for this proof's verification equations
+            // Each equation gets an independent weight so that errors in one cannot cancel errors in another
             let mut w1 = Scalar::ZERO;
             let mut w2 = Scalar::ZERO;
             let mut w3 = Scalar::ZERO;
@@ -782,98 +874,44 @@ impl TriptychProof {
                 w5 = Scalar::random(&mut transcript_weights_rng);
             }
 
-            // Get the challenge for convenience
-            let xi = xi_powers[1];
-
-            // G
-            G_scalar -= w3 * proof.z;
-
-            // H
-            H_scalar -= w5 * proof.z1;
-
-            // CommitmentG
-            for (CommitmentG_scalar, f_item) in CommitmentG_scalars
-                .iter_mut()
-                .zip(f.iter().flatten().map(|f| w1 * f + w2 * f * (xi - f)))
-            {
-                *CommitmentG_scalar += f_item;
-            }
-
-            // CommitmentH
-            CommitmentH_scalar += w1 * proof.z_A + w2 * proof.z_C;
-
-            // A
-            scalars.push(-w1);
-
-            // B
-            scalars.push(-w1 * xi_powers[1]);
-
-            // C
-            scalars.push(-w2 * xi_powers[1]);
-
-            // D
-            scalars.push(-w2);
-
-            // J
-            scalars.push(-w4 * proof.z);
+            // Accumulate the terms shared with non-parallel proofs, along with the `M1` terms
+            let responses = ProofResponses {
+                f: &proof.f,
+                z_A: &proof.z_A,
+                z_C: &proof.z_C,
+                z: &proof.z,
+            };
+            let f_product_sum = batch_scalars
+                .accumulate(
+                    params.get_n(),
+                    params.get_m(),
+                    &responses,
+                    xi_powers,
+                    &[w1, w2, w3, w4],
+                    |index, f_product| {
+                        if let Some(M1_scalar) = M1_scalars.get_mut(index) {
+                            *M1_scalar += w5 * f_product;
+                        }
+                    },
+                )
+                .map_err(|reason| ProofError::InvalidParameter { reason })?;
 
-            // X
-            for xi_power in &xi_powers[0..(params.get_m() as usize)] {
-                scalars.push(-w3 * xi_power);
-            }
+            // G1
+            G1_scalar -= w5 * proof.z1;
 
             // X1
             for xi_power in &xi_powers[0..(params.get_m() as usize)] {
-                scalars.push(-w5 * xi_power);
-            }
-
-            // Y
-            for xi_power in &xi_powers[0..(params.get_m() as usize)] {
-                scalars.push(-w4 * xi_power);
-            }
-
-            // Set up the initial `f` product and Gray iterator
-            let mut f_product = f.iter().map(|f_row| f_row[0]).product::<Scalar>();
-            let gray_iterator =
-                GrayIterator::new(params.get_n(), params.get_m()).ok_or(ProofError::InvalidParameter {
-                    reason: "coefficient decomposition failed",
-                })?;
-
-            // Invert each element of `f` for efficiency
-            let mut f_inverse_flat = f.iter().flatten().copied().collect::<Vec<Scalar>>();
-            Scalar::batch_invert(&mut f_inverse_flat);
-            let f_inverse = f_inverse_flat
-                .chunks_exact(params.get_n() as usize)
-                .collect::<Vec<&[Scalar]>>();
-
-            // M, M1
-            let mut f_product_sum = Scalar::ZERO;
-            for ((M_scalar, M1_scalar), (gray_index, gray_old, gray_new)) in
-                M_scalars.iter_mut().zip(M1_scalars.iter_mut()).zip(gray_iterator)
-            {
-                // Update the `f` product
-                f_product *= f_inverse[gray_index][gray_old as usize] * f[gray_index][gray_new as usize];
-
-                *M_scalar += w3 * f_product;
-                *M1_scalar += w5 * f_product;
-                f_product_sum += f_product;
+                batch_scalars.proof.push(-w5 * xi_power);
             }
 
-            // U
-            U_scalar += w4 * f_product_sum;
-
             // offset
-            scalars.push(-w5 * f_product_sum);
+            batch_scalars.proof.push(-w5 * f_product_sum);
         }
 
-        // Add all common elements to the scalar vector
-        scalars.push(G_scalar);
-        scalars.push(H_scalar);
-        scalars.extend(CommitmentG_scalars);
-        scalars.push(CommitmentH_scalar);
-        scalars.extend(M_scalars);
+        // Add the additional common parallel elements to the scalar vector
+        let mut scalars = batch_scalars.into_scalars();
+        scalars.push(G1_scalar);
         scalars.extend(M1_scalars);
-        scalars.push(U_scalar);
 
         // Perform the final check; this can be done in variable time since it holds no secrets
         if RistrettoPoint::vartime_multiscalar_mul(scalars.iter(), points) == RistrettoPoint::identity() {
@@ -883,13 +921,48 @@ impl TriptychProof {
         }
     }
 
-    /// Serialize a [`TriptychProof`] to a canonical byte vector.
-    #[allow(non_snake_case)]
-    pub fn to_bytes(&self) -> Vec<u8> {
+    /// Get the size of a serialized [`TriptychProof`] for base `n` and exponent `m`.
+    ///
+    /// This is the length of the output of [`TriptychProof::to_bytes`] for any proof using parameters with these
+    /// values, and can be computed without generating a proof. Returns `None` if `n < 2` or the size overflows
+    /// [`prim@usize`].
+    ///
+    /// Since this is a `const fn`, it can be used to size arrays for known parameters at compile time.
+    pub const fn serialized_size_for(n: u32, m: u32) -> Option<usize> {
+        let n_minus_1 = match n.checked_sub(1) {
+            Some(n_minus_1) if n_minus_1 > 0 => n_minus_1 as usize,
+            _ => return None,
+        };
+        let m = m as usize;
+
+        // Combinators aren't available in a `const fn`, so check each step explicitly
+        // `f`
+        let Some(len) = m.checked_mul(n_minus_1) else {
+            return None;
+        };
+        // `X, X1, Y`
+        let Some(len) = (match m.checked_mul(3) {
+            Some(vectors) => len.checked_add(vectors),
+            None => None,
+        }) else {
+            return None;
+        };
+        // `A, B, C, D, z_A, z_C, z, z1`
+        let Some(len) = len.checked_add(8) else {
+            return None;
+        };
+        let Some(len) = len.checked_mul(SERIALIZED_BYTES) else {
+            return None;
+        };
+        // `n - 1`, `m`
+        len.checked_add(8)
+    }
+
+    /// Get the size of this [`TriptychProof`] when serialized using [`TriptychProof::to_bytes`].
+    pub fn serialized_size(&self) -> usize {
         // This cannot overflow
         #[allow(clippy::arithmetic_side_effects)]
-        let mut result = Vec::with_capacity(
-            8 // `n - 1`, `m`
+        let size = 8 // `n - 1`, `m`
             + SERIALIZED_BYTES * (
                 4 // `A, B, C, D`
                 + self.X.len()
@@ -897,8 +970,15 @@ impl TriptychProof {
                 + self.Y.len()
                 + 4 // `z_A, z_C, z, z1`
                 + self.f.len() * self.f[0].len()
-            ),
-        );
+            );
+
+        size
+    }
+
+    /// Serialize a [`TriptychProof`] to a canonical byte vector.
+    #[allow(non_snake_case)]
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut result = Vec::with_capacity(self.serialized_size());
         #[allow(clippy::cast_possible_truncation)]
         let n_minus_1 = self.f[0].len() as u32;
         #[allow(clippy::cast_possible_truncation)]
@@ -932,10 +1012,78 @@ impl TriptychProof {
         result
     }
 
+    /// Serialize a [`TriptychProof`] to a canonical byte vector with a compact header.
+    ///
+    /// This is identical to [`TriptychProof::to_bytes`], except that the dimensions `n - 1` and `m` are each encoded as
+    /// a minimal variable-length integer rather than four bytes. For typical parameters, this saves six bytes.
+    pub fn to_bytes_compact_header(&self) -> Vec<u8> {
+        let bytes = self.to_bytes();
+        let (header, body) = bytes.split_at(8);
+
+        let mut result = Vec::with_capacity(bytes.len());
+        for dimension in header.chunks_exact(4) {
+            let mut dimension_bytes = [0u8; 4];
+            dimension_bytes.copy_from_slice(dimension);
+            encode_varint(u32::from_le_bytes(dimension_bytes), &mut result);
+        }
+        result.extend_from_slice(body);
+
+        result
+    }
+
+    /// Serialize a [`TriptychProof`] to a canonical byte vector with a leading version byte.
+    ///
+    /// This is identical to [`TriptychProof::to_bytes`], except that a single byte identifying the serialization
+    /// version is prepended. This allows a future change to the serialization format to be detected, rather than
+    /// misparsed. Use [`TriptychProof::from_bytes_versioned`] to deserialize.
+    ///
+    /// The version byte applies only to this encoding. The encodings produced by [`TriptychProof::to_bytes`],
+    /// [`TriptychProof::to_bytes_compact_header`], and the `borsh` and `serde` implementations are unversioned, and
+    /// the version byte cannot be combined with the compact header.
+    pub fn to_bytes_versioned(&self) -> Vec<u8> {
+        let mut result = Vec::with_capacity(self.serialized_size().saturating_add(1));
+        result.push(domains::VERSION_BYTE);
+        result.extend(self.to_bytes());
+
+        result
+    }
+
+    /// Deserialize a [`TriptychProof`] from a canonical byte slice with a leading version byte.
+    ///
+    /// This reverses [`TriptychProof::to_bytes_versioned`], and performs the same checks as
+    /// [`TriptychProof::from_bytes`].
+    ///
+    /// If the version byte is missing or unknown, or if the remaining bytes do not represent a canonical encoding,
+    /// returns a [`ProofError`].
+    pub fn from_bytes_versioned(bytes: &[u8]) -> Result<Self, ProofError> {
+        match bytes.split_first() {
+            Some((&domains::VERSION_BYTE, bytes)) => Self::from_bytes(bytes),
+            _ => Err(ProofError::FailedDeserialization),
+        }
+    }
+
+    /// Deserialize a [`TriptychProof`] from a canonical byte slice with a compact header.
+    ///
+    /// This reverses [`TriptychProof::to_bytes_compact_header`], and performs the same checks as
+    /// [`TriptychProof::from_bytes`]. Non-minimal dimension encodings are rejected, so the encoding remains canonical.
+    ///
+    /// If `bytes` does not represent a canonical encoding, returns a [`ProofError`].
+    pub fn from_bytes_compact_header(bytes: &[u8]) -> Result<Self, ProofError> {
+        let (n_minus_1, bytes) = decode_varint(bytes).ok_or(ProofError::FailedDeserialization)?;
+        let (m, bytes) = decode_varint(bytes).ok_or(ProofError::FailedDeserialization)?;
+
+        let mut canonical = Vec::new();
+        canonical.extend(n_minus_1.to_le_bytes());
+        canonical.extend(m.to_le_bytes());
+        canonical.extend_from_slice(bytes);
+
+        Self::from_bytes(&canonical)
+    }
+
     /// Deserialize a [`TriptychProof`] from a canonical byte slice.
     ///
     /// If `bytes` does not represent a canonical encoding, returns a [`ProofError`].
-    #[allow(non_snake_case)]
+    #[allow(clippy::too_many_lines, non_snake_case)]
     pub fn from_bytes(bytes: &[u8]) -> Result<Self, ProofError> {
         // Helper to parse a `u32` from a `u8` iterator
         let parse_u32 = |iter: &mut dyn Iterator<Item = &u8>| {
@@ -986,8 +1134,20 @@ impl TriptychProof {
         if n_minus_1.checked_add(1).ok_or(ProofError::FailedDeserialization)? < 2 {
             return Err(ProofError::FailedDeserialization);
         }
+        // No valid parameters can have `m` exceeding the maximum exponent, so reject it early
         let m = parse_u32(&mut iter)?;
-        if m < 2 {
+        if !(2..=MAX_EXPONENT).contains(&m) {
+            return Err(ProofError::FailedDeserialization);
+        }
+
+        // Check that the remaining length matches the encoded dimensions before parsing any elements
+        let expected_len = (m as usize)
+            .checked_mul(n_minus_1 as usize) // `f`
+            .and_then(|len| len.checked_add((m as usize).checked_mul(3)?)) // `X, X1, Y`
+            .and_then(|len| len.checked_add(8)) // `A, B, C, D, z_A, z_C, z, z1`
+            .and_then(|len| len.checked_mul(SERIALIZED_BYTES))
+            .ok_or(ProofError::FailedDeserialization)?;
+        if iter.as_slice().len() != expected_len {
             return Err(ProofError::FailedDeserialization);
         }
 
@@ -1081,7 +1241,7 @@ impl BorshDeserialize for TriptychProof {
 
 #[cfg(test)]
 mod test {
-    use alloc::vec::Vec;
+    use alloc::{vec, vec::Vec};
 
     use curve25519_dalek::{traits::Identity, RistrettoPoint, Scalar};
     use itertools::izip;
@@ -1097,6 +1257,7 @@ mod test {
             TriptychStatement,
             TriptychWitness,
         },
+        parameters::MAX_EXPONENT,
         Transcript,
     };
 
@@ -1201,6 +1362,34 @@ mod test {
         assert!(proof.verify(&statements[0], &mut transcripts[0]).is_ok());
     }
 
+    #[test]
+    #[cfg(feature = "rand")]
+    #[allow(non_snake_case, non_upper_case_globals)]
+    fn test_verify_bool() {
+        // Generate data
+        const n: u32 = 2;
+        const m: u32 = 4;
+        let mut rng = ChaCha12Rng::seed_from_u64(8675309);
+        let (witnesses, statements, transcripts) = generate_data(n, m, 1, &mut rng);
+
+        // A valid proof verifies
+        let proof = TriptychProof::prove(&witnesses[0], &statements[0], &mut transcripts[0].clone()).unwrap();
+        assert!(proof.verify_bool(&statements[0], &mut transcripts[0].clone()));
+
+        // A transcript mismatch fails verification
+        let mut evil_transcript = Transcript::new(b"Evil transcript");
+        assert!(proof.verify(&statements[0], &mut evil_transcript.clone()).is_err());
+        assert!(!proof.verify_bool(&statements[0], &mut evil_transcript));
+
+        // A statement with mismatched parameters is rejected as a parameter error
+        let (_, evil_statements, _) = generate_data(n, m - 1, 1, &mut rng);
+        assert!(matches!(
+            proof.verify(&evil_statements[0], &mut transcripts[0].clone()),
+            Err(ProofError::InvalidParameter { .. })
+        ));
+        assert!(!proof.verify_bool(&evil_statements[0], &mut transcripts[0].clone()));
+    }
+
     #[test]
     #[cfg(all(feature = "rand", feature = "hazmat"))]
     #[allow(non_snake_case, non_upper_case_globals)]
@@ -1233,6 +1422,32 @@ mod test {
         assert!(proof.verify(&statements[0], &mut transcripts[0]).is_ok());
     }
 
+    #[test]
+    #[cfg(feature = "hazmat")]
+    #[allow(non_snake_case, non_upper_case_globals)]
+    fn test_prove_verify_with_timing() {
+        use crate::OperationTiming;
+
+        // Generate data
+        const n: u32 = 2;
+        const m: u32 = 4;
+        let mut rng = ChaCha12Rng::seed_from_u64(8675309);
+        let (witnesses, statements, transcripts) = generate_data(n, m, 1, &mut rng);
+
+        // Generate and verify a proof using each timing mode
+        for timing in [OperationTiming::Constant, OperationTiming::Variable] {
+            let proof = TriptychProof::prove_with_timing(
+                &witnesses[0],
+                &statements[0],
+                &mut rng,
+                &mut transcripts[0].clone(),
+                timing,
+            )
+            .unwrap();
+            assert!(proof.verify(&statements[0], &mut transcripts[0].clone()).is_ok());
+        }
+    }
+
     #[test]
     #[allow(non_snake_case, non_upper_case_globals)]
     fn test_serialize_deserialize() {
@@ -1255,6 +1470,167 @@ mod test {
         assert_eq!(deserialized, proof);
     }
 
+    #[test]
+    #[allow(non_snake_case, non_upper_case_globals)]
+    fn test_serialize_deserialize_versioned() {
+        // Generate data
+        const n: u32 = 2;
+        const m: u32 = 4;
+        let mut rng = ChaCha12Rng::seed_from_u64(8675309);
+        let (witnesses, statements, transcripts) = generate_data(n, m, 1, &mut rng);
+
+        // Generate a proof
+        let proof = TriptychProof::prove_with_rng(&witnesses[0], &statements[0], &mut rng, &mut transcripts[0].clone())
+            .unwrap();
+
+        // The versioned serialization prepends the version byte to the canonical serialization
+        let serialized = proof.to_bytes_versioned();
+        let (version, canonical) = serialized.split_first().unwrap();
+        assert_eq!(*version, 0);
+        assert_eq!(canonical, proof.to_bytes());
+
+        // The proof round-trips
+        assert_eq!(TriptychProof::from_bytes_versioned(&serialized).unwrap(), proof);
+
+        // An unknown version fails, even if the canonical serialization that follows it is valid
+        for bad_version in [1u8, 0xFF] {
+            let mut evil = vec![bad_version];
+            evil.extend_from_slice(canonical);
+            assert!(matches!(
+                TriptychProof::from_bytes_versioned(&evil),
+                Err(ProofError::FailedDeserialization)
+            ));
+        }
+
+        // An empty serialization fails
+        assert!(matches!(
+            TriptychProof::from_bytes_versioned(&[]),
+            Err(ProofError::FailedDeserialization)
+        ));
+    }
+
+    #[test]
+    #[allow(non_snake_case)]
+    fn test_serialized_size() {
+        let mut rng = ChaCha12Rng::seed_from_u64(8675309);
+
+        // The sizes match the serialization for several parameters
+        for (n, m) in [(2, 2), (3, 3)] {
+            let (witnesses, statements, transcripts) = generate_data(n, m, 1, &mut rng);
+            let proof =
+                TriptychProof::prove_with_rng(&witnesses[0], &statements[0], &mut rng, &mut transcripts[0].clone())
+                    .unwrap();
+
+            assert_eq!(proof.to_bytes().len(), proof.serialized_size());
+            assert_eq!(TriptychProof::serialized_size_for(n, m), Some(proof.serialized_size()));
+        }
+
+        // Invalid parameters have no size
+        assert!(TriptychProof::serialized_size_for(1, 4).is_none());
+    }
+
+    #[test]
+    #[allow(non_snake_case, non_upper_case_globals)]
+    fn test_serialize_deserialize_compact_header() {
+        // Generate data
+        const n: u32 = 2;
+        const m: u32 = 4;
+        let mut rng = ChaCha12Rng::seed_from_u64(8675309);
+        let (witnesses, statements, mut transcripts) = generate_data(n, m, 1, &mut rng);
+
+        // Generate a proof
+        let proof =
+            TriptychProof::prove_with_rng(&witnesses[0], &statements[0], &mut rng, &mut transcripts[0]).unwrap();
+
+        // The compact header saves six bytes for small dimensions, and round-trips
+        let serialized = proof.to_bytes_compact_header();
+        assert_eq!(serialized.len() + 6, proof.to_bytes().len());
+        assert_eq!(TriptychProof::from_bytes_compact_header(&serialized).unwrap(), proof);
+
+        // A non-minimal dimension encoding fails
+        let mut evil = vec![serialized[0] | 0x80, 0x00];
+        evil.extend_from_slice(&serialized[1..]);
+        assert!(TriptychProof::from_bytes_compact_header(&evil).is_err());
+
+        // Truncated encodings fail
+        assert!(TriptychProof::from_bytes_compact_header(&serialized[..serialized.len() - 1]).is_err());
+        assert!(TriptychProof::from_bytes_compact_header(&serialized[..1]).is_err());
+    }
+
+    #[test]
+    #[allow(non_snake_case, non_upper_case_globals)]
+    fn test_deserialize_invalid_length() {
+        // Generate data
+        const n: u32 = 2;
+        const m: u32 = 4;
+        let mut rng = ChaCha12Rng::seed_from_u64(8675309);
+        let (witnesses, statements, mut transcripts) = generate_data(n, m, 1, &mut rng);
+
+        // Generate a proof and serialize it
+        let proof =
+            TriptychProof::prove_with_rng(&witnesses[0], &statements[0], &mut rng, &mut transcripts[0]).unwrap();
+        let serialized = proof.to_bytes();
+
+        // Truncated and extended encodings fail
+        assert!(TriptychProof::from_bytes(&serialized[..serialized.len() - SERIALIZED_BYTES]).is_err());
+        assert!(TriptychProof::from_bytes(&serialized[..serialized.len() - 1]).is_err());
+        let mut extended = serialized.clone();
+        extended.extend_from_slice(&[0u8; SERIALIZED_BYTES]);
+        assert!(TriptychProof::from_bytes(&extended).is_err());
+
+        // Encoded dimensions that don't match the length fail
+        let mut evil_m = serialized.clone();
+        evil_m[4..8].copy_from_slice(&(m + 1).to_le_bytes());
+        assert!(TriptychProof::from_bytes(&evil_m).is_err());
+        let mut evil_m = serialized;
+        evil_m[4..8].copy_from_slice(&u32::MAX.to_le_bytes());
+        assert!(TriptychProof::from_bytes(&evil_m).is_err());
+
+        // An `m` exceeding the maximum exponent fails, even with a matching length and valid elements
+        let mut evil_m = Vec::new();
+        evil_m.extend(1u32.to_le_bytes());
+        evil_m.extend((MAX_EXPONENT + 1).to_le_bytes());
+        evil_m.resize(8 + SERIALIZED_BYTES * ((MAX_EXPONENT as usize + 1) * 4 + 8), 0);
+        assert!(TriptychProof::from_bytes(&evil_m).is_err());
+    }
+
+    #[test]
+    #[allow(non_snake_case, non_upper_case_globals)]
+    fn test_validate() {
+        // Generate data
+        const n: u32 = 2;
+        const m: u32 = 4;
+        let mut rng = ChaCha12Rng::seed_from_u64(8675309);
+        let (witnesses, statements, mut transcripts) = generate_data(n, m, 1, &mut rng);
+
+        // Generate a proof, which has valid dimensions
+        let proof =
+            TriptychProof::prove_with_rng(&witnesses[0], &statements[0], &mut rng, &mut transcripts[0]).unwrap();
+        let params = statements[0].get_params();
+        assert!(proof.validate(params).is_ok());
+
+        // Dimensions for other parameters fail
+        assert!(proof.validate(&TriptychParameters::new(n + 1, m).unwrap()).is_err());
+        assert!(proof.validate(&TriptychParameters::new(n, m + 1).unwrap()).is_err());
+
+        // Malformed vectors fail
+        let mut evil = proof.clone();
+        evil.X.pop();
+        assert!(evil.validate(params).is_err());
+        let mut evil = proof.clone();
+        evil.X1.pop();
+        assert!(evil.validate(params).is_err());
+        let mut evil = proof.clone();
+        evil.Y.pop();
+        assert!(evil.validate(params).is_err());
+        let mut evil = proof.clone();
+        evil.f.pop();
+        assert!(evil.validate(params).is_err());
+        let mut evil = proof;
+        evil.f[0].pop();
+        assert!(evil.validate(params).is_err());
+    }
+
     #[test]
     #[cfg(feature = "borsh")]
     #[allow(non_snake_case, non_upper_case_globals)]
@@ -1278,6 +1654,33 @@ mod test {
         assert_eq!(deserialized, proof);
     }
 
+    #[test]
+    #[allow(non_snake_case)]
+    fn test_prove_verify_larger_bases() {
+        // Test against several parameters with `n > 2`
+        for (n, m) in [(3, 2), (3, 3), (4, 2), (4, 3), (5, 2)] {
+            let mut rng = ChaCha12Rng::seed_from_u64(8675309);
+            let (witnesses, statements, transcripts) = generate_data(n, m, 2, &mut rng);
+
+            // Generate the proofs
+            let proofs = izip!(witnesses.iter(), statements.iter(), transcripts.clone().iter_mut())
+                .map(|(w, s, t)| TriptychProof::prove_with_rng(w, s, &mut rng, t).unwrap())
+                .collect::<Vec<TriptychProof>>();
+
+            // Serialization should round-trip, and the proofs should verify singly and as a batch
+            for (statement, proof, transcript) in izip!(statements.iter(), proofs.iter(), transcripts.iter()) {
+                assert_eq!(&TriptychProof::from_bytes(&proof.to_bytes()).unwrap(), proof);
+                assert!(proof.verify(statement, &mut transcript.clone()).is_ok());
+            }
+            assert!(TriptychProof::verify_batch(&statements, &proofs, &mut transcripts.clone()).is_ok());
+
+            // Proofs should not verify against the wrong transcript
+            assert!(proofs[0]
+                .verify(&statements[0], &mut Transcript::new(b"Evil transcript"))
+                .is_err());
+        }
+    }
+
     #[test]
     #[allow(non_snake_case, non_upper_case_globals)]
     fn test_prove_verify_batch() {
@@ -1299,6 +1702,106 @@ mod test {
         assert!(TriptychProof::verify_batch_with_full_blame(&statements, &proofs, &mut transcripts).is_ok());
     }
 
+    #[test]
+    #[allow(non_snake_case, non_upper_case_globals)]
+    fn test_evil_parallel_terms() {
+        // Generate data
+        const n: u32 = 2;
+        const m: u32 = 4;
+        let mut rng = ChaCha12Rng::seed_from_u64(8675309);
+        let (witnesses, statements, transcripts) = generate_data(n, m, 1, &mut rng);
+
+        // Generate and verify a proof
+        let proof = TriptychProof::prove_with_rng(&witnesses[0], &statements[0], &mut rng, &mut transcripts[0].clone())
+            .unwrap();
+        assert!(proof.verify(&statements[0], &mut transcripts[0].clone()).is_ok());
+
+        // A proof whose auxiliary response reuses the non-parallel response fails
+        // The auxiliary response is not bound to the challenge, so only the final check can reject it
+        let mut evil = proof.clone();
+        evil.z1 = evil.z;
+        assert!(matches!(
+            evil.verify(&statements[0], &mut transcripts[0].clone()),
+            Err(ProofError::FailedVerification)
+        ));
+
+        // A proof with a modified auxiliary response fails, both alone and in a batch
+        let mut evil = proof.clone();
+        evil.z1 += Scalar::ONE;
+        assert!(matches!(
+            evil.verify(&statements[0], &mut transcripts[0].clone()),
+            Err(ProofError::FailedVerification)
+        ));
+        assert!(
+            TriptychProof::verify_batch(&[statements[0].clone(), statements[0].clone()], &[proof, evil], &mut [
+                transcripts[0].clone(),
+                transcripts[0].clone()
+            ],)
+            .is_err()
+        );
+    }
+
+    #[test]
+    #[allow(non_snake_case, non_upper_case_globals)]
+    fn test_prove_verify_batch_collect_tags() {
+        // Generate data
+        const n: u32 = 2;
+        const m: u32 = 4;
+        const batch: usize = 3; // batch size
+        let mut rng = ChaCha12Rng::seed_from_u64(8675309);
+        let (witnesses, statements, mut transcripts) = generate_data(n, m, batch, &mut rng);
+
+        // Generate the proofs
+        let proofs = izip!(witnesses.iter(), statements.iter(), transcripts.clone().iter_mut())
+            .map(|(w, s, t)| TriptychProof::prove_with_rng(w, s, &mut rng, t).unwrap())
+            .collect::<Vec<TriptychProof>>();
+
+        // A valid batch collects all tags in order
+        let mut tags = Vec::new();
+        assert!(
+            TriptychProof::verify_batch_collect_tags(&statements, &proofs, &mut transcripts.clone(), &mut tags).is_ok()
+        );
+        assert_eq!(
+            tags,
+            statements.iter().map(|s| s.get_J().compress()).collect::<Vec<_>>()
+        );
+
+        // An invalid batch collects nothing
+        let mut tags = Vec::new();
+        transcripts[0] = Transcript::new(b"Evil transcript");
+        assert!(TriptychProof::verify_batch_collect_tags(&statements, &proofs, &mut transcripts, &mut tags).is_err());
+        assert!(tags.is_empty());
+    }
+
+    #[test]
+    #[allow(non_snake_case, non_upper_case_globals)]
+    fn test_prove_verify_batch_distinct_tags() {
+        // Generate data
+        const n: u32 = 2;
+        const m: u32 = 4;
+        const batch: usize = 3; // batch size
+        let mut rng = ChaCha12Rng::seed_from_u64(8675309);
+        let (witnesses, mut statements, mut transcripts) = generate_data(n, m, batch, &mut rng);
+
+        // Generate the proofs
+        let mut proofs = izip!(witnesses.iter(), statements.iter(), transcripts.clone().iter_mut())
+            .map(|(w, s, t)| TriptychProof::prove_with_rng(w, s, &mut rng, t).unwrap())
+            .collect::<Vec<TriptychProof>>();
+
+        // Distinct tags are accepted
+        assert!(TriptychProof::verify_batch_distinct_tags(&statements, &proofs, &mut transcripts.clone()).is_ok());
+
+        // A repeated statement is detected, even though the batch is valid
+        statements[2] = statements[0].clone();
+        proofs[2] = proofs[0].clone();
+        transcripts[2] = transcripts[0].clone();
+        assert!(TriptychProof::verify_batch(&statements, &proofs, &mut transcripts.clone()).is_ok());
+        assert!(matches!(
+            TriptychProof::verify_batch_distinct_tags(&statements, &proofs, &mut transcripts.clone()),
+            Err(ProofError::DuplicateLinkingTag { first: 0, second: 2 })
+        ));
+    }
+
     #[test]
     fn test_prove_verify_empty_batch() {
         // An empty batch is valid by definition
@@ -1307,6 +1810,49 @@ mod test {
         assert!(TriptychProof::verify_batch_with_full_blame(&[], &[], &mut []).is_ok());
     }
 
+    #[test]
+    #[allow(non_snake_case, non_upper_case_globals)]
+    fn test_prove_errors() {
+        // Generate data
+        const n: u32 = 2;
+        const m: u32 = 4;
+        let mut rng = ChaCha12Rng::seed_from_u64(8675309);
+        let (witnesses, statements, transcripts) = generate_data(n, m, 1, &mut rng);
+        let params = statements[0].get_params();
+        let l = witnesses[0].get_l();
+
+        // The witness parameters must match the statement
+        let other_params = TriptychParameters::new(n, m + 1).unwrap();
+        let witness = TriptychWitness::random(&other_params, &mut rng);
+        assert!(matches!(
+            TriptychProof::prove_with_rng(&witness, &statements[0], &mut rng, &mut transcripts[0].clone()),
+            Err(ProofError::ParameterMismatch)
+        ));
+
+        // The witness signing key must match the verification key at its index
+        let witness = TriptychWitness::new(params, l, &Scalar::random(&mut rng), witnesses[0].get_r1()).unwrap();
+        assert!(matches!(
+            TriptychProof::prove_with_rng(&witness, &statements[0], &mut rng, &mut transcripts[0].clone()),
+            Err(ProofError::WitnessKeyMismatch)
+        ));
+
+        // The witness auxiliary signing key must match the auxiliary verification key at its index
+        let witness = TriptychWitness::new(params, l, witnesses[0].get_r(), &Scalar::random(&mut rng)).unwrap();
+        assert!(matches!(
+            TriptychProof::prove_with_rng(&witness, &statements[0], &mut rng, &mut transcripts[0].clone()),
+            Err(ProofError::AuxiliaryKeyMismatch)
+        ));
+
+        // The witness signing key must match the linking tag
+        let J = TriptychWitness::random(params, &mut rng).compute_linking_tag();
+        let statement =
+            TriptychStatement::new(params, statements[0].get_input_set(), statements[0].get_offset(), &J).unwrap();
+        assert!(matches!(
+            TriptychProof::prove_with_rng(&witnesses[0], &statement, &mut rng, &mut transcripts[0].clone()),
+            Err(ProofError::LinkingTagMismatch)
+        ));
+    }
+
     #[test]
     #[allow(non_snake_case, non_upper_case_globals)]
     fn test_prove_verify_invalid_batch() {
@@ -1502,6 +2048,41 @@ mod test {
         assert!(proof.verify(&evil_statement, &mut transcripts[0]).is_err());
     }
 
+    #[test]
+    #[allow(non_snake_case, non_upper_case_globals)]
+    fn test_evil_zero_f() {
+        // Generate data
+        const n: u32 = 2;
+        const m: u32 = 4;
+        let mut rng = ChaCha12Rng::seed_from_u64(8675309);
+        let (witnesses, statements, transcripts) = generate_data(n, m, 1, &mut rng);
+
+        // Generate a proof
+        let proof = TriptychProof::prove_with_rng(&witnesses[0], &statements[0], &mut rng, &mut transcripts[0].clone())
+            .unwrap();
+
+        // Set an `f` entry to zero, so the reconstructed `f` matrix contains zero
+        let mut evil = proof.clone();
+        evil.f[0][0] = Scalar::ZERO;
+
+        // Verification rejects the malformed matrix, both alone and in a batch
+        assert!(matches!(
+            evil.verify(&statements[0], &mut transcripts[0].clone()),
+            Err(ProofError::InvalidParameter {
+                reason: "proof `f` matrix contained 0"
+            })
+        ));
+        assert!(matches!(
+            TriptychProof::verify_batch(&[statements[0].clone(), statements[0].clone()], &[proof, evil], &mut [
+                transcripts[0].clone(),
+                transcripts[0].clone()
+            ],),
+            Err(ProofError::InvalidParameter {
+                reason: "proof `f` matrix contained 0"
+            })
+        ));
+    }
+
     #[test]
     #[allow(non_snake_case, non_upper_case_globals)]
     fn test_evil_offset() {
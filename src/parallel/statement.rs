@@ -2,6 +2,7 @@
 // SPDX-License-Identifier: BSD-3-Clause
 
 use alloc::{sync::Arc, vec, vec::Vec};
+use core::hash::Hash;
 
 use curve25519_dalek::{traits::Identity, RistrettoPoint};
 use snafu::prelude::*;
@@ -17,6 +18,7 @@ use crate::{domains, parallel::TriptychParameters, Transcript};
 pub struct TriptychInputSet {
     M: Arc<Vec<RistrettoPoint>>,
     M1: Arc<Vec<RistrettoPoint>>,
+    unpadded_size: u32,
     hash: Vec<u8>,
 }
 
@@ -108,6 +110,7 @@ impl TriptychInputSet {
         Ok(Self {
             M: Arc::new(M.to_vec()),
             M1: Arc::new(M1.to_vec()),
+            unpadded_size,
             hash,
         })
     }
@@ -122,8 +125,17 @@ impl TriptychInputSet {
         &self.M1
     }
 
+    /// Get the number of verification keys for this [`TriptychInputSet`] before any padding was applied.
+    pub fn get_unpadded_size(&self) -> u32 {
+        self.unpadded_size
+    }
+
     /// Get a cryptographic hash representation of this [`TriptychInputSet`], suitable for transcripting.
-    pub(crate) fn get_hash(&self) -> &[u8] {
+    ///
+    /// The hash commits to the unpadded size and to both verification key vectors in order. It is bound into the hash
+    /// of any [`TriptychStatement`] using this input set, so comparing `statement.get_input_set().get_hash()` against a
+    /// known value is a cheap way to check which input set a statement claims before loading the keys themselves.
+    pub fn get_hash(&self) -> &[u8] {
         &self.hash
     }
 }
@@ -240,6 +252,24 @@ impl TriptychStatement {
     }
 }
 
+impl Hash for TriptychInputSet {
+    /// Hash only the precomputed input set hash, which binds all of its data.
+    ///
+    /// This is consistent with equality, and relies on the collision resistance of the precomputed hash.
+    fn hash<H: core::hash::Hasher>(&self, state: &mut H) {
+        self.hash.hash(state);
+    }
+}
+
+impl Hash for TriptychStatement {
+    /// Hash only the precomputed statement hash, which binds all of its data.
+    ///
+    /// This is consistent with equality, and relies on the collision resistance of the precomputed hash.
+    fn hash<H: core::hash::Hasher>(&self, state: &mut H) {
+        self.hash.hash(state);
+    }
+}
+
 #[cfg(test)]
 mod test {
     use alloc::{borrow::ToOwned, vec::Vec};
@@ -281,6 +311,12 @@ mod test {
             TriptychInputSet::new_with_padding(&M, &M1, &params).unwrap(),
             TriptychInputSet::new(&M, &M1).unwrap()
         );
+        assert_eq!(
+            TriptychInputSet::new_with_padding(&M, &M1, &params)
+                .unwrap()
+                .get_unpadded_size(),
+            params.get_N()
+        );
 
         // Vectors are padded
         let M = random_vector(N - 1);
@@ -301,6 +337,18 @@ mod test {
                 .unwrap()
                 .get_auxiliary_keys()
         );
+        assert_eq!(
+            TriptychInputSet::new_with_padding(&M, &M1, &params)
+                .unwrap()
+                .get_unpadded_size(),
+            params.get_N() - 1
+        );
+        assert_eq!(
+            TriptychInputSet::new(&M_padded, &M1_padded)
+                .unwrap()
+                .get_unpadded_size(),
+            params.get_N()
+        );
         assert_ne!(
             TriptychInputSet::new_with_padding(&M, &M1, &params).unwrap().get_hash(),
             TriptychInputSet::new(&M_padded, &M1_padded).unwrap().get_hash()
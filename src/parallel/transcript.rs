@@ -83,21 +83,7 @@ impl<'a, R: CryptoRngCore> ProofTranscript<'a, R> {
         self.transcript.challenge_bytes(b"xi", &mut xi_bytes);
         let xi = Scalar::from_bytes_mod_order_wide(&xi_bytes);
 
-        // Get powers of the challenge and confirm they are nonzero
-        let mut xi_powers = Vec::with_capacity(m.checked_add(1).ok_or(ProofError::InvalidParameter {
-            reason: "challenge power count overflowed `usize`",
-        })?);
-        let mut xi_power = Scalar::ONE;
-        for _ in 0..=m {
-            if xi_power == Scalar::ZERO {
-                return Err(ProofError::InvalidChallenge);
-            }
-
-            xi_powers.push(xi_power);
-            xi_power *= xi;
-        }
-
-        Ok(xi_powers)
+        challenge_powers(&xi, m)
     }
 
     /// Run the Fiat-Shamir response phase
@@ -150,3 +136,45 @@ impl<'a, R: CryptoRngCore> ProofTranscript<'a, R> {
         }
     }
 }
+
+/// Get the powers `xi^0, ..., xi^m` of a challenge `xi`, confirming that they are nonzero.
+///
+/// A zero power can only occur for a zero challenge, which a transcript produces with negligible probability. If it
+/// does occur, returns a [`ProofError`].
+fn challenge_powers(xi: &Scalar, m: usize) -> Result<Vec<Scalar>, ProofError> {
+    let mut xi_powers = Vec::with_capacity(m.checked_add(1).ok_or(ProofError::InvalidParameter {
+        reason: "challenge power count overflowed `usize`",
+    })?);
+    let mut xi_power = Scalar::ONE;
+    for _ in 0..=m {
+        if xi_power == Scalar::ZERO {
+            return Err(ProofError::InvalidChallenge);
+        }
+
+        xi_powers.push(xi_power);
+        xi_power *= xi;
+    }
+
+    Ok(xi_powers)
+}
+
+#[cfg(test)]
+mod test {
+    use curve25519_dalek::Scalar;
+
+    use super::challenge_powers;
+    use crate::parallel::proof::ProofError;
+
+    #[test]
+    fn test_challenge_powers() {
+        // A nonzero challenge produces its powers
+        let xi = Scalar::from(3u64);
+        assert_eq!(challenge_powers(&xi, 2).unwrap(), [Scalar::ONE, xi, xi * xi]);
+
+        // A zero challenge is rejected
+        assert!(matches!(
+            challenge_powers(&Scalar::ZERO, 2),
+            Err(ProofError::InvalidChallenge)
+        ));
+    }
+}
@@ -1,12 +1,20 @@
 // Copyright (c) 2024, The Tari Project
 // SPDX-License-Identifier: BSD-3-Clause
 
+use alloc::vec::Vec;
+
 use curve25519_dalek::{RistrettoPoint, Scalar};
 use rand_core::CryptoRngCore;
 use snafu::prelude::*;
 use zeroize::{Zeroize, ZeroizeOnDrop, Zeroizing};
 
-use crate::parallel::TriptychParameters;
+use crate::{gray::GrayIterator, parallel::TriptychParameters};
+
+// Size of the serialized index in bytes
+const SERIALIZED_INDEX_BYTES: usize = 4;
+
+// Size of serialized scalars in bytes
+const SERIALIZED_SCALAR_BYTES: usize = 32;
 
 /// A Triptych proof witness.
 ///
@@ -31,6 +39,9 @@ pub enum WitnessError {
         /// The reason for the parameter error.
         reason: &'static str,
     },
+    /// Witness deserialization failed.
+    #[snafu(display("Witness deserialization failed"))]
+    FailedDeserialization,
 }
 
 impl TriptychWitness {
@@ -82,6 +93,61 @@ impl TriptychWitness {
         }
     }
 
+    /// Serialize a [`TriptychWitness`] to a canonical byte vector.
+    ///
+    /// The encoding contains the index, signing key, and auxiliary key, but not the [`TriptychParameters`].
+    /// Since it contains secret data, the result is zeroized when dropped.
+    pub fn to_bytes(&self) -> Zeroizing<Vec<u8>> {
+        // This cannot overflow
+        #[allow(clippy::arithmetic_side_effects)]
+        let mut result = Zeroizing::new(Vec::with_capacity(SERIALIZED_INDEX_BYTES + 2 * SERIALIZED_SCALAR_BYTES));
+        result.extend_from_slice(&self.l.to_le_bytes());
+        result.extend_from_slice(self.r.as_bytes());
+        result.extend_from_slice(self.r1.as_bytes());
+
+        result
+    }
+
+    /// Deserialize a [`TriptychWitness`] from a canonical byte slice and [`TriptychParameters`] `params`.
+    ///
+    /// The buffers this function uses to hold secret data are zeroized when dropped. This cannot cover copies that the
+    /// compiler makes, such as when a scalar encoding is passed by value to the curve library, so secret data may
+    /// remain in stack memory that is later reused. Since `bytes` contains secret data, you should zeroize it yourself
+    /// after use.
+    ///
+    /// If `bytes` does not represent a canonical encoding of a witness that is valid for `params`, returns a
+    /// [`WitnessError`].
+    pub fn from_bytes(params: &TriptychParameters, bytes: &[u8]) -> Result<Self, WitnessError> {
+        // This cannot overflow
+        #[allow(clippy::arithmetic_side_effects)]
+        if bytes.len() != SERIALIZED_INDEX_BYTES + 2 * SERIALIZED_SCALAR_BYTES {
+            return Err(WitnessError::FailedDeserialization);
+        }
+        let (l_slice, r_slices) = bytes.split_at(SERIALIZED_INDEX_BYTES);
+        let (r_slice, r1_slice) = r_slices.split_at(SERIALIZED_SCALAR_BYTES);
+
+        // Parse the index
+        let mut l_bytes = Zeroizing::new([0u8; SERIALIZED_INDEX_BYTES]);
+        l_bytes.copy_from_slice(l_slice);
+        let l = Zeroizing::new(u32::from_le_bytes(*l_bytes));
+
+        // Parse the signing key and auxiliary key
+        let r = Self::parse_scalar(r_slice)?;
+        let r1 = Self::parse_scalar(r1_slice)?;
+
+        Self::new(params, *l, &r, &r1).map_err(|_| WitnessError::FailedDeserialization)
+    }
+
+    /// Parse a canonical scalar, holding it in zeroizing memory.
+    fn parse_scalar(slice: &[u8]) -> Result<Zeroizing<Scalar>, WitnessError> {
+        let mut bytes = Zeroizing::new([0u8; SERIALIZED_SCALAR_BYTES]);
+        bytes.copy_from_slice(slice);
+
+        Option::<Scalar>::from(Scalar::from_canonical_bytes(*bytes))
+            .map(Zeroizing::new)
+            .ok_or(WitnessError::FailedDeserialization)
+    }
+
     /// Get the [`TriptychParameters`] from this [`TriptychWitness`].
     pub fn get_params(&self) -> &TriptychParameters {
         &self.params
@@ -102,6 +168,16 @@ impl TriptychWitness {
         &self.r1
     }
 
+    /// Get the Gray code decomposition of the index from this [`TriptychWitness`].
+    ///
+    /// This is the base-`n` Gray code digit vector of length `m` that corresponds to the index. Since the index is
+    /// secret, the decomposition is computed in constant time and is zeroized when dropped.
+    ///
+    /// If the decomposition fails, returns `None`.
+    pub fn gray_decomposition(&self) -> Option<Zeroizing<Vec<u32>>> {
+        GrayIterator::decompose(self.params.get_n(), self.params.get_m(), self.l).map(Zeroizing::new)
+    }
+
     /// Compute the linking tag for the [`TriptychWitness`] signing key.
     #[allow(non_snake_case)]
     pub fn compute_linking_tag(&self) -> RistrettoPoint {
@@ -117,4 +193,135 @@ impl TriptychWitness {
     pub fn compute_auxiliary_verification_key(&self) -> RistrettoPoint {
         self.r1 * self.params.get_G1()
     }
+
+    /// Compute the entries expected in a [`TriptychInputSet`](`crate::parallel::TriptychInputSet`) for this
+    /// [`TriptychWitness`], given the `offset` of a [`TriptychStatement`](`crate::parallel::TriptychStatement`).
+    ///
+    /// Returns the index `l`, the verification key `r * G` expected at index `l` of the verification keys, and the
+    /// value `r1 * G1 + offset` expected at index `l` of the auxiliary verification keys. These can be checked against
+    /// an input set before proving.
+    pub fn expected_ring_entries(&self, offset: &RistrettoPoint) -> (u32, RistrettoPoint, RistrettoPoint) {
+        (
+            self.l,
+            self.compute_verification_key(),
+            self.compute_auxiliary_verification_key() + offset,
+        )
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use alloc::{vec, vec::Vec};
+
+    use curve25519_dalek::RistrettoPoint;
+    use rand_chacha::ChaCha12Rng;
+    use rand_core::SeedableRng;
+
+    use crate::{
+        gray::GrayIterator,
+        parallel::{TriptychInputSet, TriptychParameters, TriptychProof, TriptychStatement, TriptychWitness},
+        Transcript,
+    };
+
+    #[test]
+    fn test_zeroize_on_drop() {
+        use core::{mem::MaybeUninit, ptr};
+
+        use curve25519_dalek::Scalar;
+
+        let params = TriptychParameters::new(2, 4).unwrap();
+        let mut rng = ChaCha12Rng::seed_from_u64(8675309);
+        let witness = TriptychWitness::random(&params, &mut rng);
+
+        // Drop the witness in place, leaving its memory allocated so it can be inspected
+        let mut slot = MaybeUninit::new(witness);
+        let witness = slot.as_mut_ptr();
+        // SAFETY: The witness is initialized, and is dropped exactly once. Afterward, only the index and keys are read;
+        // these have no drop glue, and their memory remains allocated.
+        let (l, r, r1) = unsafe {
+            ptr::drop_in_place(witness);
+            (
+                ptr::addr_of!((*witness).l).read(),
+                ptr::addr_of!((*witness).r).read(),
+                ptr::addr_of!((*witness).r1).read(),
+            )
+        };
+
+        // The index and both keys are scrubbed
+        assert_eq!(l, 0);
+        assert_eq!(r, Scalar::ZERO);
+        assert_eq!(r1, Scalar::ZERO);
+    }
+
+    #[test]
+    #[allow(non_snake_case)]
+    fn test_expected_ring_entries() {
+        let params = TriptychParameters::new(2, 4).unwrap();
+        let mut rng = ChaCha12Rng::seed_from_u64(8675309);
+        let witness = TriptychWitness::random(&params, &mut rng);
+        let offset = RistrettoPoint::random(&mut rng);
+
+        // Build an input set from the expected entries
+        let (l, M_l, M1_l) = witness.expected_ring_entries(&offset);
+        assert_eq!(l, witness.get_l());
+        let mut M = (0..params.get_N())
+            .map(|_| RistrettoPoint::random(&mut rng))
+            .collect::<Vec<RistrettoPoint>>();
+        let mut M1 = (0..params.get_N())
+            .map(|_| RistrettoPoint::random(&mut rng))
+            .collect::<Vec<RistrettoPoint>>();
+        M[l as usize] = M_l;
+        M1[l as usize] = M1_l;
+        let input_set = TriptychInputSet::new(&M, &M1).unwrap();
+
+        // The resulting statement is valid for the witness
+        let statement = TriptychStatement::new(&params, &input_set, &offset, &witness.compute_linking_tag()).unwrap();
+        assert!(TriptychProof::prove_with_rng(&witness, &statement, &mut rng, &mut Transcript::new(b"Test")).is_ok());
+    }
+
+    #[test]
+    fn test_gray_decomposition() {
+        let params = TriptychParameters::new(3, 4).unwrap();
+        let mut rng = ChaCha12Rng::seed_from_u64(8675309);
+        let witness = TriptychWitness::random(&params, &mut rng);
+
+        // The decomposition matches the Gray code position of the index
+        let (index, digits) = GrayIterator::new(params.get_n(), params.get_m())
+            .unwrap()
+            .scan(vec![0; params.get_m() as usize], |digits, (i, _, new)| {
+                digits[i] = new;
+                Some(digits.clone())
+            })
+            .enumerate()
+            .nth(witness.get_l() as usize)
+            .unwrap();
+        assert_eq!(index, witness.get_l() as usize);
+        assert_eq!(*witness.gray_decomposition().unwrap(), digits);
+    }
+
+    #[test]
+    fn test_serialize_deserialize() {
+        let params = TriptychParameters::new(2, 4).unwrap();
+        let mut rng = ChaCha12Rng::seed_from_u64(8675309);
+        let witness = TriptychWitness::random(&params, &mut rng);
+
+        // Serialize and deserialize the witness
+        let serialized = witness.to_bytes();
+        let deserialized = TriptychWitness::from_bytes(&params, &serialized).unwrap();
+        assert_eq!(deserialized.get_l(), witness.get_l());
+        assert_eq!(deserialized.get_r(), witness.get_r());
+        assert_eq!(deserialized.get_r1(), witness.get_r1());
+
+        // Invalid encodings fail
+        assert!(TriptychWitness::from_bytes(&params, &serialized[1..]).is_err());
+        let mut evil = serialized.clone();
+        evil[0..4].copy_from_slice(&params.get_N().to_le_bytes());
+        assert!(TriptychWitness::from_bytes(&params, &evil).is_err());
+        let mut evil = serialized.clone();
+        evil[4..36].copy_from_slice(&[0xFF; 32]);
+        assert!(TriptychWitness::from_bytes(&params, &evil).is_err());
+        let mut evil = serialized;
+        evil[36..].copy_from_slice(&[0u8; 32]);
+        assert!(TriptychWitness::from_bytes(&params, &evil).is_err());
+    }
 }
@@ -1,19 +1,33 @@
 // Copyright (c) 2024, The Tari Project
 // SPDX-License-Identifier: BSD-3-Clause
 
-use alloc::{sync::Arc, vec, vec::Vec};
-use core::iter::once;
+use alloc::{format, string::String, sync::Arc, vec, vec::Vec};
+use core::{hash::Hash, iter::once};
 
 use blake3::Hasher;
+#[cfg(feature = "borsh")]
+use borsh::{io, BorshDeserialize, BorshSerialize};
+#[cfg(feature = "serde")]
+use curve25519_dalek::ristretto::CompressedRistretto;
 use curve25519_dalek::{
     constants::RISTRETTO_BASEPOINT_POINT,
-    traits::{MultiscalarMul, VartimeMultiscalarMul},
+    ristretto::VartimeRistrettoPrecomputation,
+    traits::{MultiscalarMul, VartimeMultiscalarMul, VartimePrecomputedMultiscalarMul},
     RistrettoPoint,
     Scalar,
 };
+#[cfg(feature = "serde")]
+use serde::{ser::SerializeStruct, Deserialize, Deserializer, Serialize, Serializer};
 use snafu::prelude::*;
 
-use crate::{domains, util::OperationTiming, Transcript};
+#[cfg(feature = "borsh")]
+use crate::util::deserialize_point;
+use crate::{
+    domains,
+    util::{derive_application_U, generator_hasher, generator_seed_prefix, small_multiple_relation, OperationTiming},
+    Transcript,
+    TriptychProof,
+};
 
 /// Public parameters used for generating and verifying Triptych proofs.
 ///
@@ -21,8 +35,12 @@ use crate::{domains, util::OperationTiming, Transcript};
 /// `G` and `U` required by the protocol. You can either use [`TriptychParameters::new`] to have these generators
 /// defined securely for you, or use [`TriptychParameters::new_with_generators`] if your use case requires specific
 /// values for these.
+///
+/// Linking tags are defined using `U`, so a signing key produces the same linking tag for all parameters sharing `U`.
+/// If linking tags must not be linkable across applications, use [`TriptychParameters::new_for_application`] to
+/// derive `U` from an application identifier.
 #[allow(non_snake_case)]
-#[derive(Clone, Eq, PartialEq)]
+#[derive(Clone)]
 pub struct TriptychParameters {
     n: u32,
     m: u32,
@@ -30,6 +48,9 @@ pub struct TriptychParameters {
     U: RistrettoPoint,
     CommitmentG: Arc<Vec<RistrettoPoint>>,
     CommitmentH: RistrettoPoint,
+    // This is only needed to serialize parameters with seeded generators
+    #[cfg_attr(not(any(feature = "borsh", feature = "serde")), allow(dead_code))]
+    seed_prefix: Option<[u8; 32]>,
     hash: Vec<u8>,
 }
 
@@ -42,32 +63,184 @@ pub enum ParameterError {
         /// The reason for the parameter error.
         reason: &'static str,
     },
+    /// The size `n**m` exceeded a maximum.
+    #[snafu(display("The size `n**m` exceeded the maximum of {maximum}"))]
+    ExceededMaximumSize {
+        /// The maximum size.
+        maximum: u32,
+    },
+}
+
+/// The largest exponent `m` permitted for any [`TriptychParameters`].
+///
+/// Since `n**m` may not overflow [`prim@u32`], this bound is attained by the smallest base `n = 2`.
+pub const MAX_EXPONENT: u32 = u32::BITS - 1;
+
+/// Determine if a base `n` and exponent `m` are valid for [`TriptychParameters`].
+///
+/// It must be the case that `n > 1`, `m > 1`, and that `n**m` does not overflow [`prim@u32`].
+/// Since this is a `const fn`, it can be used in a `const` assertion to check fixed parameters at compile time.
+pub const fn validate_params(n: u32, m: u32) -> bool {
+    n > 1 && m > 1 && n.checked_pow(m).is_some()
+}
+
+/// Helper to check fixed parameters at compile time.
+#[allow(non_upper_case_globals)]
+struct ParameterCheck<const n: u32, const m: u32>;
+
+#[allow(non_upper_case_globals)]
+impl<const n: u32, const m: u32> ParameterCheck<n, m> {
+    const VALID: () = assert!(validate_params(n, m), "invalid Triptych parameters");
 }
 
 impl TriptychParameters {
+    /// Generate new [`TriptychParameters`] for Triptych proofs using a fixed base `n` and exponent `m`.
+    ///
+    /// The base and exponent are checked at compile time, so invalid values fail to compile rather than returning a
+    /// [`ParameterError`]; see [`validate_params`] for the requirements.
+    ///
+    /// This function produces group generators `G` and `U` for you, exactly as [`TriptychParameters::new`] does.
+    #[allow(non_upper_case_globals)]
+    pub fn new_fixed<const n: u32, const m: u32>() -> Self {
+        #[allow(clippy::let_unit_value)]
+        let () = ParameterCheck::<n, m>::VALID;
+
+        match Self::new(n, m) {
+            Ok(params) => params,
+            // This cannot happen since the parameters were checked at compile time
+            Err(_) => unreachable!(),
+        }
+    }
+
     /// Generate new [`TriptychParameters`] for Triptych proofs.
     ///
     /// The base `n > 1` and exponent `m > 1` define the size of verification key vectors, so it must be the case that
     /// `n**m` does not overflow [`prim@u32`]. If any of these conditions is not met, returns a [`ParameterError`].
     ///
+    /// The [`prim@u32`] bound on `N = n**m` is deliberate. Proving and verification both operate on every verification
+    /// key in the input set, with time and memory linear in `N`, so the input set cannot be addressed sparsely. An
+    /// input set larger than this would require more than 128 GiB of compressed verification keys alone.
+    ///
     /// This function produces group generators `G` and `U` for you.
     /// If your use case requires specific generators, use [`TriptychParameters::new_with_generators`] instead.
     #[allow(non_snake_case)]
     pub fn new(n: u32, m: u32) -> Result<Self, ParameterError> {
         // Use the default base point for `G` (this is arbitrary)
         let G = RISTRETTO_BASEPOINT_POINT;
+        let U = Self::default_U();
 
+        Self::new_with_generators(n, m, &G, &U)
+    }
+
+    /// Produce the default group generator `U` used by [`TriptychParameters::new`].
+    #[allow(non_snake_case)]
+    fn default_U() -> RistrettoPoint {
+        Self::derive_U(None)
+    }
+
+    /// Produce the group generator `U`, using an optional seed `prefix`.
+    #[allow(non_snake_case)]
+    fn derive_U(prefix: Option<&[u8; 32]>) -> RistrettoPoint {
         // Use `BLAKE3` to generate `U`
         let mut U_bytes = [0u8; 64];
-        let mut hasher = Hasher::new();
-        hasher.update(domains::POINT_U.as_bytes());
-        hasher.update(&domains::VERSION.to_le_bytes());
-        hasher.finalize_xof().fill(&mut U_bytes);
-        let U = RistrettoPoint::from_uniform_bytes(&U_bytes);
+        generator_hasher(domains::POINT_U, prefix)
+            .finalize_xof()
+            .fill(&mut U_bytes);
+
+        RistrettoPoint::from_uniform_bytes(&U_bytes)
+    }
+
+    /// Generate new [`TriptychParameters`] for Triptych proofs with a bounded size.
+    ///
+    /// This is identical to [`TriptychParameters::new`], except that it additionally checks that `N = n**m` does not
+    /// exceed `max_N`. If it does, returns [`ParameterError::ExceededMaximumSize`]; this check is made before any
+    /// generators are produced.
+    #[allow(non_snake_case)]
+    pub fn new_bounded(n: u32, m: u32, max_N: u32) -> Result<Self, ParameterError> {
+        // Invalid parameters are caught by the unbounded constructor
+        if n.checked_pow(m).is_some_and(|N| N > max_N) {
+            return Err(ParameterError::ExceededMaximumSize { maximum: max_N });
+        }
+
+        Self::new(n, m)
+    }
+
+    /// Generate new [`TriptychParameters`] for verifying a serialized Triptych proof.
+    ///
+    /// This reads the base `n` and exponent `m` encoded in `bytes`, which must begin with the canonical encoding
+    /// produced by [`TriptychProof::to_bytes`](`crate::proof::TriptychProof::to_bytes`), and produces parameters with
+    /// the same group generators as [`TriptychParameters::new`]. It is only useful if the prover also used those
+    /// generators.
+    ///
+    /// Producing parameters derives `n * m` group generators, and verification takes time linear in `N = n**m`, so a
+    /// proof encoding could otherwise force a large amount of work before the proof is checked. As with
+    /// [`TriptychParameters::new_bounded`], if `N` exceeds `max_N`, returns [`ParameterError::ExceededMaximumSize`]
+    /// before any generators are produced.
+    ///
+    /// The rest of the proof is not decoded or checked. If the encoded values are not valid, returns a
+    /// [`ParameterError`].
+    #[allow(non_snake_case)]
+    pub fn from_proof_bytes(bytes: &[u8], max_N: u32) -> Result<Self, ParameterError> {
+        let mut dimensions = bytes.chunks_exact(4).take(2).map(|chunk| {
+            let mut dimension_bytes = [0u8; 4];
+            dimension_bytes.copy_from_slice(chunk);
+            u32::from_le_bytes(dimension_bytes)
+        });
+        let (n_minus_1, m) = dimensions
+            .next()
+            .zip(dimensions.next())
+            .ok_or(ParameterError::InvalidParameter {
+                reason: "proof encoding was too short",
+            })?;
+        let n = n_minus_1.checked_add(1).ok_or(ParameterError::InvalidParameter {
+            reason: "`n` overflowed `u32`",
+        })?;
+
+        Self::new_bounded(n, m, max_N)
+    }
+
+    /// Generate new [`TriptychParameters`] for Triptych proofs that are specific to an application.
+    ///
+    /// The base `n > 1` and exponent `m > 1` define the size of verification key vectors, so it must be the case that
+    /// `n**m` does not overflow [`prim@u32`]. If any of these conditions is not met, returns a [`ParameterError`].
+    ///
+    /// This function produces group generators `G` and `U` for you. The generator `G` is the same as for
+    /// [`TriptychParameters::new`], so verification keys can be shared between applications. The generator `U` is
+    /// derived from the application identifier `app_id` using `BLAKE3`. Since linking tags are defined using `U`, the
+    /// same signing key produces linking tags that cannot be linked across applications with distinct identifiers.
+    #[allow(non_snake_case)]
+    pub fn new_for_application(n: u32, m: u32, app_id: &[u8]) -> Result<Self, ParameterError> {
+        // Use the default base point for `G` (this is arbitrary)
+        let G = RISTRETTO_BASEPOINT_POINT;
+
+        // Use `BLAKE3` to generate `U` from the application identifier
+        let U = derive_application_U(app_id);
 
         Self::new_with_generators(n, m, &G, &U)
     }
 
+    /// Generate new [`TriptychParameters`] for Triptych proofs with generators derived from a seed.
+    ///
+    /// The base `n > 1` and exponent `m > 1` define the size of verification key vectors, so it must be the case that
+    /// `n**m` does not overflow [`prim@u32`]. If any of these conditions is not met, returns a [`ParameterError`].
+    ///
+    /// This function produces group generators for you. The generator `G` is the same as for
+    /// [`TriptychParameters::new`], so verification keys can be shared. The generator `U` and all commitment generators
+    /// are derived from `seed` using `BLAKE3`. The same seed always produces the same parameters, while distinct seeds
+    /// produce independent generators. Since linking tags are defined using `U`, the same signing key produces linking
+    /// tags that cannot be linked across distinct seeds.
+    #[allow(non_snake_case)]
+    pub fn new_with_seed(n: u32, m: u32, seed: &[u8]) -> Result<Self, ParameterError> {
+        // Use the default base point for `G` (this is arbitrary)
+        let G = RISTRETTO_BASEPOINT_POINT;
+
+        // Every other generator derivation is prefixed by the seed
+        let prefix = generator_seed_prefix(seed);
+        let U = Self::derive_U(Some(&prefix));
+
+        Self::new_internal(n, m, &G, &U, Some(&prefix))
+    }
+
     /// Generate new [`TriptychParameters`] for Triptych proofs.
     ///
     /// The base `n > 1` and exponent `m > 1` define the size of verification key vectors, so it must be the case that
@@ -81,6 +254,18 @@ impl TriptychParameters {
     /// If you'd rather have the generators securely defined for you, use [`TriptychParameters::new`] instead.
     #[allow(non_snake_case)]
     pub fn new_with_generators(n: u32, m: u32, G: &RistrettoPoint, U: &RistrettoPoint) -> Result<Self, ParameterError> {
+        Self::new_internal(n, m, G, U, None)
+    }
+
+    /// Generate new [`TriptychParameters`], deriving commitment generators using an optional seed `prefix`.
+    #[allow(non_snake_case)]
+    fn new_internal(
+        n: u32,
+        m: u32,
+        G: &RistrettoPoint,
+        U: &RistrettoPoint,
+        prefix: Option<&[u8; 32]>,
+    ) -> Result<Self, ParameterError> {
         // These bounds are required by the protocol
         if n < 2 {
             return Err(ParameterError::InvalidParameter { reason: "`n < 2`" });
@@ -98,16 +283,13 @@ impl TriptychParameters {
 
         // Use `BLAKE3` to generate `CommitmentH`
         let mut CommitmentH_bytes = [0u8; 64];
-        let mut hasher = Hasher::new();
-        hasher.update(domains::POINT_COMMITMENT_H.as_bytes());
-        hasher.update(&domains::VERSION.to_le_bytes());
-        hasher.finalize_xof().fill(&mut CommitmentH_bytes);
+        generator_hasher(domains::POINT_COMMITMENT_H, prefix)
+            .finalize_xof()
+            .fill(&mut CommitmentH_bytes);
         let CommitmentH = RistrettoPoint::from_uniform_bytes(&CommitmentH_bytes);
 
         // Use `BLAKE3` for the commitment matrix generators
-        let mut hasher = Hasher::new();
-        hasher.update(domains::POINT_COMMITMENT_G.as_bytes());
-        hasher.update(&domains::VERSION.to_le_bytes());
+        let mut hasher = generator_hasher(domains::POINT_COMMITMENT_G, prefix);
         hasher.update(&n.to_le_bytes());
         hasher.update(&m.to_le_bytes());
         let mut hasher_xof = hasher.finalize_xof();
@@ -122,28 +304,102 @@ impl TriptychParameters {
             .collect::<Vec<RistrettoPoint>>();
 
         // Use Merlin for the transcript hash
+        let hash = Self::compute_hash(n, m, G, U, &CommitmentG, &CommitmentH);
+
+        Ok(TriptychParameters {
+            n,
+            m,
+            G: *G,
+            U: *U,
+            CommitmentG: Arc::new(CommitmentG),
+            CommitmentH,
+            seed_prefix: prefix.copied(),
+            hash,
+        })
+    }
+
+    /// Perform a best-effort check that the group generators of these [`TriptychParameters`] are not trivially related.
+    ///
+    /// The generators `G` and `U` must be independent, which cannot be checked. However, this checks for gross misuse:
+    /// no generator may be the identity, or a small multiple (or negated small multiple) of another generator, of the
+    /// commitment generator `CommitmentH`, or of the default generator that [`TriptychParameters::new`] uses for a
+    /// different role. If any such relation is found, returns a [`ParameterError`].
+    ///
+    /// Passing this check does not mean that the generators are independent!
+    pub fn check_generators(&self) -> Result<(), ParameterError> {
+        let generators = [self.G, self.U];
+        let defaults = [RISTRETTO_BASEPOINT_POINT, Self::default_U()];
+
+        for (i, generator) in generators.iter().enumerate() {
+            let related = generators
+                .iter()
+                .skip(i)
+                .skip(1)
+                .any(|other| small_multiple_relation(generator, other)) ||
+                small_multiple_relation(generator, &self.CommitmentH) ||
+                defaults
+                    .iter()
+                    .enumerate()
+                    .any(|(j, default)| i != j && small_multiple_relation(generator, default));
+            if related {
+                return Err(ParameterError::InvalidParameter {
+                    reason: "group generators were trivially related",
+                });
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Compute a cryptographic hash representation of parameter data using Merlin.
+    #[allow(non_snake_case)]
+    fn compute_hash(
+        n: u32,
+        m: u32,
+        G: &RistrettoPoint,
+        U: &RistrettoPoint,
+        CommitmentG: &[RistrettoPoint],
+        CommitmentH: &RistrettoPoint,
+    ) -> Vec<u8> {
         let mut transcript = Transcript::new(domains::TRANSCRIPT_PARAMETERS.as_bytes());
         transcript.append_u64(b"version", domains::VERSION);
         transcript.append_message(b"n", &n.to_le_bytes());
         transcript.append_message(b"m", &m.to_le_bytes());
         transcript.append_message(b"G", G.compress().as_bytes());
         transcript.append_message(b"U", U.compress().as_bytes());
-        for item in &CommitmentG {
+        for item in CommitmentG {
             transcript.append_message(b"CommitmentG", item.compress().as_bytes());
         }
         transcript.append_message(b"CommitmentH", CommitmentH.compress().as_bytes());
         let mut hash = vec![0u8; domains::TRANSCRIPT_HASH_BYTES];
         transcript.challenge_bytes(b"hash", &mut hash);
 
-        Ok(TriptychParameters {
-            n,
-            m,
-            G: *G,
-            U: *U,
-            CommitmentG: Arc::new(CommitmentG),
-            CommitmentH,
-            hash,
-        })
+        hash
+    }
+
+    /// Generate new [`TriptychParameters`] from these, with the group generator `U` rotated for a given `epoch`.
+    ///
+    /// The new generator `U` is derived from the existing one and `epoch` using `BLAKE3`, while all other generators
+    /// are kept. Since linking tags are defined using `U`, a signing key produces a different linking tag for each
+    /// epoch, and linking tags from different epochs cannot be linked to each other. The resulting parameters have a
+    /// distinct hash, so proofs are bound to their epoch.
+    #[allow(non_snake_case)]
+    pub fn with_rotated_U(&self, epoch: u64) -> Self {
+        // Use `BLAKE3` to generate the rotated `U`
+        let mut U_bytes = [0u8; 64];
+        let mut hasher = Hasher::new();
+        hasher.update(domains::POINT_U_ROTATION.as_bytes());
+        hasher.update(&domains::VERSION.to_le_bytes());
+        hasher.update(self.U.compress().as_bytes());
+        hasher.update(&epoch.to_le_bytes());
+        hasher.finalize_xof().fill(&mut U_bytes);
+        let U = RistrettoPoint::from_uniform_bytes(&U_bytes);
+
+        Self {
+            U,
+            hash: Self::compute_hash(self.n, self.m, &self.G, &U, &self.CommitmentG, &self.CommitmentH),
+            ..self.clone()
+        }
     }
 
     /// Commit to a matrix.
@@ -217,6 +473,103 @@ impl TriptychParameters {
         self.n.pow(self.m)
     }
 
+    /// Get the largest ring size supported by these [`TriptychParameters`].
+    ///
+    /// This is the verification key vector size `N`; smaller rings must be padded to this size. Use
+    /// [`TriptychParameters::suggest`] to choose parameters for a target ring size.
+    pub fn max_ring_size(&self) -> u32 {
+        self.get_N()
+    }
+
+    /// Suggest a base `n` and exponent `m` whose size `N == n**m` is at least `target_N`.
+    ///
+    /// Of all valid choices covering `target_N`, this returns the one with the smallest serialized proof size, as
+    /// given by [`TriptychProof::serialized_size_for`]. Ties are broken by the smallest `N`, which is cheaper to
+    /// verify. Returns `None` if no valid choice covers `target_N`.
+    #[allow(non_snake_case)]
+    pub fn suggest(target_N: u32) -> Option<(u32, u32)> {
+        // Track the best choice as `(size, N, n, m)`
+        let mut best: Option<(usize, u32, u32, u32)> = None;
+
+        for n in 2..=u32::MAX {
+            // If even `m == 2` overflows, no larger base is valid
+            if n.checked_pow(2).is_none() {
+                break;
+            }
+
+            // Find the smallest exponent that covers the target, if any
+            let Some((m, N)) = (2..=MAX_EXPONENT)
+                .map_while(|m| n.checked_pow(m).map(|N| (m, N)))
+                .find(|(_, N)| *N >= target_N)
+            else {
+                continue;
+            };
+
+            let size = TriptychProof::serialized_size_for(n, m)?;
+            if best.map_or(true, |(best_size, best_N, _, _)| (size, N) < (best_size, best_N)) {
+                best = Some((size, N, n, m));
+            }
+
+            // Larger bases can only increase the proof size once the smallest exponent is reached
+            if m == 2 {
+                break;
+            }
+        }
+
+        best.map(|(_, _, n, m)| (n, m))
+    }
+
+    /// Get the largest number of proofs that can be verified in a single batch using these [`TriptychParameters`].
+    ///
+    /// Batch verification uses a final check whose size grows with the batch size, and this size must not overflow
+    /// [`prim@usize`] on the current target. Larger batches are rejected by the verifier, and should be split.
+    pub fn max_batch_size(&self) -> usize {
+        self.batch_check_sizes()
+            .and_then(|(common, per_proof)| usize::MAX.checked_sub(common)?.checked_div(per_proof))
+            .unwrap_or(0)
+    }
+
+    /// Get the size of the final batch verification check for `batch_size` proofs.
+    ///
+    /// Returns `None` if the size overflows [`prim@usize`].
+    pub(crate) fn batch_check_size(&self, batch_size: usize) -> Option<usize> {
+        let (common, per_proof) = self.batch_check_sizes()?;
+
+        batch_size.checked_mul(per_proof)?.checked_add(common)
+    }
+
+    /// Get the number of common and per-proof elements in the final batch verification check.
+    #[allow(non_snake_case)]
+    fn batch_check_sizes(&self) -> Option<(usize, usize)> {
+        let n = self.n as usize;
+        let m = self.m as usize;
+        let N = self.get_N() as usize;
+
+        let common = n
+            .checked_mul(m)? // CommitmentG
+            .checked_add(N)? // M
+            .checked_add(3)?; // G, CommitmentH, U
+        let per_proof = m
+            .checked_mul(2)? // X, Y
+            .checked_add(5)?; // A, B, C, D, J
+
+        Some((common, per_proof))
+    }
+
+    /// Get a short descriptor that identifies these [`TriptychParameters`], suitable for logging.
+    ///
+    /// The descriptor has the form `triptych-n2-m8-N256-0123abcd`, and includes the base `n`, exponent `m`, size `N`,
+    /// and the first four bytes of the parameter hash in hexadecimal. Since the hash commits to all generators,
+    /// parameters with the same dimensions but different generators have different descriptors.
+    pub fn descriptor(&self) -> String {
+        let mut descriptor = format!("triptych-n{}-m{}-N{}-", self.n, self.m, self.get_N());
+        for byte in self.hash.iter().take(4) {
+            descriptor.push_str(&format!("{byte:02x}"));
+        }
+
+        descriptor
+    }
+
     /// Get the value `CommitmentG` from these [`TriptychParameters`].
     #[allow(non_snake_case)]
     pub(crate) fn get_CommitmentG(&self) -> &Vec<RistrettoPoint> {
@@ -233,4 +586,527 @@ impl TriptychParameters {
     pub(crate) fn get_hash(&self) -> &[u8] {
         &self.hash
     }
+
+    /// Determine if these [`TriptychParameters`] are equal to `other` by comparing only their hashes.
+    ///
+    /// The hash binds the dimensions and every group generator, so equal hashes imply equal parameters under the
+    /// collision resistance of the hash. This avoids comparing every commitment generator, and is how the `==`
+    /// operator is implemented.
+    pub fn eq_fast(&self, other: &Self) -> bool {
+        self.hash == other.hash
+    }
+
+    /// Get the fixed generators used in the final batch verification check, in the order they are precomputed.
+    pub(crate) fn fixed_points(&self) -> impl Iterator<Item = &RistrettoPoint> {
+        once(&self.G)
+            .chain(self.CommitmentG.iter())
+            .chain(once(&self.CommitmentH))
+            .chain(once(&self.U))
+    }
+
+    /// Build a [`TriptychPrecomputation`] for the fixed generators of these [`TriptychParameters`].
+    ///
+    /// The precomputation can be used with
+    /// [`TriptychProof::verify_batch_precomputed`](`crate::TriptychProof::verify_batch_precomputed`) for any proofs
+    /// using these parameters. It is relatively expensive to build and can be cloned cheaply, so it should be built
+    /// once and reused.
+    pub fn build_precomputation(&self) -> TriptychPrecomputation {
+        TriptychPrecomputation {
+            hash: self.hash.clone(),
+            table: Arc::new(VartimeRistrettoPrecomputation::new(self.fixed_points())),
+        }
+    }
+}
+
+/// Precomputed tables for the fixed generators of [`TriptychParameters`].
+///
+/// Batch verification combines generators that are fixed by the parameters with points that vary by proof and input
+/// set. Precomputing tables for the fixed generators speeds up their part of the final check. The gain is largest
+/// for small input sets, where the fixed generators are a larger share of the check; for large input sets, the input
+/// set verification keys dominate and performance is similar to verification without a precomputation.
+/// Use [`TriptychParameters::build_precomputation`] to build this.
+#[derive(Clone)]
+pub struct TriptychPrecomputation {
+    hash: Vec<u8>,
+    table: Arc<VartimeRistrettoPrecomputation>,
+}
+
+impl TriptychPrecomputation {
+    /// Get the hash of the [`TriptychParameters`] used to build this [`TriptychPrecomputation`].
+    pub(crate) fn get_params_hash(&self) -> &[u8] {
+        &self.hash
+    }
+
+    /// Get the precomputed table.
+    pub(crate) fn get_table(&self) -> &VartimeRistrettoPrecomputation {
+        &self.table
+    }
+}
+
+impl PartialEq for TriptychParameters {
+    fn eq(&self, other: &Self) -> bool {
+        self.eq_fast(other)
+    }
+}
+
+impl Eq for TriptychParameters {}
+
+impl Hash for TriptychParameters {
+    /// Hash only the precomputed parameter hash, which binds all of its data.
+    ///
+    /// This is consistent with equality, and relies on the collision resistance of the precomputed hash.
+    fn hash<H: core::hash::Hasher>(&self, state: &mut H) {
+        self.hash.hash(state);
+    }
+}
+
+#[cfg(feature = "borsh")]
+impl BorshSerialize for TriptychParameters {
+    fn serialize<W: io::Write>(&self, writer: &mut W) -> io::Result<()> {
+        BorshSerialize::serialize(&self.n, writer)?;
+        BorshSerialize::serialize(&self.m, writer)?;
+        BorshSerialize::serialize(self.G.compress().as_bytes(), writer)?;
+        BorshSerialize::serialize(self.U.compress().as_bytes(), writer)?;
+        BorshSerialize::serialize(&self.seed_prefix, writer)
+    }
+}
+
+#[cfg(feature = "borsh")]
+impl BorshDeserialize for TriptychParameters {
+    #[allow(non_snake_case)]
+    fn deserialize_reader<R: io::Read>(reader: &mut R) -> io::Result<Self> {
+        let n: u32 = BorshDeserialize::deserialize_reader(reader)?;
+        let m: u32 = BorshDeserialize::deserialize_reader(reader)?;
+        let G = deserialize_point(reader)?;
+        let U = deserialize_point(reader)?;
+        let seed_prefix: Option<[u8; 32]> = BorshDeserialize::deserialize_reader(reader)?;
+
+        // The remaining generators and the hash are recomputed, not read
+        TriptychParameters::new_internal(n, m, &G, &U, seed_prefix.as_ref())
+            .map_err(|_| io::Error::new(io::ErrorKind::InvalidInput, "Invalid Triptych parameters"))
+    }
+}
+
+#[cfg(feature = "serde")]
+impl Serialize for TriptychParameters {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut state = serializer.serialize_struct("TriptychParameters", 5)?;
+        state.serialize_field("n", &self.n)?;
+        state.serialize_field("m", &self.m)?;
+        state.serialize_field("G", self.G.compress().as_bytes())?;
+        state.serialize_field("U", self.U.compress().as_bytes())?;
+        state.serialize_field("seed_prefix", &self.seed_prefix)?;
+        state.end()
+    }
+}
+
+/// The serialized form of [`TriptychParameters`], which omits derived generators and the hash.
+#[cfg(feature = "serde")]
+#[allow(non_snake_case)]
+#[derive(Deserialize)]
+#[serde(rename = "TriptychParameters")]
+struct SerializedParameters {
+    n: u32,
+    m: u32,
+    G: [u8; 32],
+    U: [u8; 32],
+    seed_prefix: Option<[u8; 32]>,
+}
+
+#[cfg(feature = "serde")]
+impl<'de> Deserialize<'de> for TriptychParameters {
+    #[allow(non_snake_case)]
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let serialized = SerializedParameters::deserialize(deserializer)?;
+
+        let error = || serde::de::Error::custom("Invalid Triptych parameters");
+        let G = CompressedRistretto(serialized.G).decompress().ok_or_else(error)?;
+        let U = CompressedRistretto(serialized.U).decompress().ok_or_else(error)?;
+
+        // The remaining generators and the hash are recomputed, not read
+        TriptychParameters::new_internal(serialized.n, serialized.m, &G, &U, serialized.seed_prefix.as_ref())
+            .map_err(|_| error())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use alloc::vec::Vec;
+
+    use curve25519_dalek::{constants::RISTRETTO_BASEPOINT_POINT, traits::Identity, RistrettoPoint};
+    use rand_chacha::ChaCha12Rng;
+    use rand_core::SeedableRng;
+
+    use crate::{
+        parallel,
+        parameters::{validate_params, ParameterError, MAX_EXPONENT},
+        Transcript,
+        TriptychInputSet,
+        TriptychParameters,
+        TriptychProof,
+        TriptychStatement,
+        TriptychWitness,
+    };
+
+    // This is checked at compile time
+    const _: () = assert!(validate_params(2, 8));
+
+    #[test]
+    fn test_validate_params() {
+        // Valid parameters
+        assert!(validate_params(2, 2));
+        assert!(validate_params(2, MAX_EXPONENT));
+        assert!(validate_params(u32::from(u16::MAX), 2));
+
+        // Invalid parameters
+        assert!(!validate_params(0, 2));
+        assert!(!validate_params(1, 2));
+        assert!(!validate_params(2, 0));
+        assert!(!validate_params(2, 1));
+        assert!(!validate_params(2, MAX_EXPONENT + 1));
+        assert!(!validate_params(u32::MAX, 2));
+
+        // This must agree with the runtime check
+        for n in 0..8 {
+            for m in 0..8 {
+                assert_eq!(validate_params(n, m), TriptychParameters::new(n, m).is_ok());
+            }
+        }
+    }
+
+    #[test]
+    #[allow(non_snake_case)]
+    fn test_with_rotated_U() {
+        let params = TriptychParameters::new(2, 4).unwrap();
+
+        // Rotation is deterministic
+        let params_1 = params.with_rotated_U(1);
+        assert!(params_1 == params.with_rotated_U(1));
+
+        // Rotation changes only `U` and the hash
+        assert_eq!(params_1.get_G(), params.get_G());
+        assert_eq!(params_1.get_CommitmentG(), params.get_CommitmentG());
+        assert_eq!(params_1.get_CommitmentH(), params.get_CommitmentH());
+        assert_ne!(params_1.get_U(), params.get_U());
+        assert_ne!(params_1.get_hash(), params.get_hash());
+
+        // Each epoch is distinct
+        let params_2 = params.with_rotated_U(2);
+        assert_ne!(params_1.get_U(), params_2.get_U());
+        assert_ne!(params_1.get_hash(), params_2.get_hash());
+
+        // The hash is computed as if the generators were supplied directly
+        assert!(params_1 == TriptychParameters::new_with_generators(2, 4, params_1.get_G(), params_1.get_U()).unwrap());
+    }
+
+    #[test]
+    fn test_new_for_application() {
+        let params_a = TriptychParameters::new_for_application(2, 4, b"application A").unwrap();
+        let params_b = TriptychParameters::new_for_application(2, 4, b"application B").unwrap();
+
+        // Applications are deterministic
+        assert!(params_a == TriptychParameters::new_for_application(2, 4, b"application A").unwrap());
+
+        // Applications share `G` but not `U`
+        let params = TriptychParameters::new(2, 4).unwrap();
+        assert_eq!(params_a.get_G(), params.get_G());
+        assert_eq!(params_a.get_G(), params_b.get_G());
+        assert_ne!(params_a.get_U(), params.get_U());
+        assert_ne!(params_a.get_U(), params_b.get_U());
+
+        // Parallel parameters for the same application use the same `U`
+        let parallel_params_a = parallel::TriptychParameters::new_for_application(2, 4, b"application A").unwrap();
+        assert_eq!(params_a.get_U(), parallel_params_a.get_U());
+
+        // Invalid parameters are still rejected
+        assert!(TriptychParameters::new_for_application(1, 4, b"application A").is_err());
+    }
+
+    #[test]
+    fn test_new_with_seed() {
+        let params_a = TriptychParameters::new_with_seed(2, 4, b"seed A").unwrap();
+        let params_b = TriptychParameters::new_with_seed(2, 4, b"seed B").unwrap();
+
+        // Seeds are deterministic
+        assert!(params_a == TriptychParameters::new_with_seed(2, 4, b"seed A").unwrap());
+
+        // Distinct seeds share `G` but no other generators
+        let params = TriptychParameters::new(2, 4).unwrap();
+        for other in [&params, &params_b] {
+            assert_eq!(params_a.get_G(), other.get_G());
+            assert_ne!(params_a.get_U(), other.get_U());
+            assert_ne!(params_a.get_CommitmentH(), other.get_CommitmentH());
+            assert!(params_a
+                .get_CommitmentG()
+                .iter()
+                .all(|point| !other.get_CommitmentG().contains(point)));
+            assert_ne!(params_a.get_hash(), other.get_hash());
+        }
+        assert!(params_a.check_generators().is_ok());
+
+        // Invalid parameters are still rejected
+        assert!(TriptychParameters::new_with_seed(1, 4, b"seed A").is_err());
+    }
+
+    #[test]
+    fn test_new_bounded() {
+        // Sizes up to the bound are accepted, and match unbounded parameters
+        assert!(TriptychParameters::new_bounded(2, 4, 16).unwrap() == TriptychParameters::new(2, 4).unwrap());
+        assert!(TriptychParameters::new_bounded(2, 4, 17).is_ok());
+
+        // Sizes exceeding the bound are rejected with a distinct error
+        assert!(matches!(
+            TriptychParameters::new_bounded(2, 5, 16),
+            Err(ParameterError::ExceededMaximumSize { maximum: 16 })
+        ));
+
+        // Invalid parameters are still rejected
+        assert!(matches!(
+            TriptychParameters::new_bounded(1, 4, 16),
+            Err(ParameterError::InvalidParameter { .. })
+        ));
+        assert!(matches!(
+            TriptychParameters::new_bounded(2, 32, u32::MAX),
+            Err(ParameterError::InvalidParameter { .. })
+        ));
+    }
+
+    #[test]
+    fn test_max_batch_size() {
+        for params in [
+            TriptychParameters::new(2, 4).unwrap(),
+            TriptychParameters::new(2, MAX_EXPONENT).unwrap(),
+        ] {
+            // The maximum batch size is the largest that does not overflow
+            let max_batch_size = params.max_batch_size();
+            assert!(max_batch_size > 0);
+            assert!(params.batch_check_size(max_batch_size).is_some());
+            assert!(params.batch_check_size(max_batch_size + 1).is_none());
+        }
+    }
+
+    #[test]
+    #[allow(non_snake_case)]
+    fn test_suggest() {
+        for target_N in [0, 4, 5, 100, 256, 1000, 1 << 20, 1_000_000] {
+            // The suggestion is valid and covers the target
+            let (n, m) = TriptychParameters::suggest(target_N).unwrap();
+            assert!(validate_params(n, m));
+            let N = n.pow(m);
+            assert!(N >= target_N);
+            assert_eq!(TriptychParameters::new(n, m).unwrap().max_ring_size(), N);
+
+            // No covering choice has a smaller proof, or the same proof size with a smaller `N`
+            let size = TriptychProof::serialized_size_for(n, m).unwrap();
+            for other_n in 2..=1024u32 {
+                for other_m in 2..=MAX_EXPONENT {
+                    let Some(other_N) = other_n.checked_pow(other_m) else {
+                        break;
+                    };
+                    if other_N < target_N {
+                        continue;
+                    }
+
+                    let other_size = TriptychProof::serialized_size_for(other_n, other_m).unwrap();
+                    assert!((size, N) <= (other_size, other_N));
+                }
+            }
+        }
+
+        // The smallest base is not always best, even for a power of two
+        assert_eq!(TriptychParameters::suggest(1 << 20), Some((4, 10)));
+
+        // No valid parameters cover the maximum size, since it is not a perfect power
+        assert!(TriptychParameters::suggest(u32::MAX).is_none());
+    }
+
+    #[test]
+    fn test_descriptor() {
+        let params = TriptychParameters::new(2, 8).unwrap();
+        let descriptor = params.descriptor();
+
+        // The descriptor includes the dimensions and a hash prefix
+        assert!(descriptor.starts_with("triptych-n2-m8-N256-"));
+        assert_eq!(descriptor.len(), "triptych-n2-m8-N256-".len() + 8);
+
+        // It is stable, and distinguishes generators
+        assert_eq!(descriptor, TriptychParameters::new(2, 8).unwrap().descriptor());
+        assert_ne!(
+            descriptor,
+            TriptychParameters::new_for_application(2, 8, b"application")
+                .unwrap()
+                .descriptor()
+        );
+    }
+
+    #[test]
+    #[allow(non_snake_case)]
+    fn test_from_proof_bytes() {
+        let mut rng = ChaCha12Rng::seed_from_u64(8675309);
+
+        // Generate a proof
+        let params = TriptychParameters::new(3, 2).unwrap();
+        let witness = TriptychWitness::random(&params, &mut rng);
+        let M = (0..params.get_N())
+            .map(|i| {
+                if i == witness.get_l() {
+                    witness.compute_verification_key()
+                } else {
+                    RistrettoPoint::random(&mut rng)
+                }
+            })
+            .collect::<Vec<RistrettoPoint>>();
+        let input_set = TriptychInputSet::new(&M).unwrap();
+        let statement = TriptychStatement::new(&params, &input_set, &witness.compute_linking_tag()).unwrap();
+        let proof =
+            TriptychProof::prove_with_rng(&witness, &statement, &mut rng, &mut Transcript::new(b"Test")).unwrap();
+        let bytes = proof.to_bytes();
+
+        // The parameters are recovered
+        assert!(TriptychParameters::from_proof_bytes(&bytes, params.get_N()).unwrap() == params);
+
+        // Short and invalid encodings fail
+        assert!(TriptychParameters::from_proof_bytes(&bytes[..7], u32::MAX).is_err());
+        assert!(TriptychParameters::from_proof_bytes(&[0u8; 8], u32::MAX).is_err());
+        assert!(TriptychParameters::from_proof_bytes(&[0xFF; 8], u32::MAX).is_err());
+
+        // Encodings exceeding the bound fail
+        assert!(matches!(
+            TriptychParameters::from_proof_bytes(&bytes, params.get_N() - 1),
+            Err(ParameterError::ExceededMaximumSize { .. })
+        ));
+        // This encodes `n == 65535` and `m == 2`
+        let large = [0xFE, 0xFF, 0x00, 0x00, 0x02, 0x00, 0x00, 0x00];
+        assert!(matches!(
+            TriptychParameters::from_proof_bytes(&large, 1 << 16),
+            Err(ParameterError::ExceededMaximumSize { maximum: 65536 })
+        ));
+    }
+
+    #[test]
+    #[allow(non_snake_case)]
+    fn test_check_generators() {
+        let mut rng = ChaCha12Rng::seed_from_u64(8675309);
+
+        // Default generators pass
+        let params = TriptychParameters::new(2, 4).unwrap();
+        assert!(params.check_generators().is_ok());
+
+        // Random generators pass
+        let G = RistrettoPoint::random(&mut rng);
+        let U = RistrettoPoint::random(&mut rng);
+        assert!(TriptychParameters::new_with_generators(2, 4, &G, &U)
+            .unwrap()
+            .check_generators()
+            .is_ok());
+
+        // A small multiple of another generator fails
+        assert!(TriptychParameters::new_with_generators(2, 4, &G, &(G + G))
+            .unwrap()
+            .check_generators()
+            .is_err());
+        assert!(TriptychParameters::new_with_generators(2, 4, &G, &-G)
+            .unwrap()
+            .check_generators()
+            .is_err());
+
+        // The identity fails
+        assert!(
+            TriptychParameters::new_with_generators(2, 4, &G, &RistrettoPoint::identity())
+                .unwrap()
+                .check_generators()
+                .is_err()
+        );
+
+        // A default generator in a different role fails
+        assert!(
+            TriptychParameters::new_with_generators(2, 4, &G, &RISTRETTO_BASEPOINT_POINT)
+                .unwrap()
+                .check_generators()
+                .is_err()
+        );
+        assert!(TriptychParameters::new_with_generators(2, 4, params.get_U(), &U)
+            .unwrap()
+            .check_generators()
+            .is_err());
+    }
+
+    #[test]
+    #[cfg(feature = "borsh")]
+    fn test_borsh() {
+        // Round trip default, application-specific, and seeded parameters
+        for params in [
+            TriptychParameters::new(2, 4).unwrap(),
+            TriptychParameters::new_for_application(3, 2, b"Test application").unwrap(),
+            TriptychParameters::new_with_seed(2, 3, b"Test seed").unwrap(),
+        ] {
+            let serialized = borsh::to_vec(&params).unwrap();
+            let deserialized: TriptychParameters = borsh::from_slice(&serialized).unwrap();
+            assert!(deserialized == params);
+        }
+
+        // Invalid dimensions are rejected
+        let mut serialized = borsh::to_vec(&TriptychParameters::new(2, 4).unwrap()).unwrap();
+        serialized[0] = 1;
+        assert!(borsh::from_slice::<TriptychParameters>(&serialized).is_err());
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn test_serde() {
+        // Round trip default, application-specific, and seeded parameters
+        for params in [
+            TriptychParameters::new(2, 4).unwrap(),
+            TriptychParameters::new_for_application(3, 2, b"Test application").unwrap(),
+            TriptychParameters::new_with_seed(2, 3, b"Test seed").unwrap(),
+        ] {
+            let serialized = serde_json::to_string(&params).unwrap();
+            let deserialized: TriptychParameters = serde_json::from_str(&serialized).unwrap();
+            assert!(deserialized == params);
+        }
+
+        // Invalid dimensions are rejected
+        let mut serialized = serde_json::to_value(TriptychParameters::new(2, 4).unwrap()).unwrap();
+        serialized["n"] = 1.into();
+        assert!(serde_json::from_value::<TriptychParameters>(serialized).is_err());
+    }
+
+    #[test]
+    fn test_eq_fast() {
+        // Compare every field directly
+        let full_eq = |a: &TriptychParameters, b: &TriptychParameters| {
+            a.n == b.n &&
+                a.m == b.m &&
+                a.G == b.G &&
+                a.U == b.U &&
+                a.CommitmentG == b.CommitmentG &&
+                a.CommitmentH == b.CommitmentH &&
+                a.seed_prefix == b.seed_prefix &&
+                a.hash == b.hash
+        };
+
+        let params = [
+            TriptychParameters::new(2, 4).unwrap(),
+            TriptychParameters::new(2, 4).unwrap(),
+            TriptychParameters::new(4, 2).unwrap(),
+            TriptychParameters::new_for_application(2, 4, b"Test application").unwrap(),
+            TriptychParameters::new_with_seed(2, 4, b"Test seed").unwrap(),
+            TriptychParameters::new(2, 4).unwrap().with_rotated_U(1),
+        ];
+
+        // Hash equality agrees with full equality for matching and mismatching parameters
+        for a in &params {
+            for b in &params {
+                assert_eq!(a.eq_fast(b), full_eq(a, b));
+                assert_eq!(a == b, full_eq(a, b));
+            }
+        }
+        assert!(params[0] == params[1]);
+        assert!(params[0] != params[2]);
+    }
+
+    #[test]
+    fn test_new_fixed() {
+        assert!(TriptychParameters::new_fixed::<2, 4>() == TriptychParameters::new(2, 4).unwrap());
+    }
 }
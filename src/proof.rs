@@ -1,14 +1,30 @@
 // Copyright (c) 2024, The Tari Project
 // SPDX-License-Identifier: BSD-3-Clause
 
-use alloc::{vec, vec::Vec};
-use core::{iter::once, slice, slice::ChunksExact};
+#[cfg(feature = "std")]
+extern crate std;
+
+#[cfg(any(feature = "base64", feature = "hex"))]
+use alloc::string::String;
+use alloc::{collections::BTreeMap, vec, vec::Vec};
+use core::{
+    borrow::{Borrow, BorrowMut},
+    iter::once,
+    mem,
+    slice,
+    slice::ChunksExact,
+};
+#[cfg(feature = "std")]
+use std::io::{Read, Write};
 
+#[cfg(feature = "base64")]
+use base64::{prelude::BASE64_STANDARD, Engine};
+use blake3::Hasher;
 #[cfg(feature = "borsh")]
 use borsh::{io, BorshDeserialize, BorshSerialize};
 use curve25519_dalek::{
     ristretto::CompressedRistretto,
-    traits::{Identity, MultiscalarMul, VartimeMultiscalarMul},
+    traits::{Identity, MultiscalarMul, VartimeMultiscalarMul, VartimePrecomputedMultiscalarMul},
     RistrettoPoint,
     Scalar,
 };
@@ -17,15 +33,23 @@ use rand_core::CryptoRngCore;
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 use snafu::prelude::*;
-use subtle::{ConditionallySelectable, ConstantTimeEq};
-use zeroize::Zeroizing;
+use subtle::{Choice, ConditionallySelectable, ConstantTimeEq};
+use zeroize::{Zeroize, ZeroizeOnDrop, Zeroizing};
 
+#[cfg(feature = "hazmat")]
+use crate::util::SeededRng;
 use crate::{
+    batch::{BatchScalars, ProofResponses},
+    context::ProofContext,
     domains,
     gray::GrayIterator,
+    parameters::{ParameterError, TriptychPrecomputation, MAX_EXPONENT},
+    statement::StatementError,
     transcript::ProofTranscript,
-    util::{delta, NullRng, OperationTiming},
+    util::{decode_varint, delta, encode_varint, NullRng, OperationTiming},
     Transcript,
+    TriptychInputSet,
+    TriptychParameters,
     TriptychStatement,
     TriptychWitness,
 };
@@ -33,6 +57,126 @@ use crate::{
 // Size of serialized proof elements in bytes
 const SERIALIZED_BYTES: usize = 32;
 
+/// Verify a serialized Triptych [`TriptychProof`] against serialized statement data.
+///
+/// This decompresses the verification keys `input_set_keys` and linking tag `tag`, builds a [`TriptychStatement`]
+/// using the [`TriptychParameters`] `params`, decodes `proof_bytes` into a [`TriptychProof`], and verifies it against a
+/// domain-separated [`Transcript`] that binds `transcript_label`. Since the label need not be known at compile time,
+/// it can be supplied at runtime, for example across an FFI boundary.
+///
+/// The transcript is the one used for signatures, so the proof must have been generated using
+/// [`TriptychProof::sign`] or [`TriptychProof::sign_with_rng`] with `transcript_label` as the message.
+///
+/// The length of `proof_bytes` is checked against `params` first, so a proof of the wrong length is rejected before
+/// any verification keys are decompressed.
+///
+/// On failure, returns a [`ProofError`] that identifies the failing stage:
+/// - [`ProofError::FailedDeserialization`] if the proof has the wrong length for `params`.
+/// - [`ProofError::InvalidVerificationKey`] if a verification key cannot be decompressed.
+/// - [`ProofError::InvalidLinkingTag`] if the linking tag cannot be decompressed.
+/// - [`ProofError::InvalidStatement`] if the statement cannot be built.
+/// - [`ProofError::FailedDeserialization`] if the proof cannot be decoded.
+/// - [`ProofError::FailedVerification`] if the proof is invalid.
+#[allow(non_snake_case)]
+pub fn verify_serialized(
+    params: &TriptychParameters,
+    input_set_keys: &[[u8; SERIALIZED_BYTES]],
+    tag: [u8; SERIALIZED_BYTES],
+    proof_bytes: &[u8],
+    transcript_label: &[u8],
+) -> Result<(), ProofError> {
+    // Check the proof length against the parameters before doing any other work
+    if Some(proof_bytes.len()) != TriptychProof::serialized_size_for(params.get_n(), params.get_m()) {
+        return Err(ProofError::FailedDeserialization);
+    }
+
+    // Decompress the verification keys
+    let M = input_set_keys
+        .iter()
+        .enumerate()
+        .map(|(index, key)| {
+            CompressedRistretto(*key)
+                .decompress()
+                .ok_or(ProofError::InvalidVerificationKey { index })
+        })
+        .collect::<Result<Vec<RistrettoPoint>, ProofError>>()?;
+
+    // Decompress the linking tag
+    let J = CompressedRistretto(tag)
+        .decompress()
+        .ok_or(ProofError::InvalidLinkingTag)?;
+
+    // Build the statement
+    let input_set = TriptychInputSet::new(&M)?;
+    let statement = TriptychStatement::new(params, &input_set, &J)?;
+
+    // Decode the proof
+    let proof = TriptychProof::from_bytes(proof_bytes)?;
+
+    // Verify the proof
+    proof.verify_signature(&statement, transcript_label)
+}
+
+/// Compute a fingerprint of the current state of a [`Transcript`].
+///
+/// A proof only verifies against a transcript in the same state as the one used to generate it, but the proof carries
+/// no information about that transcript, so a mismatch is indistinguishable from an invalid proof. To diagnose this, a
+/// prover and verifier can each compute this fingerprint immediately before proving and verifying, and compare them.
+///
+/// The `transcript` is not modified.
+pub fn transcript_fingerprint(transcript: &Transcript) -> [u8; domains::TRANSCRIPT_HASH_BYTES] {
+    let mut transcript = transcript.clone();
+    transcript.append_message(b"dom-sep", domains::TRANSCRIPT_FINGERPRINT.as_bytes());
+    transcript.append_u64(b"version", domains::VERSION);
+
+    let mut fingerprint = [0u8; domains::TRANSCRIPT_HASH_BYTES];
+    transcript.challenge_bytes(b"fingerprint", &mut fingerprint);
+
+    fingerprint
+}
+
+/// Run a self-test of proof generation, serialization, and verification.
+///
+/// This generates a small proof using a cryptographically-secure random number generator, serializes and deserializes
+/// it, and verifies it both singly and as part of a batch. It is intended as a quick sanity check of the underlying
+/// arithmetic backend on the current hardware, for example at process startup.
+///
+/// If any step fails, returns a [`ProofError`].
+#[cfg(feature = "rand")]
+#[allow(non_snake_case)]
+pub fn self_test() -> Result<(), ProofError> {
+    use rand_core::OsRng;
+
+    let mut rng = OsRng;
+
+    // Generate a witness and a statement for it
+    let params = TriptychParameters::new(2, 2)?;
+    let witness = TriptychWitness::random(&params, &mut rng);
+    let M = (0..params.get_N())
+        .map(|i| {
+            if i == witness.get_l() {
+                witness.compute_verification_key()
+            } else {
+                RistrettoPoint::random(&mut rng)
+            }
+        })
+        .collect::<Vec<RistrettoPoint>>();
+    let input_set = TriptychInputSet::new(&M)?;
+    let statement = TriptychStatement::new(&params, &input_set, &witness.compute_linking_tag())?;
+    let transcript = || Transcript::new(b"Triptych self-test");
+
+    // Generate a proof and round-trip its serialization
+    let proof = TriptychProof::prove_with_rng(&witness, &statement, &mut rng, &mut transcript())?;
+    let proof = TriptychProof::from_bytes(&proof.to_bytes())?;
+
+    // Verify the proof singly and as a batch
+    proof.verify(&statement, &mut transcript())?;
+    TriptychProof::verify_batch_iter([&statement, &statement], [&proof, &proof], [
+        &mut transcript(),
+        &mut transcript(),
+    ])
+}
+
 /// A Triptych proof.
 #[allow(non_snake_case)]
 #[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
@@ -59,18 +203,64 @@ pub enum ProofError {
         /// The reason for the parameter error.
         reason: &'static str,
     },
+    /// The witness parameters did not match those of the statement.
+    #[snafu(display("The witness parameters did not match those of the statement"))]
+    ParameterMismatch,
+    /// The witness signing key did not match the verification key at its index in the input set.
+    #[snafu(display("The witness signing key did not match the verification key at its index in the input set"))]
+    WitnessKeyMismatch,
+    /// The witness signing key did not match the statement linking tag.
+    #[snafu(display("The witness signing key did not match the statement linking tag"))]
+    LinkingTagMismatch,
     /// A transcript challenge was invalid.
     #[snafu(display("A transcript challenge was invalid"))]
     InvalidChallenge,
     /// Proof deserialization failed.
     #[snafu(display("Proof deserialization failed"))]
     FailedDeserialization,
+    /// A verification key could not be decompressed.
+    #[snafu(display("A verification key could not be decompressed"))]
+    InvalidVerificationKey {
+        /// The index of the invalid verification key.
+        index: usize,
+    },
+    /// A linking tag could not be decompressed.
+    #[snafu(display("A linking tag could not be decompressed"))]
+    InvalidLinkingTag,
+    /// A statement could not be constructed.
+    #[snafu(display("A statement could not be constructed: {reason}"))]
+    InvalidStatement {
+        /// The reason for the statement error.
+        reason: &'static str,
+    },
+    /// An input set hash did not match the claimed hash.
+    #[snafu(display("An input set hash did not match the claimed hash"))]
+    InputSetHashMismatch,
+    /// An input set had too few distinct verification keys.
+    #[snafu(display("An input set had {size} distinct verification keys, fewer than the minimum {minimum}"))]
+    InsufficientAnonymity {
+        /// The number of distinct verification keys in the input set.
+        size: u32,
+        /// The minimum number of distinct verification keys required.
+        minimum: u32,
+    },
+    /// A statement linking tag was not in the allowed set.
+    #[snafu(display("A statement linking tag was not in the allowed set"))]
+    TagNotAllowed,
     /// Single proof verification failed.
     #[snafu[display("Single proof verification failed")]]
     FailedVerification,
     /// Batch proof verification failed.
     #[snafu[display("Batch proof verification failed")]]
     FailedBatchVerification,
+    /// Two statements in a batch shared a linking tag.
+    #[snafu(display("Statements {first} and {second} shared a linking tag"))]
+    DuplicateLinkingTag {
+        /// The index of the first statement with the linking tag.
+        first: usize,
+        /// The index of the second statement with the linking tag.
+        second: usize,
+    },
     /// Batch proof verification failed.
     #[snafu[display("Batch proof verification failed")]]
     FailedBatchVerificationWithSingleBlame {
@@ -85,6 +275,143 @@ pub enum ProofError {
     },
 }
 
+impl From<ParameterError> for ProofError {
+    /// Convert a [`ParameterError`] into a [`ProofError::InvalidParameter`].
+    fn from(error: ParameterError) -> Self {
+        match error {
+            ParameterError::InvalidParameter { reason } => ProofError::InvalidParameter { reason },
+            ParameterError::ExceededMaximumSize { .. } => ProofError::InvalidParameter {
+                reason: "size `n**m` exceeded the maximum",
+            },
+        }
+    }
+}
+
+impl From<StatementError> for ProofError {
+    /// Convert a [`StatementError`] into a [`ProofError::InvalidStatement`].
+    ///
+    /// The index of a duplicate verification key is not kept.
+    fn from(error: StatementError) -> Self {
+        match error {
+            StatementError::InvalidParameter { reason } => ProofError::InvalidStatement { reason },
+            StatementError::DuplicateKey { .. } => ProofError::InvalidStatement {
+                reason: "input vector contained a duplicate key",
+            },
+        }
+    }
+}
+
+/// Options for computing the final check scalars in batch verification.
+#[derive(Clone, Copy)]
+pub(crate) enum BatchProcessing {
+    /// Process proofs one at a time.
+    Serial,
+    /// Process proofs across a thread pool.
+    #[cfg(feature = "rayon")]
+    Parallel,
+}
+
+/// A field of a [`TriptychProof`] that can be corrupted using [`TriptychProof::corrupt_field`].
+#[cfg(feature = "hazmat")]
+#[allow(non_camel_case_types)]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ProofField {
+    /// The `A` commitment.
+    A,
+    /// The `B` commitment.
+    B,
+    /// The `C` commitment.
+    C,
+    /// The `D` commitment.
+    D,
+    /// An element of the `X` vector, by index.
+    X(usize),
+    /// An element of the `Y` vector, by index.
+    Y(usize),
+    /// An element of the `f` matrix, by row and column.
+    f(usize, usize),
+    /// The `z_A` response.
+    z_A,
+    /// The `z_C` response.
+    z_C,
+    /// The `z` response.
+    z,
+}
+
+/// The commitments produced by the first phase of a split Triptych prover.
+///
+/// These are produced by [`TriptychProof::commit_phase`], and are included in the [`TriptychProof`] produced by
+/// [`TriptychProof::response_phase`].
+#[allow(non_snake_case)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TriptychProofCommitments {
+    A: RistrettoPoint,
+    B: RistrettoPoint,
+    C: RistrettoPoint,
+    D: RistrettoPoint,
+    X: Vec<RistrettoPoint>,
+    Y: Vec<RistrettoPoint>,
+}
+
+#[allow(non_snake_case)]
+impl TriptychProofCommitments {
+    /// Get the `A` matrix commitment.
+    pub fn get_A(&self) -> &RistrettoPoint {
+        &self.A
+    }
+
+    /// Get the `B` matrix commitment.
+    pub fn get_B(&self) -> &RistrettoPoint {
+        &self.B
+    }
+
+    /// Get the `C` matrix commitment.
+    pub fn get_C(&self) -> &RistrettoPoint {
+        &self.C
+    }
+
+    /// Get the `D` matrix commitment.
+    pub fn get_D(&self) -> &RistrettoPoint {
+        &self.D
+    }
+
+    /// Get the `X` vector.
+    pub fn get_X(&self) -> &[RistrettoPoint] {
+        &self.X
+    }
+
+    /// Get the `Y` vector.
+    pub fn get_Y(&self) -> &[RistrettoPoint] {
+        &self.Y
+    }
+}
+
+/// Secret prover state held between the phases of a split Triptych prover.
+///
+/// This is produced by [`TriptychProof::commit_phase`] and consumed by [`TriptychProof::response_phase`].
+/// It contains the signing key and all prover nonces, and is zeroized when dropped.
+/// It cannot be cloned, since producing more than one response from the same state reveals the signing key.
+///
+/// The state cannot be serialized, so it never leaves the process that produced it. Both phases of the prover need
+/// the signing key, so they must run on the same device; a split-device flow, where one device computes the
+/// commitments and another completes the proof, is not supported.
+#[allow(non_snake_case)]
+#[derive(Zeroize, ZeroizeOnDrop)]
+pub struct TriptychProverState {
+    #[zeroize(skip)]
+    params: TriptychParameters,
+    #[zeroize(skip)]
+    commitments: TriptychProofCommitments,
+    r: Scalar,
+    a: Vec<Vec<Scalar>>,
+    sigma: Vec<Vec<Scalar>>,
+    r_A: Scalar,
+    r_B: Scalar,
+    r_C: Scalar,
+    r_D: Scalar,
+    rho: Vec<Scalar>,
+}
+
 impl TriptychProof {
     /// Generate a Triptych [`TriptychProof`].
     ///
@@ -149,6 +476,89 @@ impl TriptychProof {
         Self::prove_internal(witness, statement, &mut OsRng, transcript, OperationTiming::Constant)
     }
 
+    /// Generate a Triptych [`TriptychProof`] along with the [`Transcript`] needed to verify it.
+    ///
+    /// The proof is generated by supplying a [`TriptychWitness`] `witness` and corresponding [`TriptychStatement`]
+    /// `statement`. If the witness and statement do not share the same parameters, or if the statement is invalid
+    /// for the witness, returns a [`ProofError`].
+    ///
+    /// Proving and verification each modify their transcript, so they must start from identical transcripts. This
+    /// function takes the `transcript` by value and proves using a copy of it. The returned transcript is the one you
+    /// supplied, unmodified, and is exactly what the verifier must pass to [`TriptychProof::verify`].
+    ///
+    /// This function provides a cryptographically-secure random number generator for you.
+    ///
+    /// This function makes some attempt at avoiding timing side-channel attacks using constant-time operations.
+    ///
+    /// ```
+    /// # use curve25519_dalek::RistrettoPoint;
+    /// # use rand_core::OsRng;
+    /// # use triptych::*;
+    /// # let mut rng = OsRng;
+    /// # let params = TriptychParameters::new(2, 3).unwrap();
+    /// # let witness = TriptychWitness::random(&params, &mut rng);
+    /// # let M = (0..params.get_N())
+    /// #     .map(|i| {
+    /// #         if i == witness.get_l() {
+    /// #             witness.compute_verification_key()
+    /// #         } else {
+    /// #             RistrettoPoint::random(&mut rng)
+    /// #         }
+    /// #     })
+    /// #     .collect::<Vec<RistrettoPoint>>();
+    /// # let input_set = TriptychInputSet::new(&M).unwrap();
+    /// # let statement = TriptychStatement::new(&params, &input_set, &witness.compute_linking_tag()).unwrap();
+    /// let transcript = Transcript::new(b"Test transcript");
+    /// let (proof, mut transcript) = TriptychProof::prove_and_transcript(&witness, &statement, transcript).unwrap();
+    /// assert!(proof.verify(&statement, &mut transcript).is_ok());
+    /// ```
+    #[cfg(feature = "rand")]
+    pub fn prove_and_transcript(
+        witness: &TriptychWitness,
+        statement: &TriptychStatement,
+        transcript: Transcript,
+    ) -> Result<(Self, Transcript), ProofError> {
+        use rand_core::OsRng;
+
+        Self::prove_and_transcript_with_rng(witness, statement, &mut OsRng, transcript)
+    }
+
+    /// Generate a Triptych [`TriptychProof`] along with the [`Transcript`] needed to verify it.
+    ///
+    /// The proof is generated by supplying a [`TriptychWitness`] `witness` and corresponding [`TriptychStatement`]
+    /// `statement`. If the witness and statement do not share the same parameters, or if the statement is invalid
+    /// for the witness, returns a [`ProofError`].
+    ///
+    /// This function takes the `transcript` by value and proves using a copy of it. The returned transcript is the one
+    /// you supplied, unmodified, and is exactly what the verifier must pass to [`TriptychProof::verify`].
+    ///
+    /// You must also supply a [`CryptoRngCore`] random number generator `rng`.
+    ///
+    /// This function makes some attempt at avoiding timing side-channel attacks using constant-time operations.
+    pub fn prove_and_transcript_with_rng<R: CryptoRngCore>(
+        witness: &TriptychWitness,
+        statement: &TriptychStatement,
+        rng: &mut R,
+        transcript: Transcript,
+    ) -> Result<(Self, Transcript), ProofError> {
+        let proof = Self::prove_with_rng(witness, statement, rng, &mut transcript.clone())?;
+
+        Ok((proof, transcript))
+    }
+
+    /// Generate a Triptych [`TriptychProof`] bound to a [`ProofContext`].
+    ///
+    /// This is identical to [`TriptychProof::prove`], but uses the transcript produced by `context`.
+    /// Verify the proof using [`TriptychProof::verify_with_context`] with an identical context.
+    #[cfg(feature = "rand")]
+    pub fn prove_with_context(
+        witness: &TriptychWitness,
+        statement: &TriptychStatement,
+        context: &ProofContext,
+    ) -> Result<Self, ProofError> {
+        Self::prove(witness, statement, &mut context.to_transcript())
+    }
+
     /// Generate a Triptych [`TriptychProof`].
     ///
     /// The proof is generated by supplying a [`TriptychWitness`] `witness` and corresponding [`TriptychStatement`]
@@ -167,94 +577,450 @@ impl TriptychProof {
         Self::prove_internal(witness, statement, rng, transcript, OperationTiming::Constant)
     }
 
-    /// The actual prover functionality.
-    #[allow(clippy::too_many_lines, non_snake_case)]
-    fn prove_internal<R: CryptoRngCore>(
+    /// Generate a Triptych [`TriptychProof`] with a selectable [`OperationTiming`] `timing`.
+    ///
+    /// The proof is generated by supplying a [`TriptychWitness`] `witness` and corresponding [`TriptychStatement`]
+    /// `statement`. If the witness and statement do not share the same parameters, or if the statement is invalid
+    /// for the witness, returns a [`ProofError`].
+    ///
+    /// You must also supply a [`CryptoRngCore`] random number generator `rng` and a [`Transcript`] `transcript`.
+    ///
+    /// Using [`OperationTiming::Constant`] is equivalent to [`TriptychProof::prove_with_rng`], and using
+    /// [`OperationTiming::Variable`] is equivalent to [`TriptychProof::prove_with_rng_vartime`].
+    ///
+    /// This is only available with the `hazmat` feature, since it can select variable-time proving, which may leak
+    /// secret data through timing side channels.
+    #[cfg(feature = "hazmat")]
+    pub fn prove_with_timing<R: CryptoRngCore>(
         witness: &TriptychWitness,
         statement: &TriptychStatement,
         rng: &mut R,
         transcript: &mut Transcript,
         timing: OperationTiming,
     ) -> Result<Self, ProofError> {
-        // Check that the witness and statement have identical parameters
-        if witness.get_params() != statement.get_params() {
-            return Err(ProofError::InvalidParameter {
-                reason: "witness and statement parameters did not match",
-            });
-        }
+        Self::prove_internal(witness, statement, rng, transcript, timing)
+    }
 
-        // Extract values for convenience
-        let r = witness.get_r();
-        let l = witness.get_l();
-        let M = statement.get_input_set().get_keys();
-        let params = statement.get_params();
-        let J = statement.get_J();
+    /// Generate a Triptych [`TriptychProof`] deterministically from a `seed`.
+    ///
+    /// The proof is generated by supplying a [`TriptychWitness`] `witness` and corresponding [`TriptychStatement`]
+    /// `statement`. If the witness and statement do not share the same parameters, or if the statement is invalid
+    /// for the witness, returns a [`ProofError`].
+    ///
+    /// You must also supply a 32-byte `seed` and a [`Transcript`] `transcript`. The same inputs always produce the
+    /// same proof, which is useful for test vectors and conformance testing against other implementations.
+    ///
+    /// This is DANGEROUS and must never be used in production! Reusing a seed for two proofs with the same witness
+    /// and different transcripts reveals the signing key. Use [`TriptychProof::prove`] or
+    /// [`TriptychProof::prove_with_rng`] instead.
+    ///
+    /// This function makes some attempt at avoiding timing side-channel attacks using constant-time operations.
+    #[cfg(feature = "hazmat")]
+    pub fn prove_deterministic(
+        witness: &TriptychWitness,
+        statement: &TriptychStatement,
+        seed: [u8; 32],
+        transcript: &mut Transcript,
+    ) -> Result<Self, ProofError> {
+        Self::prove_internal(
+            witness,
+            statement,
+            &mut SeededRng::new(&seed),
+            transcript,
+            OperationTiming::Constant,
+        )
+    }
 
-        // Check that the witness is valid against the statement, in constant time if needed
-        let mut M_l = RistrettoPoint::identity();
+    /// Generate a Triptych [`TriptychProof`] that acts as a linkable ring signature on a `message`.
+    ///
+    /// The proof is generated by supplying a [`TriptychWitness`] `witness` and corresponding [`TriptychStatement`]
+    /// `statement`. If the witness and statement do not share the same parameters, or if the statement is invalid
+    /// for the witness, returns a [`ProofError`].
+    ///
+    /// The `message` is bound into a domain-separated [`Transcript`] for you. Verify the signature using
+    /// [`TriptychProof::verify_signature`] with the same message. If you need to bind other data, use
+    /// [`TriptychProof::prove`] with your own transcript instead.
+    ///
+    /// This function provides a cryptographically-secure random number generator for you.
+    ///
+    /// This function makes some attempt at avoiding timing side-channel attacks using constant-time operations.
+    ///
+    /// ```
+    /// # use curve25519_dalek::RistrettoPoint;
+    /// # use rand_core::OsRng;
+    /// # use triptych::*;
+    /// # let mut rng = OsRng;
+    /// # let params = TriptychParameters::new(2, 3).unwrap();
+    /// # let witness = TriptychWitness::random(&params, &mut rng);
+    /// # let M = (0..params.get_N())
+    /// #     .map(|i| {
+    /// #         if i == witness.get_l() {
+    /// #             witness.compute_verification_key()
+    /// #         } else {
+    /// #             RistrettoPoint::random(&mut rng)
+    /// #         }
+    /// #     })
+    /// #     .collect::<Vec<RistrettoPoint>>();
+    /// # let input_set = TriptychInputSet::new(&M).unwrap();
+    /// # let statement = TriptychStatement::new(&params, &input_set, &witness.compute_linking_tag()).unwrap();
+    /// let signature = TriptychProof::sign(&witness, &statement, b"Hello, world!").unwrap();
+    /// assert!(signature.verify_signature(&statement, b"Hello, world!").is_ok());
+    /// assert!(signature.verify_signature(&statement, b"Goodbye, world!").is_err());
+    /// ```
+    #[cfg(feature = "rand")]
+    pub fn sign(witness: &TriptychWitness, statement: &TriptychStatement, message: &[u8]) -> Result<Self, ProofError> {
+        Self::prove(witness, statement, &mut Self::signature_transcript(message))
+    }
 
-        match timing {
-            OperationTiming::Constant => {
-                for (index, item) in M.iter().enumerate() {
-                    M_l.conditional_assign(item, index.ct_eq(&(l as usize)));
-                }
-            },
-            OperationTiming::Variable => {
-                M_l = M[l as usize];
-            },
-        }
+    /// Generate a Triptych [`TriptychProof`] that acts as a linkable ring signature on a `message`.
+    ///
+    /// The proof is generated by supplying a [`TriptychWitness`] `witness` and corresponding [`TriptychStatement`]
+    /// `statement`. If the witness and statement do not share the same parameters, or if the statement is invalid
+    /// for the witness, returns a [`ProofError`].
+    ///
+    /// The `message` is bound into a domain-separated [`Transcript`] for you. Verify the signature using
+    /// [`TriptychProof::verify_signature`] with the same message.
+    ///
+    /// You must also supply a [`CryptoRngCore`] random number generator `rng`.
+    ///
+    /// This function makes some attempt at avoiding timing side-channel attacks using constant-time operations.
+    pub fn sign_with_rng<R: CryptoRngCore>(
+        witness: &TriptychWitness,
+        statement: &TriptychStatement,
+        message: &[u8],
+        rng: &mut R,
+    ) -> Result<Self, ProofError> {
+        Self::prove_with_rng(witness, statement, rng, &mut Self::signature_transcript(message))
+    }
 
-        if M_l != r * params.get_G() {
+    /// Produce the [`Transcript`] used to sign a `message`.
+    pub(crate) fn signature_transcript(message: &[u8]) -> Transcript {
+        let mut transcript = Transcript::new(domains::TRANSCRIPT_SIGNATURE.as_bytes());
+        transcript.append_u64(b"version", domains::VERSION);
+        transcript.append_message(b"message", message);
+
+        transcript
+    }
+
+    /// Generate a Triptych [`TriptychProof`] along with the [`TriptychStatement`] it was generated against.
+    ///
+    /// The statement is built internally from a [`TriptychWitness`] `witness` and a slice `M` of verification keys,
+    /// using the witness parameters and linking tag. This guarantees that the returned statement is exactly the one
+    /// used by the prover, so it can be used directly for verification. If the statement cannot be built, or if it is
+    /// invalid for the witness, returns a [`ProofError`].
+    ///
+    /// This function provides a cryptographically-secure random number generator for you.
+    ///
+    /// You must also supply a [`Transcript`] `transcript`.
+    ///
+    /// This function makes some attempt at avoiding timing side-channel attacks using constant-time operations.
+    #[cfg(feature = "rand")]
+    #[allow(non_snake_case)]
+    pub fn prove_bundle(
+        witness: &TriptychWitness,
+        M: &[RistrettoPoint],
+        transcript: &mut Transcript,
+    ) -> Result<(TriptychStatement, Self), ProofError> {
+        use rand_core::OsRng;
+
+        Self::prove_bundle_with_rng(witness, M, &mut OsRng, transcript)
+    }
+
+    /// Generate a Triptych [`TriptychProof`] along with the [`TriptychStatement`] it was generated against.
+    ///
+    /// The statement is built internally from a [`TriptychWitness`] `witness` and a slice `M` of verification keys,
+    /// using the witness parameters and linking tag. This guarantees that the returned statement is exactly the one
+    /// used by the prover, so it can be used directly for verification. If the statement cannot be built, or if it is
+    /// invalid for the witness, returns a [`ProofError`].
+    ///
+    /// You must also supply a [`CryptoRngCore`] random number generator `rng` and a [`Transcript`] `transcript`.
+    ///
+    /// This function makes some attempt at avoiding timing side-channel attacks using constant-time operations.
+    #[allow(non_snake_case)]
+    pub fn prove_bundle_with_rng<R: CryptoRngCore>(
+        witness: &TriptychWitness,
+        M: &[RistrettoPoint],
+        rng: &mut R,
+        transcript: &mut Transcript,
+    ) -> Result<(TriptychStatement, Self), ProofError> {
+        let input_set = TriptychInputSet::new(M)?;
+        let statement = TriptychStatement::new(witness.get_params(), &input_set, &witness.compute_linking_tag())?;
+
+        let proof = Self::prove_internal(witness, &statement, rng, transcript, OperationTiming::Constant)?;
+
+        Ok((statement, proof))
+    }
+
+    /// Generate Triptych [`TriptychProofs`](`TriptychProof`) for several witnesses over a shared input set.
+    ///
+    /// A proof is generated for each [`TriptychWitness`] in `witnesses` against the [`TriptychInputSet`] `input_set`,
+    /// using the corresponding [`Transcript`] in `transcripts`. Returns the [`TriptychStatement`] and proof for each
+    /// witness, in order. If the witnesses do not all share the same parameters, if the number of witnesses and
+    /// transcripts does not match, or if any statement is invalid for its witness, returns a [`ProofError`].
+    ///
+    /// This function provides a cryptographically-secure random number generator for you.
+    ///
+    /// This function makes some attempt at avoiding timing side-channel attacks using constant-time operations.
+    #[cfg(feature = "rand")]
+    #[allow(clippy::type_complexity)]
+    pub fn prove_batch(
+        witnesses: &[TriptychWitness],
+        input_set: &TriptychInputSet,
+        transcripts: &mut [Transcript],
+    ) -> Result<(Vec<TriptychStatement>, Vec<Self>), ProofError> {
+        use rand_core::OsRng;
+
+        Self::prove_batch_with_rng(witnesses, input_set, &mut OsRng, transcripts)
+    }
+
+    /// Generate Triptych [`TriptychProofs`](`TriptychProof`) for several witnesses over a shared input set.
+    ///
+    /// A proof is generated for each [`TriptychWitness`] in `witnesses` against the [`TriptychInputSet`] `input_set`,
+    /// using the corresponding [`Transcript`] in `transcripts`. Returns the [`TriptychStatement`] and proof for each
+    /// witness, in order. If the witnesses do not all share the same parameters, if the number of witnesses and
+    /// transcripts does not match, or if any statement is invalid for its witness, returns a [`ProofError`].
+    ///
+    /// You must also supply a [`CryptoRngCore`] random number generator `rng`. Proofs are generated in order from the
+    /// same generator, so each is identical to one produced by [`TriptychProof::prove_with_rng`] in turn.
+    ///
+    /// This function makes some attempt at avoiding timing side-channel attacks using constant-time operations.
+    #[allow(clippy::type_complexity)]
+    pub fn prove_batch_with_rng<R: CryptoRngCore>(
+        witnesses: &[TriptychWitness],
+        input_set: &TriptychInputSet,
+        rng: &mut R,
+        transcripts: &mut [Transcript],
+    ) -> Result<(Vec<TriptychStatement>, Vec<Self>), ProofError> {
+        if witnesses.len() != transcripts.len() {
             return Err(ProofError::InvalidParameter {
-                reason: "`M[l] != r * G`",
+                reason: "number of witnesses and transcripts does not match",
             });
         }
-        if &(r * J) != params.get_U() {
-            return Err(ProofError::InvalidParameter { reason: "`r * J != U`" });
-        }
 
-        // Set up the transcript
-        let mut transcript = ProofTranscript::new(transcript, statement, rng, Some(witness));
+        // Check all witnesses and build their statements before proving
+        let statements = witnesses
+            .iter()
+            .map(|witness| {
+                if witness.get_params() != witnesses[0].get_params() {
+                    return Err(ProofError::ParameterMismatch);
+                }
 
-        // Compute the `A` matrix commitment
-        let r_A = Scalar::random(transcript.as_mut_rng());
-        let mut a = (0..params.get_m())
-            .map(|_| {
-                (0..params.get_n())
-                    .map(|_| Scalar::random(transcript.as_mut_rng()))
-                    .collect::<Vec<Scalar>>()
+                Ok(TriptychStatement::new(
+                    witness.get_params(),
+                    input_set,
+                    &witness.compute_linking_tag(),
+                )?)
             })
-            .collect::<Vec<Vec<Scalar>>>();
-        for j in (0..params.get_m()).map(|j| j as usize) {
-            a[j][0] = -a[j][1..].iter().sum::<Scalar>();
-        }
-        let A = params
-            .commit_matrix(&a, &r_A, timing)
-            .map_err(|_| ProofError::InvalidParameter {
-                reason: "unable to compute `A`",
-            })?;
+            .collect::<Result<Vec<TriptychStatement>, ProofError>>()?;
 
-        // Compute the `B` matrix commitment
-        let r_B = Scalar::random(transcript.as_mut_rng());
-        let l_decomposed = match timing {
-            OperationTiming::Constant => {
-                GrayIterator::decompose(params.get_n(), params.get_m(), l).ok_or(ProofError::InvalidParameter {
-                    reason: "`l` decomposition failed",
-                })?
-            },
-            OperationTiming::Variable => GrayIterator::decompose_vartime(params.get_n(), params.get_m(), l).ok_or(
-                ProofError::InvalidParameter {
-                    reason: "`l` decomposition failed",
-                },
-            )?,
-        };
-        let sigma = (0..params.get_m())
+        let proofs = izip!(witnesses.iter(), statements.iter(), transcripts.iter_mut())
+            .map(|(witness, statement, transcript)| {
+                Self::prove_internal(witness, statement, rng, transcript, OperationTiming::Constant)
+            })
+            .collect::<Result<Vec<Self>, ProofError>>()?;
+
+        Ok((statements, proofs))
+    }
+
+    /// Run the commitment phase of a split Triptych prover.
+    ///
+    /// The phase is run by supplying a [`TriptychWitness`] `witness` and corresponding [`TriptychStatement`]
+    /// `statement`. If the witness and statement do not share the same parameters, or if the statement is invalid
+    /// for the witness, returns a [`ProofError`].
+    ///
+    /// You must also supply a [`CryptoRngCore`] random number generator `rng` and a [`Transcript`] `transcript`.
+    ///
+    /// Returns the [`TriptychProofCommitments`] and a [`TriptychProverState`], which must be passed to
+    /// [`TriptychProof::response_phase`] along with the same `transcript` to complete the proof. The resulting proof
+    /// is identical to one produced by [`TriptychProof::prove_with_rng`].
+    ///
+    /// Splitting the prover allows the caller to inspect or forward the commitments before the challenge is
+    /// computed. Both phases must run in the same process, since the state holds the signing key and cannot be
+    /// serialized; this does not support a split-device or air-gapped flow.
+    ///
+    /// This function makes some attempt at avoiding timing side-channel attacks using constant-time operations.
+    pub fn commit_phase<R: CryptoRngCore>(
+        witness: &TriptychWitness,
+        statement: &TriptychStatement,
+        rng: &mut R,
+        transcript: &mut Transcript,
+    ) -> Result<(TriptychProofCommitments, TriptychProverState), ProofError> {
+        let state = Self::commit_phase_internal(witness, statement, rng, transcript, OperationTiming::Constant)?;
+
+        Ok((state.commitments.clone(), state))
+    }
+
+    /// Run the response phase of a split Triptych prover.
+    ///
+    /// The phase is run by supplying the [`TriptychProverState`] `state` produced by [`TriptychProof::commit_phase`],
+    /// along with the same [`Transcript`] `transcript` that was supplied to it. The state is consumed, so each set of
+    /// commitments receives only a single response.
+    ///
+    /// If the Fiat-Shamir challenge is invalid, returns a [`ProofError`].
+    #[allow(clippy::needless_pass_by_value, non_snake_case)]
+    pub fn response_phase(state: TriptychProverState, transcript: &mut Transcript) -> Result<Self, ProofError> {
+        // Extract values for convenience
+        let params = &state.params;
+        let commitments = &state.commitments;
+        let a = &state.a;
+        let sigma = &state.sigma;
+
+        // Resume the transcript; no further prover randomness is needed
+        let mut null_rng = NullRng;
+        let mut transcript = ProofTranscript::resume(transcript, &mut null_rng);
+
+        // Run the Fiat-Shamir commitment phase to get the challenge powers
+        let xi_powers = transcript.commit(
+            params,
+            &commitments.A,
+            &commitments.B,
+            &commitments.C,
+            &commitments.D,
+            &commitments.X,
+            &commitments.Y,
+        )?;
+
+        // Compute the `f` matrix
+        let f = (0..params.get_m())
             .map(|j| {
-                (0..params.get_n())
-                    .map(|i| delta(l_decomposed[j as usize], i, timing))
+                (1..params.get_n())
+                    .map(|i| sigma[j as usize][i as usize] * xi_powers[1] + a[j as usize][i as usize])
                     .collect::<Vec<Scalar>>()
             })
             .collect::<Vec<Vec<Scalar>>>();
+
+        // Compute the remaining response values
+        let z_A = state.r_A + xi_powers[1] * state.r_B;
+        let z_C = xi_powers[1] * state.r_C + state.r_D;
+        let z = state.r * xi_powers[params.get_m() as usize] -
+            state
+                .rho
+                .iter()
+                .zip(xi_powers.iter())
+                .map(|(rho, xi_power)| rho * xi_power)
+                .sum::<Scalar>();
+
+        Ok(Self {
+            A: commitments.A,
+            B: commitments.B,
+            C: commitments.C,
+            D: commitments.D,
+            X: commitments.X.clone(),
+            Y: commitments.Y.clone(),
+            f,
+            z_A,
+            z_C,
+            z,
+        })
+    }
+
+    /// The actual prover functionality.
+    fn prove_internal<R: CryptoRngCore>(
+        witness: &TriptychWitness,
+        statement: &TriptychStatement,
+        rng: &mut R,
+        transcript: &mut Transcript,
+        timing: OperationTiming,
+    ) -> Result<Self, ProofError> {
+        let state = Self::commit_phase_internal(witness, statement, rng, transcript, timing)?;
+
+        Self::response_phase(state, transcript)
+    }
+
+    /// The actual prover commitment phase functionality.
+    ///
+    /// Secret intermediate values are held in zeroizing containers or moved into the returned state, so they are
+    /// cleared from the heap when dropped, even if an error occurs. Copies of secret scalars on the stack and within
+    /// the underlying curve library are not covered.
+    #[allow(clippy::too_many_lines, non_snake_case)]
+    fn commit_phase_internal<R: CryptoRngCore>(
+        witness: &TriptychWitness,
+        statement: &TriptychStatement,
+        rng: &mut R,
+        transcript: &mut Transcript,
+        timing: OperationTiming,
+    ) -> Result<TriptychProverState, ProofError> {
+        // Check that the witness and statement have identical parameters
+        if witness.get_params() != statement.get_params() {
+            return Err(ProofError::ParameterMismatch);
+        }
+
+        // Extract values for convenience
+        let r = witness.get_r();
+        let l = witness.get_l();
+        let M = statement.get_input_set().get_keys();
+        let params = statement.get_params();
+        let J = statement.get_J();
+
+        // Check that the witness is valid against the statement, in constant time if needed
+        let mut M_l = RistrettoPoint::identity();
+
+        match timing {
+            OperationTiming::Constant => {
+                for (index, item) in M.iter().enumerate() {
+                    M_l.conditional_assign(item, index.ct_eq(&(l as usize)));
+                }
+            },
+            OperationTiming::Variable => {
+                M_l = M[l as usize];
+            },
+        }
+
+        // The verification key is always recomputed from the signing key rather than accepted precomputed, since a
+        // supplied key would only be checked against `M[l]` and not against `r`; the cost is negligible next to proving
+        if M_l != r * params.get_G() {
+            return Err(ProofError::WitnessKeyMismatch);
+        }
+        if &(r * J) != params.get_U() {
+            return Err(ProofError::LinkingTagMismatch);
+        }
+
+        // Set up the transcript
+        let mut transcript = ProofTranscript::new(transcript, statement, rng, Some(witness));
+
+        // Compute the `A` matrix commitment
+        let r_A = Scalar::random(transcript.as_mut_rng());
+        let mut a = Zeroizing::new(
+            (0..params.get_m())
+                .map(|_| {
+                    (0..params.get_n())
+                        .map(|_| Scalar::random(transcript.as_mut_rng()))
+                        .collect::<Vec<Scalar>>()
+                })
+                .collect::<Vec<Vec<Scalar>>>(),
+        );
+        for j in (0..params.get_m()).map(|j| j as usize) {
+            a[j][0] = -a[j][1..].iter().sum::<Scalar>();
+        }
+        let A = params
+            .commit_matrix(&a, &r_A, timing)
+            .map_err(|_| ProofError::InvalidParameter {
+                reason: "unable to compute `A`",
+            })?;
+
+        // Compute the `B` matrix commitment
+        let r_B = Scalar::random(transcript.as_mut_rng());
+        let l_decomposed = Zeroizing::new(match timing {
+            OperationTiming::Constant => {
+                GrayIterator::decompose(params.get_n(), params.get_m(), l).ok_or(ProofError::InvalidParameter {
+                    reason: "`l` decomposition failed",
+                })?
+            },
+            OperationTiming::Variable => GrayIterator::decompose_vartime(params.get_n(), params.get_m(), l).ok_or(
+                ProofError::InvalidParameter {
+                    reason: "`l` decomposition failed",
+                },
+            )?,
+        });
+        let mut sigma = Zeroizing::new(
+            (0..params.get_m())
+                .map(|j| {
+                    (0..params.get_n())
+                        .map(|i| delta(l_decomposed[j as usize], i, timing))
+                        .collect::<Vec<Scalar>>()
+                })
+                .collect::<Vec<Vec<Scalar>>>(),
+        );
         let B = params
             .commit_matrix(&sigma, &r_B, timing)
             .map_err(|_| ProofError::InvalidParameter {
@@ -264,13 +1030,15 @@ impl TriptychProof {
         // Compute the `C` matrix commitment
         let two = Scalar::from(2u32);
         let r_C = Scalar::random(transcript.as_mut_rng());
-        let a_sigma = (0..params.get_m())
-            .map(|j| {
-                (0..params.get_n())
-                    .map(|i| a[j as usize][i as usize] * (Scalar::ONE - two * sigma[j as usize][i as usize]))
-                    .collect::<Vec<Scalar>>()
-            })
-            .collect::<Vec<Vec<Scalar>>>();
+        let a_sigma = Zeroizing::new(
+            (0..params.get_m())
+                .map(|j| {
+                    (0..params.get_n())
+                        .map(|i| a[j as usize][i as usize] * (Scalar::ONE - two * sigma[j as usize][i as usize]))
+                        .collect::<Vec<Scalar>>()
+                })
+                .collect::<Vec<Vec<Scalar>>>(),
+        );
         let C = params
             .commit_matrix(&a_sigma, &r_C, timing)
             .map_err(|_| ProofError::InvalidParameter {
@@ -279,13 +1047,15 @@ impl TriptychProof {
 
         // Compute the `D` matrix commitment
         let r_D = Scalar::random(transcript.as_mut_rng());
-        let a_square = (0..params.get_m())
-            .map(|j| {
-                (0..params.get_n())
-                    .map(|i| -a[j as usize][i as usize] * a[j as usize][i as usize])
-                    .collect::<Vec<Scalar>>()
-            })
-            .collect::<Vec<Vec<Scalar>>>();
+        let a_square = Zeroizing::new(
+            (0..params.get_m())
+                .map(|j| {
+                    (0..params.get_n())
+                        .map(|i| -a[j as usize][i as usize] * a[j as usize][i as usize])
+                        .collect::<Vec<Scalar>>()
+                })
+                .collect::<Vec<Vec<Scalar>>>(),
+        );
         let D = params
             .commit_matrix(&a_square, &r_D, timing)
             .map_err(|_| ProofError::InvalidParameter {
@@ -293,15 +1063,15 @@ impl TriptychProof {
             })?;
 
         // Random masks
-        let rho = Zeroizing::new(
+        let mut rho = Zeroizing::new(
             (0..params.get_m())
                 .map(|_| Scalar::random(transcript.as_mut_rng()))
                 .collect::<Vec<Scalar>>(),
         );
 
         // Compute `p` polynomial vector coefficients using repeated convolution
-        let mut p = Vec::<Vec<Scalar>>::with_capacity(params.get_N() as usize);
-        let mut k_decomposed = vec![0; params.get_m() as usize];
+        let mut p = Zeroizing::new(Vec::<Vec<Scalar>>::with_capacity(params.get_N() as usize));
+        let mut k_decomposed = Zeroizing::new(vec![0; params.get_m() as usize]);
         for (gray_index, _, gray_new) in
             GrayIterator::new(params.get_n(), params.get_m()).ok_or(ProofError::InvalidParameter {
                 reason: "coefficient decomposition failed",
@@ -310,43 +1080,48 @@ impl TriptychProof {
             k_decomposed[gray_index] = gray_new;
 
             // Set the initial coefficients using the first degree-one polynomial (`j = 0`)
-            let mut coefficients = Vec::new();
-            coefficients.resize(
-                (params.get_m() as usize)
-                    .checked_add(1)
-                    .ok_or(ProofError::InvalidParameter {
+            let mut coefficients = Zeroizing::new(vec![
+                Scalar::ZERO;
+                (params.get_m() as usize).checked_add(1).ok_or(
+                    ProofError::InvalidParameter {
                         reason: "polynomial degree overflowed",
-                    })?,
-                Scalar::ZERO,
-            );
+                    }
+                )?
+            ]);
             coefficients[0] = a[0][k_decomposed[0] as usize];
             coefficients[1] = sigma[0][k_decomposed[0] as usize];
 
             // Use convolution against each remaining degree-one polynomial
             for j in 1..params.get_m() {
                 // For the degree-zero portion, simply multiply each coefficient accordingly
-                let degree_0_portion = coefficients
-                    .iter()
-                    .map(|c| a[j as usize][k_decomposed[j as usize] as usize] * c)
-                    .collect::<Vec<Scalar>>();
+                let degree_0_portion = Zeroizing::new(
+                    coefficients
+                        .iter()
+                        .map(|c| a[j as usize][k_decomposed[j as usize] as usize] * c)
+                        .collect::<Vec<Scalar>>(),
+                );
 
                 // For the degree-one portion, we also need to increase each exponent by one
                 // Rotating the coefficients is fine here since the highest is always zero!
                 let mut shifted_coefficients = coefficients.clone();
                 shifted_coefficients.rotate_right(1);
-                let degree_1_portion = shifted_coefficients
-                    .iter()
-                    .map(|c| sigma[j as usize][k_decomposed[j as usize] as usize] * c)
-                    .collect::<Vec<Scalar>>();
-
-                coefficients = degree_0_portion
-                    .iter()
-                    .zip(degree_1_portion.iter())
-                    .map(|(x, y)| x + y)
-                    .collect::<Vec<Scalar>>();
+                let degree_1_portion = Zeroizing::new(
+                    shifted_coefficients
+                        .iter()
+                        .map(|c| sigma[j as usize][k_decomposed[j as usize] as usize] * c)
+                        .collect::<Vec<Scalar>>(),
+                );
+
+                // Update the coefficients in place, so no copies are left unzeroized
+                for (c, (x, y)) in coefficients
+                    .iter_mut()
+                    .zip(degree_0_portion.iter().zip(degree_1_portion.iter()))
+                {
+                    *c = x + y;
+                }
             }
 
-            p.push(coefficients);
+            p.push(mem::take(&mut *coefficients));
         }
 
         // Compute `X` vector
@@ -367,39 +1142,53 @@ impl TriptychProof {
         // Compute `Y` vector
         let Y = rho.iter().map(|rho| rho * J).collect::<Vec<RistrettoPoint>>();
 
-        // Run the Fiat-Shamir commitment phase to get the challenge powers
-        let xi_powers = transcript.commit(params, &A, &B, &C, &D, &X, &Y)?;
-
-        // Compute the `f` matrix
-        let f = (0..params.get_m())
-            .map(|j| {
-                (1..params.get_n())
-                    .map(|i| sigma[j as usize][i as usize] * xi_powers[1] + a[j as usize][i as usize])
-                    .collect::<Vec<Scalar>>()
-            })
-            .collect::<Vec<Vec<Scalar>>>();
+        // Move the secret values into the state, which zeroizes them when dropped
+        Ok(TriptychProverState {
+            params: params.clone(),
+            commitments: TriptychProofCommitments { A, B, C, D, X, Y },
+            r: *r,
+            a: mem::take(&mut *a),
+            sigma: mem::take(&mut *sigma),
+            r_A,
+            r_B,
+            r_C,
+            r_D,
+            rho: mem::take(&mut *rho),
+        })
+    }
 
-        // Compute the remaining response values
-        let z_A = r_A + xi_powers[1] * r_B;
-        let z_C = xi_powers[1] * r_C + r_D;
-        let z = r * xi_powers[params.get_m() as usize] -
-            rho.iter()
-                .zip(xi_powers.iter())
-                .map(|(rho, xi_power)| rho * xi_power)
-                .sum::<Scalar>();
+    /// Check that this [`TriptychProof`] has dimensions matching the given [`TriptychParameters`].
+    ///
+    /// This runs the same structural checks that verification performs up front, and is useful for validating a proof
+    /// in isolation before adding it to a batch. It does not check that the proof is valid.
+    ///
+    /// If the proof dimensions do not match, returns a [`ProofError`].
+    pub fn validate(&self, params: &TriptychParameters) -> Result<(), ProofError> {
+        if self.X.len() != params.get_m() as usize {
+            return Err(ProofError::InvalidParameter {
+                reason: "proof `X` vector length was not `m`",
+            });
+        }
+        if self.Y.len() != params.get_m() as usize {
+            return Err(ProofError::InvalidParameter {
+                reason: "proof `Y` vector length was not `m`",
+            });
+        }
+        if self.f.len() != params.get_m() as usize {
+            return Err(ProofError::InvalidParameter {
+                reason: "proof `f` matrix did not have `m` rows",
+            });
+        }
+        let columns = params.get_n().checked_sub(1).ok_or(ProofError::InvalidParameter {
+            reason: "proof `f` matrix column count overflowed",
+        })? as usize;
+        if self.f.iter().any(|f_row| f_row.len() != columns) {
+            return Err(ProofError::InvalidParameter {
+                reason: "proof `f` matrix did not have `n - 1` columns",
+            });
+        }
 
-        Ok(Self {
-            A,
-            B,
-            C,
-            D,
-            X,
-            Y,
-            f,
-            z_A,
-            z_C,
-            z,
-        })
+        Ok(())
     }
 
     /// Verify a Triptych [`TriptychProof`].
@@ -407,6 +1196,8 @@ impl TriptychProof {
     /// Verification requires that the `statement` and `transcript` match those used when the proof was generated.
     ///
     /// If this requirement is not met, or if the proof is invalid, returns a [`ProofError`].
+    /// A transcript mismatch cannot be distinguished from an invalid proof; use [`transcript_fingerprint`] to check for
+    /// one.
     pub fn verify(&self, statement: &TriptychStatement, transcript: &mut Transcript) -> Result<(), ProofError> {
         // Verify as a trivial batch
         Self::verify_batch(
@@ -416,52 +1207,430 @@ impl TriptychProof {
         )
     }
 
-    /// Verify a batch of Triptych [`TriptychProofs`](`TriptychProof`), identifying a single invalid proof if
-    /// verification fails.
-    ///
-    /// An empty batch is valid by definition.
+    /// Verify a Triptych [`TriptychProof`], returning only whether verification succeeded.
     ///
-    /// If verification fails, this performs a subsequent number of verifications logarithmic in the size of the batch.
+    /// This is a convenience for callers who do not need to distinguish between failure reasons; any [`ProofError`]
+    /// that [`TriptychProof::verify`] would return, including one caused by malformed parameters, produces `false`.
+    #[must_use]
+    pub fn verify_bool(&self, statement: &TriptychStatement, transcript: &mut Transcript) -> bool {
+        self.verify(statement, transcript).is_ok()
+    }
+
+    /// Verify a Triptych [`TriptychProof`] bound to a [`ProofContext`].
     ///
-    /// Verification requires that the `statements` and `transcripts` match those used when the `proofs` were generated,
-    /// and that they share a common [`TriptychInputSet`](`crate::statement::TriptychInputSet`) and
-    /// [`TriptychParameters`](`crate::parameters::TriptychParameters`).
+    /// Verification requires that the `statement` and `context` match those used when the proof was generated.
     ///
-    /// If any of the above requirements are not met, returns a [`ProofError`].
-    /// If any batch in the proof is invalid, returns a [`ProofError`] containing the index of an invalid proof.
-    /// It is not guaranteed that this index represents the _only_ invalid proof in the batch.
-    pub fn verify_batch_with_single_blame(
-        statements: &[TriptychStatement],
-        proofs: &[TriptychProof],
-        transcripts: &mut [Transcript],
-    ) -> Result<(), ProofError> {
-        // Try to verify the full batch
-        if Self::verify_batch(statements, proofs, &mut transcripts.to_vec()).is_ok() {
-            return Ok(());
-        }
+    /// If this requirement is not met, or if the proof is invalid, returns a [`ProofError`].
+    pub fn verify_with_context(&self, statement: &TriptychStatement, context: &ProofContext) -> Result<(), ProofError> {
+        self.verify(statement, &mut context.to_transcript())
+    }
 
-        // The batch failed, so find an invalid proof using a binary search
-        let mut left = 0;
-        let mut right = proofs.len();
+    /// Verify a Triptych [`TriptychProof`] that acts as a linkable ring signature on a `message`.
+    ///
+    /// Verification requires that the `statement` and `message` match those used when the proof was generated using
+    /// [`TriptychProof::sign`] or [`TriptychProof::sign_with_rng`].
+    ///
+    /// If this requirement is not met, or if the proof is invalid, returns a [`ProofError`].
+    pub fn verify_signature(&self, statement: &TriptychStatement, message: &[u8]) -> Result<(), ProofError> {
+        self.verify(statement, &mut Self::signature_transcript(message))
+    }
 
-        while left < right {
-            #[allow(clippy::arithmetic_side_effects)]
-            let average = left
-                .checked_add(
-                    // This cannot underflow since `left < right`
-                    (right - left) / 2,
-                )
-                .ok_or(ProofError::FailedBatchVerificationWithSingleBlame { index: None })?;
+    /// Compute a digest that binds this [`TriptychProof`] to its full verification context.
+    ///
+    /// The digest commits to the hashes of the [`TriptychParameters`] and `statement`, the canonical encoding of this
+    /// proof, and the [fingerprint](`transcript_fingerprint`) of `transcript`. It is intended as a tamper-evident audit
+    /// log entry, which anyone holding the same data can recompute. The `transcript` should be in the state passed to
+    /// [`TriptychProof::verify`], and is not modified.
+    ///
+    /// The digest does not imply that the proof is valid; compute it only for proofs that verify.
+    pub fn audit_digest(&self, statement: &TriptychStatement, transcript: &Transcript) -> [u8; blake3::OUT_LEN] {
+        let proof_bytes = self.to_bytes();
+
+        let mut hasher = Hasher::new();
+        hasher.update(domains::AUDIT_DIGEST.as_bytes());
+        hasher.update(&domains::VERSION.to_le_bytes());
+        hasher.update(statement.get_params().get_hash());
+        hasher.update(statement.get_hash());
+        hasher.update(&transcript_fingerprint(transcript));
+        hasher.update(&(proof_bytes.len() as u64).to_le_bytes());
+        hasher.update(&proof_bytes);
+
+        hasher.finalize().into()
+    }
 
-            #[allow(clippy::arithmetic_side_effects)]
-            // This cannot underflow since `left < right`
-            let mid = if (right - left) % 2 == 0 {
-                average
-            } else {
-                average
-                    .checked_add(1)
-                    .ok_or(ProofError::FailedBatchVerificationWithSingleBlame { index: None })?
-            };
+    /// Verify a Triptych [`TriptychProof`] and return its Fiat-Shamir challenge.
+    ///
+    /// Verification requires that the `statement` and `transcript` match those used when the proof was generated.
+    ///
+    /// If this requirement is not met, or if the proof is invalid, returns a [`ProofError`].
+    /// Otherwise, returns the challenge `xi` computed during verification. This is useful for binding another proof to
+    /// the same challenge.
+    pub fn verify_and_challenge(
+        &self,
+        statement: &TriptychStatement,
+        transcript: &mut Transcript,
+    ) -> Result<Scalar, ProofError> {
+        Self::verify_batch_internal(
+            slice::from_ref(statement),
+            slice::from_ref(self),
+            slice::from_mut(transcript),
+        )?
+        .first()
+        .copied()
+        .ok_or(ProofError::FailedVerification)
+    }
+
+    /// Compute the Fiat-Shamir challenge for a Triptych [`TriptychProof`] without verifying it.
+    ///
+    /// This runs only the commitment phase of the Fiat-Shamir transformation on a copy of `transcript`, which is not
+    /// modified, and returns the challenge `xi`. The `statement` and `transcript` must match those used when the proof
+    /// was generated, or the challenge will not match the one used by the prover.
+    ///
+    /// The proof is not verified, so the challenge must not be relied upon unless the proof is also verified. If the
+    /// proof dimensions do not match the statement, or if the challenge is invalid, returns a [`ProofError`].
+    pub fn challenge(&self, statement: &TriptychStatement, transcript: &Transcript) -> Result<Scalar, ProofError> {
+        let params = statement.get_params();
+
+        // Check that the proof semantics are valid for the statement
+        self.validate(params)?;
+
+        // Run the Fiat-Shamir commitment phase on a copy of the transcript
+        let mut transcript = transcript.clone();
+        let mut null_rng = NullRng;
+        let mut transcript = ProofTranscript::new(&mut transcript, statement, &mut null_rng, None);
+        let xi_powers = transcript.commit(params, &self.A, &self.B, &self.C, &self.D, &self.X, &self.Y)?;
+
+        Ok(xi_powers[1])
+    }
+
+    /// Verify a Triptych [`TriptychProof`] and return the number of group elements used in verification.
+    ///
+    /// Verification requires that the `statement` and `transcript` match those used when the proof was generated.
+    ///
+    /// If this requirement is not met, or if the proof is invalid, returns a [`ProofError`].
+    /// Otherwise, returns the number of points in the final multiscalar multiplication, which dominates the cost of
+    /// verification.
+    pub fn verify_metered(
+        &self,
+        statement: &TriptychStatement,
+        transcript: &mut Transcript,
+    ) -> Result<usize, ProofError> {
+        self.verify(statement, transcript)?;
+
+        statement
+            .get_params()
+            .batch_check_size(1)
+            .ok_or(ProofError::InvalidParameter {
+                reason: "batch size exceeded the maximum for the parameters",
+            })
+    }
+
+    /// Verify a Triptych [`TriptychProof`] against any statement in a window of candidate statements.
+    ///
+    /// A proof does not identify the statement it was generated against, so each candidate in `window` whose
+    /// parameters match the proof dimensions is tried in order, and the index of the first statement for which the
+    /// proof verifies is returned. The `transcript` must match the one used when the proof was generated; it is left
+    /// in the state produced by the successful verification.
+    ///
+    /// This may verify the proof once for each candidate, so it is much slower than [`TriptychProof::verify`] for
+    /// large windows. If no candidate statement verifies the proof, returns a [`ProofError`].
+    pub fn verify_against_window(
+        &self,
+        transcript: &mut Transcript,
+        window: &[TriptychStatement],
+    ) -> Result<usize, ProofError> {
+        for (index, statement) in window.iter().enumerate() {
+            // Skip any statement whose parameters cannot match the proof
+            if self.validate(statement.get_params()).is_err() {
+                continue;
+            }
+
+            let mut candidate_transcript = transcript.clone();
+            if self.verify(statement, &mut candidate_transcript).is_ok() {
+                *transcript = candidate_transcript;
+                return Ok(index);
+            }
+        }
+
+        Err(ProofError::FailedVerification)
+    }
+
+    /// Verify a Triptych [`TriptychProof`] and check that its input set meets a minimum anonymity set size.
+    ///
+    /// Verification requires that the `statement` and `transcript` match those used when the proof was generated.
+    ///
+    /// If the [effective anonymity set](`TriptychInputSet::effective_anonymity_set`) of the statement's input set is
+    /// smaller than `min_distinct`, returns [`ProofError::InsufficientAnonymity`] without verifying the proof. If the
+    /// proof is invalid, returns a different [`ProofError`].
+    pub fn verify_min_anonymity(
+        &self,
+        statement: &TriptychStatement,
+        transcript: &mut Transcript,
+        min_distinct: u32,
+    ) -> Result<(), ProofError> {
+        // Check the policy first, since it is cheaper than verification
+        let size = statement.get_input_set().effective_anonymity_set();
+        if size < min_distinct {
+            return Err(ProofError::InsufficientAnonymity {
+                size,
+                minimum: min_distinct,
+            });
+        }
+
+        self.verify(statement, transcript)
+    }
+
+    /// Verify a Triptych [`TriptychProof`] and check that its linking tag is allowed.
+    ///
+    /// Verification requires that the `statement` and `transcript` match those used when the proof was generated.
+    ///
+    /// If the statement's linking tag is not in `allowed`, returns [`ProofError::TagNotAllowed`] without verifying the
+    /// proof. This check runs in variable time, since linking tags are public. If the proof is invalid, returns a
+    /// different [`ProofError`].
+    pub fn verify_with_allowed_tags(
+        &self,
+        statement: &TriptychStatement,
+        transcript: &mut Transcript,
+        allowed: &[RistrettoPoint],
+    ) -> Result<(), ProofError> {
+        // Check the policy first, since it is cheaper than verification
+        if !allowed.contains(statement.get_J()) {
+            return Err(ProofError::TagNotAllowed);
+        }
+
+        self.verify(statement, transcript)
+    }
+
+    /// Verify a Triptych [`TriptychProof`] against verification keys and a claimed input set hash.
+    ///
+    /// This builds a [`TriptychInputSet`] from the verification keys `M` using [`TriptychInputSet::new`], checks that
+    /// its hash matches `claimed_hash`, builds a [`TriptychStatement`] using the [`TriptychParameters`] `params` and
+    /// linking tag `J`, and verifies the proof. This ensures that the keys are those committed to by the hash, and that
+    /// the proof is valid for them, in a single check.
+    ///
+    /// Verification requires that the `transcript` matches the one used when the proof was generated.
+    ///
+    /// If the input set hash does not match, returns [`ProofError::InputSetHashMismatch`]. If the statement cannot be
+    /// built, or if the proof is invalid, returns a different [`ProofError`].
+    #[allow(non_snake_case)]
+    pub fn verify_with_ring_commitment(
+        &self,
+        params: &TriptychParameters,
+        M: &[RistrettoPoint],
+        claimed_hash: &[u8],
+        J: &RistrettoPoint,
+        transcript: &mut Transcript,
+    ) -> Result<(), ProofError> {
+        let input_set = TriptychInputSet::new(M)?;
+
+        // Check the hash before doing any further work
+        if input_set.get_hash() != claimed_hash {
+            return Err(ProofError::InputSetHashMismatch);
+        }
+
+        let statement = TriptychStatement::new(params, &input_set, J)?;
+
+        self.verify(&statement, transcript)
+    }
+
+    /// Corrupt a single field of a Triptych [`TriptychProof`].
+    ///
+    /// The [`ProofField`] `field` is changed deterministically: a group element has the Ristretto basepoint added, and
+    /// a scalar has one added. The result is well-formed and serializes normally, but does not verify. This is
+    /// intended only for testing that verifiers reject invalid proofs.
+    ///
+    /// If `field` refers to an index that is not in the proof, returns a [`ProofError`].
+    #[cfg(feature = "hazmat")]
+    pub fn corrupt_field(&mut self, field: ProofField) -> Result<(), ProofError> {
+        use curve25519_dalek::constants::RISTRETTO_BASEPOINT_POINT;
+
+        let point = match field {
+            ProofField::A => Some(&mut self.A),
+            ProofField::B => Some(&mut self.B),
+            ProofField::C => Some(&mut self.C),
+            ProofField::D => Some(&mut self.D),
+            ProofField::X(index) => self.X.get_mut(index),
+            ProofField::Y(index) => self.Y.get_mut(index),
+            ProofField::f(..) | ProofField::z_A | ProofField::z_C | ProofField::z => None,
+        };
+        if let Some(point) = point {
+            *point += RISTRETTO_BASEPOINT_POINT;
+            return Ok(());
+        }
+
+        let scalar = match field {
+            ProofField::f(row, column) => self.f.get_mut(row).and_then(|f_row| f_row.get_mut(column)),
+            ProofField::z_A => Some(&mut self.z_A),
+            ProofField::z_C => Some(&mut self.z_C),
+            ProofField::z => Some(&mut self.z),
+            _ => None,
+        }
+        .ok_or(ProofError::InvalidParameter {
+            reason: "proof field index was out of range",
+        })?;
+        *scalar += Scalar::ONE;
+
+        Ok(())
+    }
+
+    /// Check each of the verification equations for a Triptych [`TriptychProof`] separately.
+    ///
+    /// Verification requires that the `statement` and `transcript` match those used when the proof was generated.
+    /// Ordinary verification combines four equations into a single weighted check, so a failure does not indicate which
+    /// relation is violated. This instead checks each equation independently, and returns whether each holds:
+    /// 1. The `A` and `B` matrix commitment relation.
+    /// 2. The `C` and `D` matrix commitment relation.
+    /// 3. The `X` and `G` relation for the input set.
+    /// 4. The `Y` and `J` relation for the linking tag.
+    ///
+    /// If the proof dimensions do not match the statement, or if the challenge is invalid, returns a [`ProofError`].
+    ///
+    /// This is much slower than verification, and is intended only for debugging. A proof is valid only if ordinary
+    /// verification succeeds.
+    #[cfg(feature = "hazmat")]
+    #[allow(non_snake_case)]
+    pub fn verify_equations_separately(
+        &self,
+        statement: &TriptychStatement,
+        transcript: &mut Transcript,
+    ) -> Result<[bool; 4], ProofError> {
+        // Extract values for convenience
+        let params = statement.get_params();
+        let M = statement.get_input_set().get_keys();
+        let J = statement.get_J();
+
+        // Check that the proof semantics are valid for the statement
+        self.validate(params)?;
+
+        // Run the Fiat-Shamir commitment phase to get the challenge powers
+        let mut null_rng = NullRng;
+        let mut transcript = ProofTranscript::new(transcript, statement, &mut null_rng, None);
+        let xi_powers = transcript.commit(params, &self.A, &self.B, &self.C, &self.D, &self.X, &self.Y)?;
+        let xi = xi_powers[1];
+
+        // Reconstruct the remaining `f` terms
+        let f = self
+            .f
+            .iter()
+            .map(|f_row| {
+                once(xi - f_row.iter().sum::<Scalar>())
+                    .chain(f_row.iter().copied())
+                    .collect::<Vec<Scalar>>()
+            })
+            .collect::<Vec<Vec<Scalar>>>();
+
+        // Compute the `f` product for each input set element directly, so `f` may contain zero
+        let mut k_decomposed = vec![0; params.get_m() as usize];
+        let mut f_products = Vec::with_capacity(M.len());
+        for (gray_index, _, gray_new) in
+            GrayIterator::new(params.get_n(), params.get_m()).ok_or(ProofError::InvalidParameter {
+                reason: "coefficient decomposition failed",
+            })?
+        {
+            k_decomposed[gray_index] = gray_new;
+            f_products.push(
+                f.iter()
+                    .zip(k_decomposed.iter())
+                    .map(|(f_row, k)| f_row[*k as usize])
+                    .product::<Scalar>(),
+            );
+        }
+
+        // Commitment generators
+        let commitment_points = params
+            .get_CommitmentG()
+            .iter()
+            .chain(once(params.get_CommitmentH()))
+            .collect::<Vec<&RistrettoPoint>>();
+
+        // A, B
+        let AB_scalars = f
+            .iter()
+            .flatten()
+            .chain(once(&self.z_A))
+            .copied()
+            .collect::<Vec<Scalar>>();
+        let AB = RistrettoPoint::vartime_multiscalar_mul(AB_scalars.iter(), commitment_points.iter().copied()) ==
+            self.A + xi * self.B;
+
+        // C, D
+        let CD_scalars = f
+            .iter()
+            .flatten()
+            .map(|f| f * (xi - f))
+            .chain(once(self.z_C))
+            .collect::<Vec<Scalar>>();
+        let CD = RistrettoPoint::vartime_multiscalar_mul(CD_scalars.iter(), commitment_points.iter().copied()) ==
+            xi * self.C + self.D;
+
+        // X, G
+        let X_sum = self
+            .X
+            .iter()
+            .zip(xi_powers.iter())
+            .map(|(X, xi_power)| xi_power * X)
+            .sum::<RistrettoPoint>();
+        let XG =
+            RistrettoPoint::vartime_multiscalar_mul(f_products.iter(), M.iter()) - X_sum == self.z * params.get_G();
+
+        // Y, J
+        let Y_sum = self
+            .Y
+            .iter()
+            .zip(xi_powers.iter())
+            .map(|(Y, xi_power)| xi_power * Y)
+            .sum::<RistrettoPoint>();
+        let YJ = f_products.iter().sum::<Scalar>() * params.get_U() - Y_sum == self.z * J;
+
+        Ok([AB, CD, XG, YJ])
+    }
+
+    /// Verify a batch of Triptych [`TriptychProofs`](`TriptychProof`), identifying a single invalid proof if
+    /// verification fails.
+    ///
+    /// An empty batch is valid by definition.
+    ///
+    /// If verification fails, this performs a subsequent number of verifications logarithmic in the size of the batch.
+    ///
+    /// Verification requires that the `statements` and `transcripts` match those used when the `proofs` were generated,
+    /// and that they share a common [`TriptychInputSet`](`crate::statement::TriptychInputSet`) and
+    /// [`TriptychParameters`](`crate::parameters::TriptychParameters`).
+    ///
+    /// If any of the above requirements are not met, returns a [`ProofError`].
+    /// If any batch in the proof is invalid, returns a [`ProofError`] containing the index of an invalid proof.
+    /// It is not guaranteed that this index represents the _only_ invalid proof in the batch.
+    pub fn verify_batch_with_single_blame(
+        statements: &[TriptychStatement],
+        proofs: &[TriptychProof],
+        transcripts: &mut [Transcript],
+    ) -> Result<(), ProofError> {
+        // Try to verify the full batch
+        if Self::verify_batch(statements, proofs, &mut transcripts.to_vec()).is_ok() {
+            return Ok(());
+        }
+
+        // The batch failed, so find an invalid proof using a binary search
+        let mut left = 0;
+        let mut right = proofs.len();
+
+        while left < right {
+            #[allow(clippy::arithmetic_side_effects)]
+            let average = left
+                .checked_add(
+                    // This cannot underflow since `left < right`
+                    (right - left) / 2,
+                )
+                .ok_or(ProofError::FailedBatchVerificationWithSingleBlame { index: None })?;
+
+            #[allow(clippy::arithmetic_side_effects)]
+            // This cannot underflow since `left < right`
+            let mid = if (right - left) % 2 == 0 {
+                average
+            } else {
+                average
+                    .checked_add(1)
+                    .ok_or(ProofError::FailedBatchVerificationWithSingleBlame { index: None })?
+            };
 
             let failure_on_left = Self::verify_batch(
                 &statements[left..mid],
@@ -534,6 +1703,43 @@ impl TriptychProof {
         Err(ProofError::FailedBatchVerificationWithFullBlame { indexes: failures })
     }
 
+    /// Verify a batch of Triptych [`TriptychProofs`](`TriptychProof`), returning a result for each proof.
+    ///
+    /// This has the same requirements as [`TriptychProof::verify_batch`], and first attempts batch verification. If
+    /// this succeeds, every result is `Ok`. Otherwise, each proof is verified individually to determine its result,
+    /// which is much slower.
+    ///
+    /// If the numbers of `statements`, `proofs`, and `transcripts` do not match, every result is a [`ProofError`].
+    pub fn verify_batch_detailed(
+        statements: &[TriptychStatement],
+        proofs: &[TriptychProof],
+        transcripts: &mut [Transcript],
+    ) -> Vec<Result<(), ProofError>> {
+        // Check that we have the same number of statements, proofs, and transcripts
+        if statements.len() != proofs.len() || statements.len() != transcripts.len() {
+            return proofs
+                .iter()
+                .map(|_| {
+                    Err(ProofError::InvalidParameter {
+                        reason: "number of statements, proofs, and transcripts does not match",
+                    })
+                })
+                .collect();
+        }
+
+        // Try to verify the full batch, which consumes copies of the transcripts
+        let mut batch_transcripts = transcripts.to_vec();
+        if Self::verify_batch(statements, proofs, &mut batch_transcripts).is_ok() {
+            transcripts.clone_from_slice(&batch_transcripts);
+            return proofs.iter().map(|_| Ok(())).collect();
+        }
+
+        // The batch failed, so check each proof using the original transcripts
+        izip!(statements, proofs, transcripts.iter_mut())
+            .map(|(statement, proof, transcript)| proof.verify(statement, transcript))
+            .collect()
+    }
+
     /// Verify a batch of Triptych [`TriptychProofs`](`TriptychProof`).
     ///
     /// An empty batch is valid by definition.
@@ -543,130 +1749,341 @@ impl TriptychProof {
     /// [`TriptychParameters`](`crate::parameters::TriptychParameters`).
     ///
     /// If any of the above requirements are not met, or if any proof is invalid, returns a [`ProofError`].
-    #[allow(clippy::too_many_lines, non_snake_case)]
+    ///
+    /// Verification only uses public data, so it always runs in variable time. There is no separate variable-time
+    /// verifier.
     pub fn verify_batch(
         statements: &[TriptychStatement],
         proofs: &[TriptychProof],
         transcripts: &mut [Transcript],
     ) -> Result<(), ProofError> {
-        // Check that we have the same number of statements, proofs, and transcripts
-        if statements.len() != proofs.len() {
-            return Err(ProofError::InvalidParameter {
-                reason: "number of statements and proof does not match",
-            });
-        }
-        if statements.len() != transcripts.len() {
-            return Err(ProofError::InvalidParameter {
-                reason: "number of statements and transcripts does not match",
-            });
-        }
+        Self::verify_batch_internal(statements, proofs, transcripts).map(|_| ())
+    }
 
-        // An empty batch is considered trivially valid
-        let first_statement = match statements.first() {
-            Some(statement) => statement,
-            None => return Ok(()),
-        };
+    /// Verify a batch of Triptych [`TriptychProofs`](`TriptychProof`) without modifying the transcripts.
+    ///
+    /// This is identical to [`TriptychProof::verify_batch`], except that each of the `transcripts` is copied before
+    /// use. The supplied transcripts are left untouched, so the same batch can be verified again.
+    pub fn verify_batch_cloned(
+        statements: &[TriptychStatement],
+        proofs: &[TriptychProof],
+        transcripts: &[Transcript],
+    ) -> Result<(), ProofError> {
+        Self::verify_batch(statements, proofs, &mut transcripts.to_vec())
+    }
 
-        // Each statement must use the same input set (checked using the hash for efficiency)
-        if !statements.iter().map(|s| s.get_input_set().get_hash()).all_equal() {
+    /// Verify a batch of Triptych [`TriptychProofs`](`TriptychProof`) supplied by iterators.
+    ///
+    /// This is identical to [`TriptychProof::verify_batch`], except that the `statements`, `proofs`, and `transcripts`
+    /// may be supplied by any iterators over references, such as those streaming from storage. Only the references are
+    /// collected, so the statements and proofs themselves are not copied.
+    pub fn verify_batch_iter<'a, S, P, T>(statements: S, proofs: P, transcripts: T) -> Result<(), ProofError>
+    where
+        S: IntoIterator<Item = &'a TriptychStatement>,
+        P: IntoIterator<Item = &'a TriptychProof>,
+        T: IntoIterator<Item = &'a mut Transcript>,
+    {
+        let statements = statements.into_iter().collect::<Vec<&TriptychStatement>>();
+        let proofs = proofs.into_iter().collect::<Vec<&TriptychProof>>();
+        let mut transcripts = transcripts.into_iter().collect::<Vec<&mut Transcript>>();
+
+        Self::verify_batch_with_precomputation(&statements, &proofs, &mut transcripts, None, BatchProcessing::Serial)
+            .map(|_| ())
+    }
+
+    /// Verify a batch of Triptych [`TriptychProofs`](`TriptychProof`) whose statements may use different input sets and
+    /// parameters.
+    ///
+    /// The batch is partitioned into groups of statements that share both their
+    /// [`TriptychParameters`](`crate::parameters::TriptychParameters`) and
+    /// [`TriptychInputSet`](`crate::statement::TriptychInputSet`), keeping the order of the batch within each group,
+    /// and each group is verified as with [`TriptychProof::verify_batch`]. Statements and proofs are borrowed rather
+    /// than copied; only the transcripts are copied, and the caller's transcripts are updated after each group.
+    ///
+    /// Verification keys from different input sets cannot share a multiscalar multiplication, so the efficiency of
+    /// batch verification applies only within each group.
+    ///
+    /// An empty batch is valid by definition.
+    /// Verification requires that the `statements` and `transcripts` match those used when the `proofs` were generated.
+    /// If this requirement is not met, or if any proof is invalid, returns a [`ProofError`].
+    pub fn verify_batch_grouped(
+        statements: &[TriptychStatement],
+        proofs: &[TriptychProof],
+        transcripts: &mut [Transcript],
+    ) -> Result<(), ProofError> {
+        // Check that we have the same number of statements, proofs, and transcripts
+        if statements.len() != proofs.len() || statements.len() != transcripts.len() {
             return Err(ProofError::InvalidParameter {
-                reason: "statement input sets do not match",
+                reason: "number of statements, proofs, and transcripts does not match",
             });
         }
 
-        // Each statement must use the same parameters (checked using the hash for efficiency)
-        if !statements.iter().map(|s| s.get_params().get_hash()).all_equal() {
-            return Err(ProofError::InvalidParameter {
-                reason: "statement parameters do not match",
+        // Partition the batch indexes by parameters and input set, in order of first appearance
+        let mut group_indexes = BTreeMap::new();
+        let mut groups: Vec<Vec<usize>> = Vec::new();
+        for (index, statement) in statements.iter().enumerate() {
+            let key = (statement.get_params().get_hash(), statement.get_input_set().get_hash());
+            let group = *group_indexes.entry(key).or_insert_with(|| {
+                groups.push(Vec::new());
+                groups.len().saturating_sub(1)
             });
+            groups[group].push(index);
         }
 
-        // Extract common values for convenience
-        let M = first_statement.get_input_set().get_keys();
-        let params = first_statement.get_params();
-
-        // Check that all proof semantics are valid for the statement
-        for proof in proofs {
-            if proof.X.len() != params.get_m() as usize {
-                return Err(ProofError::InvalidParameter {
-                    reason: "proof `X` vector length was not `m`",
-                });
-            }
-            if proof.Y.len() != params.get_m() as usize {
-                return Err(ProofError::InvalidParameter {
-                    reason: "proof `Y` vector length was not `m`",
-                });
-            }
-            if proof.f.len() != params.get_m() as usize {
-                return Err(ProofError::InvalidParameter {
-                    reason: "proof `f` matrix did not have `m` rows",
-                });
-            }
-            for f_row in &proof.f {
-                if f_row.len() !=
-                    params.get_n().checked_sub(1).ok_or(ProofError::InvalidParameter {
-                        reason: "proof `f` matrix column count overflowed",
-                    })? as usize
-                {
-                    return Err(ProofError::InvalidParameter {
-                        reason: "proof `f` matrix did not have `n - 1` columns",
-                    });
-                }
+        // Verify each group
+        for group in groups {
+            let group_statements = group.iter().map(|i| &statements[*i]).collect::<Vec<_>>();
+            let group_proofs = group.iter().map(|i| &proofs[*i]).collect::<Vec<_>>();
+            let mut group_transcripts = group.iter().map(|i| transcripts[*i].clone()).collect::<Vec<_>>();
+
+            Self::verify_batch_with_precomputation(
+                &group_statements,
+                &group_proofs,
+                &mut group_transcripts,
+                None,
+                BatchProcessing::Serial,
+            )?;
+
+            // Update the caller's transcripts as if the proofs were verified directly
+            for (i, transcript) in group.into_iter().zip(group_transcripts) {
+                transcripts[i] = transcript;
             }
         }
 
-        // Determine the size of the final check vector, which must not overflow `usize`
-        let batch_size = u32::try_from(proofs.len()).map_err(|_| ProofError::InvalidParameter {
-            reason: "batch size overflowed `u32`",
-        })?;
+        Ok(())
+    }
 
-        // This is unlikely to overflow; even if it does, the only effect is unnecessary reallocation
-        #[allow(clippy::arithmetic_side_effects)]
-        let final_size = usize::try_from(
-            1 // G
-            + params.get_n() * params.get_m() // CommitmentG
-            + 1 // CommitmentH
-            + params.get_N() // M
-            + 1 // U
-            + batch_size * (
-                4 // A, B, C, D
-                + 1 // J
-                + 2 * params.get_m() // X, Y
-            ),
+    /// Verify a batch of Triptych [`TriptychProofs`](`TriptychProof`) using a thread pool.
+    ///
+    /// This has the same requirements and result as [`TriptychProof::verify_batch`], but processes proofs in parallel
+    /// using the global [`rayon`] thread pool before the final check. This uses additional memory proportional to the
+    /// input set size for each thread.
+    #[cfg(feature = "rayon")]
+    pub fn verify_batch_parallel(
+        statements: &[TriptychStatement],
+        proofs: &[TriptychProof],
+        transcripts: &mut [Transcript],
+    ) -> Result<(), ProofError> {
+        Self::verify_batch_with_precomputation(statements, proofs, transcripts, None, BatchProcessing::Parallel)
+            .map(|_| ())
+    }
+
+    /// Verify a batch of Triptych [`TriptychProofs`](`TriptychProof`) using a [`TriptychPrecomputation`].
+    ///
+    /// This has the same requirements and result as [`TriptychProof::verify_batch`], but uses `precomputation` for the
+    /// fixed generators in the final check. If `precomputation` was not built from the parameters of the `statements`,
+    /// returns a [`ProofError`].
+    pub fn verify_batch_precomputed(
+        statements: &[TriptychStatement],
+        proofs: &[TriptychProof],
+        transcripts: &mut [Transcript],
+        precomputation: &TriptychPrecomputation,
+    ) -> Result<(), ProofError> {
+        Self::verify_batch_with_precomputation(
+            statements,
+            proofs,
+            transcripts,
+            Some(precomputation),
+            BatchProcessing::Serial,
         )
-        .map_err(|_| ProofError::InvalidParameter {
-            reason: "multiscalar multiplication size overflowed `usize`",
-        })?;
+        .map(|_| ())
+    }
 
-        // Set up the point vector for the final check
-        let points = proofs
-            .iter()
-            .zip(statements.iter())
-            .flat_map(|(p, s)| {
-                once(&p.A)
-                    .chain(once(&p.B))
-                    .chain(once(&p.C))
-                    .chain(once(&p.D))
-                    .chain(once(s.get_J()))
-                    .chain(p.X.iter())
-                    .chain(p.Y.iter())
-            })
-            .chain(once(params.get_G()))
-            .chain(params.get_CommitmentG().iter())
-            .chain(once(params.get_CommitmentH()))
+    /// Verify a batch of Triptych [`TriptychProofs`](`TriptychProof`), collecting linking tags on success.
+    ///
+    /// This has the same requirements as [`TriptychProof::verify_batch`].
+    /// If the entire batch is valid, the compressed linking tags of all `statements` are appended to `sink` in order.
+    /// Otherwise, returns a [`ProofError`] and leaves `sink` unchanged, so only tags from verified proofs are
+    /// collected.
+    pub fn verify_batch_collect_tags(
+        statements: &[TriptychStatement],
+        proofs: &[TriptychProof],
+        transcripts: &mut [Transcript],
+        sink: &mut impl Extend<CompressedRistretto>,
+    ) -> Result<(), ProofError> {
+        Self::verify_batch(statements, proofs, transcripts)?;
+        sink.extend(statements.iter().map(|statement| statement.get_J().compress()));
+
+        Ok(())
+    }
+
+    /// Verify a batch of Triptych [`TriptychProofs`](`TriptychProof`), and check that their linking tags are distinct.
+    ///
+    /// This has the same requirements as [`TriptychProof::verify_batch`].
+    /// If the batch is valid but two statements share a linking tag, returns [`ProofError::DuplicateLinkingTag`] with
+    /// the indexes of the first such pair. This detects a signing key used more than once within the batch.
+    pub fn verify_batch_distinct_tags(
+        statements: &[TriptychStatement],
+        proofs: &[TriptychProof],
+        transcripts: &mut [Transcript],
+    ) -> Result<(), ProofError> {
+        Self::verify_batch(statements, proofs, transcripts)?;
+
+        let mut tags = BTreeMap::new();
+        for (second, statement) in statements.iter().enumerate() {
+            if let Some(first) = tags.insert(statement.get_J().compress().to_bytes(), second) {
+                return Err(ProofError::DuplicateLinkingTag { first, second });
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Verify each Triptych [`TriptychProof`] in a batch individually, yielding results as they are produced.
+    ///
+    /// This has the same requirements as [`TriptychProof::verify_batch`], but each proof is verified separately.
+    /// The returned iterator yields the index and verification result of each proof in order, which is useful for
+    /// reporting progress. This is much slower than [`TriptychProof::verify_batch`], which should be preferred when
+    /// incremental results are not needed.
+    ///
+    /// If the numbers of `statements`, `proofs`, and `transcripts` do not match, the iterator yields a single
+    /// [`ProofError`] at index `0`.
+    pub fn verify_batch_progressive<'a>(
+        statements: &'a [TriptychStatement],
+        proofs: &'a [TriptychProof],
+        transcripts: &'a mut [Transcript],
+    ) -> impl Iterator<Item = (usize, Result<(), ProofError>)> + 'a {
+        // Check that we have the same number of statements, proofs, and transcripts
+        let lengths_match = statements.len() == proofs.len() && statements.len() == transcripts.len();
+        let mismatch = (!lengths_match).then_some((
+            0,
+            Err(ProofError::InvalidParameter {
+                reason: "number of statements, proofs, and transcripts does not match",
+            }),
+        ));
+        let count = if lengths_match { proofs.len() } else { 0 };
+
+        izip!(statements, proofs, transcripts.iter_mut())
+            .take(count)
+            .enumerate()
+            .map(|(index, (statement, proof, transcript))| (index, proof.verify(statement, transcript)))
+            .chain(mismatch)
+    }
+
+    /// The actual verifier functionality.
+    ///
+    /// On success, returns the Fiat-Shamir challenge for each proof.
+    fn verify_batch_internal(
+        statements: &[TriptychStatement],
+        proofs: &[TriptychProof],
+        transcripts: &mut [Transcript],
+    ) -> Result<Vec<Scalar>, ProofError> {
+        Self::verify_batch_with_precomputation(statements, proofs, transcripts, None, BatchProcessing::Serial)
+    }
+
+    /// Get the common points used in the final verification check, in the order that they are used.
+    #[allow(non_snake_case)]
+    fn common_points<'a>(
+        params: &'a TriptychParameters,
+        M: &'a [RistrettoPoint],
+    ) -> impl Iterator<Item = &'a RistrettoPoint> {
+        once(params.get_G())
+            .chain(params.get_CommitmentG().iter())
+            .chain(once(params.get_CommitmentH()))
             .chain(M.iter())
             .chain(once(params.get_U()))
-            .collect::<Vec<&RistrettoPoint>>();
+    }
+
+    /// The actual verifier functionality, optionally using a precomputation.
+    ///
+    /// The final check scalars are computed as specified by `processing`, which does not affect the result.
+    /// On success, returns the Fiat-Shamir challenge for each proof.
+    #[allow(clippy::too_many_lines, non_snake_case)]
+    pub(crate) fn verify_batch_with_precomputation<S, P, T>(
+        statements: &[S],
+        proofs: &[P],
+        transcripts: &mut [T],
+        precomputation: Option<&TriptychPrecomputation>,
+        processing: BatchProcessing,
+    ) -> Result<Vec<Scalar>, ProofError>
+    where
+        S: Borrow<TriptychStatement>,
+        P: Borrow<TriptychProof> + Sync,
+        T: BorrowMut<Transcript>,
+    {
+        // Check that we have the same number of statements, proofs, and transcripts
+        if statements.len() != proofs.len() {
+            return Err(ProofError::InvalidParameter {
+                reason: "number of statements and proof does not match",
+            });
+        }
+        if statements.len() != transcripts.len() {
+            return Err(ProofError::InvalidParameter {
+                reason: "number of statements and transcripts does not match",
+            });
+        }
+
+        // An empty batch is considered trivially valid
+        let first_statement = match statements.first() {
+            Some(statement) => statement.borrow(),
+            None => return Ok(Vec::new()),
+        };
+
+        // Each statement must use the same input set (checked using the hash for efficiency)
+        if !statements
+            .iter()
+            .map(|s| s.borrow().get_input_set().get_hash())
+            .all_equal()
+        {
+            return Err(ProofError::InvalidParameter {
+                reason: "statement input sets do not match",
+            });
+        }
+
+        // Each statement must use the same parameters (checked using the hash for efficiency)
+        if !statements
+            .iter()
+            .map(|s| s.borrow().get_params().get_hash())
+            .all_equal()
+        {
+            return Err(ProofError::InvalidParameter {
+                reason: "statement parameters do not match",
+            });
+        }
+
+        // Extract common values for convenience
+        let M = first_statement.get_input_set().get_keys();
+        let params = first_statement.get_params();
+
+        // Check that all proof semantics are valid for the statement
+        for proof in proofs {
+            proof.borrow().validate(params)?;
+        }
+
+        // Any precomputation must be for the same parameters
+        if let Some(precomputation) = precomputation {
+            if precomputation.get_params_hash() != params.get_hash() {
+                return Err(ProofError::InvalidParameter {
+                    reason: "precomputation parameters do not match",
+                });
+            }
+        }
 
-        // Start the scalar vector, putting the common elements last
-        let mut scalars = Vec::with_capacity(final_size);
+        // Determine the size of the final check vector, which must not overflow `usize`
+        let final_size = params
+            .batch_check_size(proofs.len())
+            .ok_or(ProofError::InvalidParameter {
+                reason: "batch size exceeded the maximum for the parameters",
+            })?;
 
-        // Set up common scalars
-        let mut G_scalar = Scalar::ZERO;
-        let mut CommitmentG_scalars = vec![Scalar::ZERO; params.get_CommitmentG().len()];
-        let mut CommitmentH_scalar = Scalar::ZERO;
-        let mut M_scalars = vec![Scalar::ZERO; M.len()];
-        let mut U_scalar = Scalar::ZERO;
+        // Set up the point vector for the final check, putting the common elements last
+        let proof_points = proofs.iter().zip(statements.iter()).flat_map(|(p, s)| {
+            let (p, s) = (p.borrow(), s.borrow());
+            once(&p.A)
+                .chain(once(&p.B))
+                .chain(once(&p.C))
+                .chain(once(&p.D))
+                .chain(once(s.get_J()))
+                .chain(p.X.iter())
+                .chain(p.Y.iter())
+        });
+        // With a precomputation, only the input set verification keys are added
+        let points = match precomputation {
+            Some(_) => proof_points.chain(M.iter()).collect::<Vec<&RistrettoPoint>>(),
+            None => proof_points
+                .chain(Self::common_points(params, M))
+                .collect::<Vec<&RistrettoPoint>>(),
+        };
 
         // Set up a transcript generator for use in weighting
         let mut transcript_weights = Transcript::new(domains::TRANSCRIPT_VERIFIER_WEIGHTS.as_bytes());
@@ -675,10 +2092,14 @@ impl TriptychProof {
         let mut null_rng = NullRng;
 
         // Generate all verifier challenges
+        // Challenge powers depend on each proof's transcript, so they can't be precomputed across proofs; computing
+        // them costs only `m` scalar multiplications per proof, which is negligible next to the final check
         let mut xi_powers_all = Vec::with_capacity(proofs.len());
         for (statement, proof, transcript) in izip!(statements.iter(), proofs.iter(), transcripts.iter_mut()) {
+            let proof = proof.borrow();
+
             // Set up the transcript
-            let mut transcript = ProofTranscript::new(transcript, statement, &mut null_rng, None);
+            let mut transcript = ProofTranscript::new(transcript.borrow_mut(), statement.borrow(), &mut null_rng, None);
 
             // Run the Fiat-Shamir commitment phase to get the challenge powers
             xi_powers_all.push(transcript.commit(params, &proof.A, &proof.B, &proof.C, &proof.D, &proof.X, &proof.Y)?);
@@ -691,139 +2112,198 @@ impl TriptychProof {
         // Finalize the weighting transcript into a pseudorandom number generator
         let mut transcript_weights_rng = transcript_weights.build_rng().finalize(&mut null_rng);
 
-        // Process each proof
-        for (proof, xi_powers) in proofs.iter().zip(xi_powers_all.iter()) {
-            // Reconstruct the remaining `f` terms
-            let f = (0..params.get_m())
-                .map(|j| {
-                    let mut f_j = Vec::with_capacity(params.get_n() as usize);
-                    f_j.push(xi_powers[1] - proof.f[j as usize].iter().sum::<Scalar>());
-                    f_j.extend(proof.f[j as usize].iter());
-                    f_j
-                })
-                .collect::<Vec<Vec<Scalar>>>();
-
-            // Check that `f` does not contain zero, which breaks batch inversion
-            for f_row in &f {
-                if f_row.contains(&Scalar::ZERO) {
-                    return Err(ProofError::InvalidParameter {
-                        reason: "proof `f` matrix contained 0",
-                    });
+        // Generate nonzero weights for each proof's verification equations
+        // Each equation needs its own weight, since the equations share generators (and the prover chooses `J`), so
+        // errors in one equation could otherwise be cancelled by errors in another; for example, with `w1 == w2`,
+        // shifting `z_A` and `z_C` by opposite amounts leaves the combined check unchanged. Independent weights
+        // make such cancellation succeed with negligible probability, so further weights would add cost
+        // without benefit.
+        let weights = proofs
+            .iter()
+            .map(|_| {
+                let mut weights = [Scalar::ZERO; 4];
+                while weights.contains(&Scalar::ZERO) {
+                    for weight in &mut weights {
+                        *weight = Scalar::random(&mut transcript_weights_rng);
+                    }
                 }
-            }
-
-            // Generate nonzero weights for this proof's verification equations
-            let mut w1 = Scalar::ZERO;
-            let mut w2 = Scalar::ZERO;
-            let mut w3 = Scalar::ZERO;
-            let mut w4 = Scalar::ZERO;
-            while w1 == Scalar::ZERO || w2 == Scalar::ZERO || w3 == Scalar::ZERO || w4 == Scalar::ZERO {
-                w1 = Scalar::random(&mut transcript_weights_rng);
-                w2 = Scalar::random(&mut transcript_weights_rng);
-                w3 = Scalar::random(&mut transcript_weights_rng);
-                w4 = Scalar::random(&mut transcript_weights_rng);
-            }
-
-            // Get the challenge for convenience
-            let xi = xi_powers[1];
-
-            // G
-            G_scalar -= w3 * proof.z;
-
-            // CommitmentG
-            for (CommitmentG_scalar, f_item) in CommitmentG_scalars
-                .iter_mut()
-                .zip(f.iter().flatten().map(|f| w1 * f + w2 * f * (xi - f)))
-            {
-                *CommitmentG_scalar += f_item;
-            }
-
-            // CommitmentH
-            CommitmentH_scalar += w1 * proof.z_A + w2 * proof.z_C;
 
-            // A
-            scalars.push(-w1);
-
-            // B
-            scalars.push(-w1 * xi_powers[1]);
+                weights
+            })
+            .collect::<Vec<[Scalar; 4]>>();
 
-            // C
-            scalars.push(-w2 * xi_powers[1]);
+        // Process each proof
+        let batch_scalars = match processing {
+            BatchProcessing::Serial => {
+                Self::batch_scalars(params, M.len(), final_size, proofs, &xi_powers_all, &weights)?
+            },
+            #[cfg(feature = "rayon")]
+            BatchProcessing::Parallel => {
+                Self::batch_scalars_parallel(params, M.len(), final_size, proofs, &xi_powers_all, &weights)?
+            },
+        };
 
-            // D
-            scalars.push(-w2);
+        // Perform the final check; this can be done in variable time since it holds no secrets
+        let result = match precomputation {
+            Some(precomputation) => {
+                // The precomputation does not use an efficient algorithm for many dynamic points, so we handle them
+                // separately
+                let (fixed_scalars, dynamic_scalars) = batch_scalars.into_precomputed_scalars();
+                precomputation.get_table().vartime_multiscalar_mul(fixed_scalars.iter()) +
+                    RistrettoPoint::vartime_multiscalar_mul(dynamic_scalars.iter(), points)
+            },
+            None => RistrettoPoint::vartime_multiscalar_mul(batch_scalars.into_scalars().iter(), points),
+        };
+        if result == RistrettoPoint::identity() {
+            Ok(xi_powers_all.iter().map(|xi_powers| xi_powers[1]).collect())
+        } else {
+            Err(ProofError::FailedVerification)
+        }
+    }
 
-            // J
-            scalars.push(-w4 * proof.z);
+    /// Accumulate the final check scalars for a batch of proofs serially.
+    ///
+    /// The `xi_powers_all` and `weights` must contain the challenge powers and verification equation weights for each
+    /// of the `proofs`.
+    fn batch_scalars<P: Borrow<TriptychProof>>(
+        params: &TriptychParameters,
+        input_set_size: usize,
+        final_size: usize,
+        proofs: &[P],
+        xi_powers_all: &[Vec<Scalar>],
+        weights: &[[Scalar; 4]],
+    ) -> Result<BatchScalars, ProofError> {
+        let mut batch_scalars = BatchScalars::new(params.get_CommitmentG().len(), input_set_size, final_size);
+        for (proof, xi_powers, weights) in izip!(proofs, xi_powers_all, weights) {
+            proof
+                .borrow()
+                .accumulate_scalars(params, xi_powers, weights, &mut batch_scalars)?;
+        }
 
-            // X
-            for xi_power in &xi_powers[0..(params.get_m() as usize)] {
-                scalars.push(-w3 * xi_power);
-            }
+        Ok(batch_scalars)
+    }
 
-            // Y
-            for xi_power in &xi_powers[0..(params.get_m() as usize)] {
-                scalars.push(-w4 * xi_power);
-            }
+    /// Accumulate the final check scalars for a batch of proofs in parallel.
+    ///
+    /// This produces exactly the same scalars as [`TriptychProof::batch_scalars`]. Each thread accumulates the proofs
+    /// in a contiguous range of the batch, and the partial results are combined in order.
+    #[cfg(feature = "rayon")]
+    fn batch_scalars_parallel<P: Borrow<TriptychProof> + Sync>(
+        params: &TriptychParameters,
+        input_set_size: usize,
+        final_size: usize,
+        proofs: &[P],
+        xi_powers_all: &[Vec<Scalar>],
+        weights: &[[Scalar; 4]],
+    ) -> Result<BatchScalars, ProofError> {
+        use rayon::prelude::*;
+
+        proofs
+            .par_iter()
+            .zip(xi_powers_all.par_iter())
+            .zip(weights.par_iter())
+            .try_fold(
+                || BatchScalars::new(params.get_CommitmentG().len(), input_set_size, 0),
+                |mut batch_scalars, ((proof, xi_powers), weights)| {
+                    proof
+                        .borrow()
+                        .accumulate_scalars(params, xi_powers, weights, &mut batch_scalars)?;
+                    Ok(batch_scalars)
+                },
+            )
+            .try_reduce(
+                || BatchScalars::new(params.get_CommitmentG().len(), input_set_size, final_size),
+                |left, right| Ok(left.merge(right)),
+            )
+    }
 
-            // Set up the initial `f` product and Gray iterator
-            let mut f_product = f.iter().map(|f_row| f_row[0]).product::<Scalar>();
-            let gray_iterator =
-                GrayIterator::new(params.get_n(), params.get_m()).ok_or(ProofError::InvalidParameter {
-                    reason: "coefficient decomposition failed",
-                })?;
-
-            // Invert each element of `f` for efficiency
-            let mut f_inverse_flat = f.iter().flatten().copied().collect::<Vec<Scalar>>();
-            Scalar::batch_invert(&mut f_inverse_flat);
-            let f_inverse = f_inverse_flat
-                .chunks_exact(params.get_n() as usize)
-                .collect::<Vec<&[Scalar]>>();
-
-            // M
-            let mut U_scalar_proof = Scalar::ZERO;
-            for (M_scalar, (gray_index, gray_old, gray_new)) in M_scalars.iter_mut().zip(gray_iterator) {
-                // Update the `f` product
-                f_product *= f_inverse[gray_index][gray_old as usize] * f[gray_index][gray_new as usize];
-
-                *M_scalar += w3 * f_product;
-                U_scalar_proof += f_product;
-            }
+    /// Accumulate the final check scalars for this proof, using its challenge powers `xi_powers` and verification
+    /// equation `weights`.
+    #[allow(non_snake_case)]
+    fn accumulate_scalars(
+        &self,
+        params: &TriptychParameters,
+        xi_powers: &[Scalar],
+        weights: &[Scalar; 4],
+        batch_scalars: &mut BatchScalars,
+    ) -> Result<(), ProofError> {
+        let responses = ProofResponses {
+            f: &self.f,
+            z_A: &self.z_A,
+            z_C: &self.z_C,
+            z: &self.z,
+        };
+        batch_scalars
+            .accumulate(
+                params.get_n(),
+                params.get_m(),
+                &responses,
+                xi_powers,
+                weights,
+                |_, _| {},
+            )
+            .map_err(|reason| ProofError::InvalidParameter { reason })?;
 
-            // U
-            U_scalar += w4 * U_scalar_proof;
-        }
+        Ok(())
+    }
 
-        // Add all common elements to the scalar vector
-        scalars.push(G_scalar);
-        scalars.extend(CommitmentG_scalars);
-        scalars.push(CommitmentH_scalar);
-        scalars.extend(M_scalars);
-        scalars.push(U_scalar);
+    /// Get the size of a serialized [`TriptychProof`] for base `n` and exponent `m`.
+    ///
+    /// This is the length of the output of [`TriptychProof::to_bytes`] for any proof using parameters with these
+    /// values, and can be computed without generating a proof. Returns `None` if `n < 2` or the size overflows
+    /// [`prim@usize`].
+    ///
+    /// Since this is a `const fn`, it can be used to size arrays for known parameters at compile time.
+    pub const fn serialized_size_for(n: u32, m: u32) -> Option<usize> {
+        let n_minus_1 = match n.checked_sub(1) {
+            Some(n_minus_1) if n_minus_1 > 0 => n_minus_1 as usize,
+            _ => return None,
+        };
+        let m = m as usize;
 
-        // Perform the final check; this can be done in variable time since it holds no secrets
-        if RistrettoPoint::vartime_multiscalar_mul(scalars.iter(), points) == RistrettoPoint::identity() {
-            Ok(())
-        } else {
-            Err(ProofError::FailedVerification)
-        }
+        // Combinators aren't available in a `const fn`, so check each step explicitly
+        // `f`
+        let Some(len) = m.checked_mul(n_minus_1) else {
+            return None;
+        };
+        // `X, Y`
+        let Some(len) = (match m.checked_mul(2) {
+            Some(vectors) => len.checked_add(vectors),
+            None => None,
+        }) else {
+            return None;
+        };
+        // `A, B, C, D, z_A, z_C, z`
+        let Some(len) = len.checked_add(7) else {
+            return None;
+        };
+        let Some(len) = len.checked_mul(SERIALIZED_BYTES) else {
+            return None;
+        };
+        // `n - 1`, `m`
+        len.checked_add(8)
     }
 
-    /// Serialize a [`TriptychProof`] to a canonical byte vector.
-    #[allow(non_snake_case)]
-    pub fn to_bytes(&self) -> Vec<u8> {
+    /// Get the size of this [`TriptychProof`] when serialized using [`TriptychProof::to_bytes`].
+    pub fn serialized_size(&self) -> usize {
         // This cannot overflow
         #[allow(clippy::arithmetic_side_effects)]
-        let mut result = Vec::with_capacity(
-            8 // `n - 1`, `m`
+        let size = 8 // `n - 1`, `m`
             + SERIALIZED_BYTES * (
                 4 // `A, B, C, D`
                 + self.X.len()
                 + self.Y.len()
                 + 3 // `z_A, z_C, z`
                 + self.f.len() * self.f[0].len()
-            ),
-        );
+            );
+
+        size
+    }
+
+    /// Serialize a [`TriptychProof`] to a canonical byte vector.
+    #[allow(non_snake_case)]
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut result = Vec::with_capacity(self.serialized_size());
         #[allow(clippy::cast_possible_truncation)]
         let n_minus_1 = self.f[0].len() as u32;
         #[allow(clippy::cast_possible_truncation)]
@@ -853,90 +2333,372 @@ impl TriptychProof {
         result
     }
 
+    /// Serialize a [`TriptychProof`] to a canonical byte vector with a compact header.
+    ///
+    /// This is identical to [`TriptychProof::to_bytes`], except that the dimensions `n - 1` and `m` are each encoded as
+    /// a minimal variable-length integer rather than four bytes. For typical parameters, this saves six bytes.
+    pub fn to_bytes_compact_header(&self) -> Vec<u8> {
+        let bytes = self.to_bytes();
+        let (header, body) = bytes.split_at(8);
+
+        let mut result = Vec::with_capacity(bytes.len());
+        for dimension in header.chunks_exact(4) {
+            let mut dimension_bytes = [0u8; 4];
+            dimension_bytes.copy_from_slice(dimension);
+            encode_varint(u32::from_le_bytes(dimension_bytes), &mut result);
+        }
+        result.extend_from_slice(body);
+
+        result
+    }
+
+    /// Serialize a [`TriptychProof`] to a canonical byte vector with a leading version byte.
+    ///
+    /// This is identical to [`TriptychProof::to_bytes`], except that a single byte identifying the serialization
+    /// version is prepended. This allows a future change to the serialization format to be detected, rather than
+    /// misparsed. Use [`TriptychProof::from_bytes_versioned`] to deserialize.
+    ///
+    /// The version byte applies only to this encoding. The encodings produced by [`TriptychProof::to_bytes`],
+    /// [`TriptychProof::to_bytes_compact_header`], and the `borsh` and `serde` implementations are unversioned, and
+    /// the version byte cannot be combined with the compact header.
+    pub fn to_bytes_versioned(&self) -> Vec<u8> {
+        let mut result = Vec::with_capacity(self.serialized_size().saturating_add(1));
+        result.push(domains::VERSION_BYTE);
+        result.extend(self.to_bytes());
+
+        result
+    }
+
+    /// Deserialize a [`TriptychProof`] from a canonical byte slice with a leading version byte.
+    ///
+    /// This reverses [`TriptychProof::to_bytes_versioned`], and performs the same checks as
+    /// [`TriptychProof::from_bytes`].
+    ///
+    /// If the version byte is missing or unknown, or if the remaining bytes do not represent a canonical encoding,
+    /// returns a [`ProofError`].
+    pub fn from_bytes_versioned(bytes: &[u8]) -> Result<Self, ProofError> {
+        match bytes.split_first() {
+            Some((&domains::VERSION_BYTE, bytes)) => Self::from_bytes(bytes),
+            _ => Err(ProofError::FailedDeserialization),
+        }
+    }
+
+    /// Deserialize a [`TriptychProof`] from a canonical byte slice with a compact header.
+    ///
+    /// This reverses [`TriptychProof::to_bytes_compact_header`], and performs the same checks as
+    /// [`TriptychProof::from_bytes`]. Non-minimal dimension encodings are rejected, so the encoding remains canonical.
+    ///
+    /// If `bytes` does not represent a canonical encoding, returns a [`ProofError`].
+    pub fn from_bytes_compact_header(bytes: &[u8]) -> Result<Self, ProofError> {
+        let (n_minus_1, bytes) = decode_varint(bytes).ok_or(ProofError::FailedDeserialization)?;
+        let (m, bytes) = decode_varint(bytes).ok_or(ProofError::FailedDeserialization)?;
+
+        let mut canonical = Vec::new();
+        canonical.extend(n_minus_1.to_le_bytes());
+        canonical.extend(m.to_le_bytes());
+        canonical.extend_from_slice(bytes);
+
+        Self::from_bytes(&canonical)
+    }
+
+    /// Read the dimensions `(n, m)` of a serialized [`TriptychProof`] without parsing the rest of it.
+    ///
+    /// This parses only the header of the canonical encoding produced by [`TriptychProof::to_bytes`], and performs the
+    /// same dimension checks as [`TriptychProof::from_bytes`]. It does not check the remaining bytes, so a successful
+    /// result does not mean that `bytes` is a valid proof. Use [`TriptychProof::serialized_size_for`] with the
+    /// dimensions to check the expected length.
+    ///
+    /// If the header is truncated or invalid, returns a [`ProofError`].
+    pub fn peek_dimensions(bytes: &[u8]) -> Result<(u32, u32), ProofError> {
+        let (n_minus_1, m, _) = CompressedTriptychProof::parse_dimensions(bytes)?;
+        let n = n_minus_1.checked_add(1).ok_or(ProofError::FailedDeserialization)?;
+
+        Ok((n, m))
+    }
+
     /// Deserialize a [`TriptychProof`] from a canonical byte slice.
     ///
     /// If `bytes` does not represent a canonical encoding, returns a [`ProofError`].
-    #[allow(non_snake_case)]
+    ///
+    /// This decompresses all group elements, which dominates the cost of deserialization. To defer this, use
+    /// [`CompressedTriptychProof::from_bytes`] instead.
     pub fn from_bytes(bytes: &[u8]) -> Result<Self, ProofError> {
-        // Helper to parse a `u32` from a `u8` iterator
-        let parse_u32 = |iter: &mut dyn Iterator<Item = &u8>| {
-            // Get the next four bytes
-            let bytes = iter.take(4).copied().collect::<Vec<u8>>();
-            if bytes.len() != 4 {
-                return Err(ProofError::FailedDeserialization);
-            }
-            let array: [u8; 4] = bytes.try_into().map_err(|_| ProofError::FailedDeserialization)?;
+        CompressedTriptychProof::from_bytes(bytes)?.decompress()
+    }
 
-            // Parse the bytes into a `u32`
-            Ok(u32::from_le_bytes(array))
-        };
+    /// Deserialize a Triptych proof from a canonical byte slice, without decompressing its group elements.
+    ///
+    /// This is identical to [`CompressedTriptychProof::from_bytes`].
+    pub fn from_bytes_unchecked_points(bytes: &[u8]) -> Result<CompressedTriptychProof, ProofError> {
+        CompressedTriptychProof::from_bytes(bytes)
+    }
 
-        // Helper to parse a scalar from a chunk iterator
-        let parse_scalar = |chunks: &mut ChunksExact<'_, u8>| -> Result<Scalar, ProofError> {
-            chunks
-                .next()
+    /// Serialize a [`TriptychProof`] to a lowercase hexadecimal string of its canonical byte encoding.
+    #[cfg(feature = "hex")]
+    pub fn to_hex(&self) -> String {
+        hex::encode(self.to_bytes())
+    }
+
+    /// Deserialize a [`TriptychProof`] from a hexadecimal string of its canonical byte encoding.
+    ///
+    /// If `string` is not valid hexadecimal, or does not represent a canonical encoding, returns a [`ProofError`].
+    #[cfg(feature = "hex")]
+    pub fn from_hex(string: &str) -> Result<Self, ProofError> {
+        let bytes = hex::decode(string).map_err(|_| ProofError::FailedDeserialization)?;
+
+        Self::from_bytes(&bytes)
+    }
+
+    /// Serialize a [`TriptychProof`] to a padded standard base64 string of its canonical byte encoding.
+    #[cfg(feature = "base64")]
+    pub fn to_base64(&self) -> String {
+        BASE64_STANDARD.encode(self.to_bytes())
+    }
+
+    /// Deserialize a [`TriptychProof`] from a padded standard base64 string of its canonical byte encoding.
+    ///
+    /// Decoding is strict: the string must be correctly padded with no unused nonzero bits. If `string` is not valid
+    /// base64, or does not represent a canonical encoding, returns a [`ProofError`].
+    #[cfg(feature = "base64")]
+    pub fn from_base64(string: &str) -> Result<Self, ProofError> {
+        let bytes = BASE64_STANDARD
+            .decode(string)
+            .map_err(|_| ProofError::FailedDeserialization)?;
+
+        Self::from_bytes(&bytes)
+    }
+
+    /// Serialize a [`TriptychProof`] to a `writer`.
+    ///
+    /// This writes exactly the canonical encoding produced by [`TriptychProof::to_bytes`], without allocating it.
+    /// If writing fails, returns the underlying error.
+    #[cfg(feature = "std")]
+    #[allow(non_snake_case)]
+    pub fn write_to<W: Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        #[allow(clippy::cast_possible_truncation)]
+        let n_minus_1 = self.f[0].len() as u32;
+        #[allow(clippy::cast_possible_truncation)]
+        let m = self.f.len() as u32;
+        writer.write_all(&n_minus_1.to_le_bytes())?;
+        writer.write_all(&m.to_le_bytes())?;
+
+        for point in [&self.A, &self.B, &self.C, &self.D] {
+            writer.write_all(point.compress().as_bytes())?;
+        }
+        for scalar in [&self.z_A, &self.z_C, &self.z] {
+            writer.write_all(scalar.as_bytes())?;
+        }
+        for point in self.X.iter().chain(self.Y.iter()) {
+            writer.write_all(point.compress().as_bytes())?;
+        }
+        for f in self.f.iter().flatten() {
+            writer.write_all(f.as_bytes())?;
+        }
+
+        Ok(())
+    }
+
+    /// Deserialize a [`TriptychProof`] from a `reader`.
+    ///
+    /// This reads a canonical encoding produced by [`TriptychProof::to_bytes`] or [`TriptychProof::write_to`], and
+    /// performs the same checks as [`TriptychProof::from_bytes`]. Since the reader may contain further data, exactly
+    /// the number of elements implied by the encoded dimensions is read, and any remaining data is left in the reader.
+    ///
+    /// If reading fails, or if the data does not represent a canonical encoding, returns a [`ProofError`].
+    #[cfg(feature = "std")]
+    #[allow(non_snake_case)]
+    pub fn read_from<R: Read>(reader: &mut R) -> Result<Self, ProofError> {
+        // Helper to read a fixed number of bytes
+        fn read_array<R: Read, const N: usize>(reader: &mut R) -> Result<[u8; N], ProofError> {
+            let mut bytes = [0u8; N];
+            reader
+                .read_exact(&mut bytes)
+                .map_err(|_| ProofError::FailedDeserialization)?;
+
+            Ok(bytes)
+        }
+
+        // Helper to read a scalar
+        let read_scalar = |reader: &mut R| -> Result<Scalar, ProofError> {
+            Option::<Scalar>::from(Scalar::from_canonical_bytes(read_array(reader)?))
                 .ok_or(ProofError::FailedDeserialization)
-                .and_then(|slice| {
-                    let bytes: [u8; SERIALIZED_BYTES] =
-                        slice.try_into().map_err(|_| ProofError::FailedDeserialization)?;
-                    Option::<Scalar>::from(Scalar::from_canonical_bytes(bytes)).ok_or(ProofError::FailedDeserialization)
-                })
         };
 
-        // Helper to parse a compressed point from a chunk iterator
-        let parse_point = |chunks: &mut ChunksExact<'_, u8>| -> Result<RistrettoPoint, ProofError> {
-            chunks
-                .next()
+        // Helper to read a point
+        let read_point = |reader: &mut R| -> Result<RistrettoPoint, ProofError> {
+            CompressedRistretto(read_array(reader)?)
+                .decompress()
                 .ok_or(ProofError::FailedDeserialization)
-                .and_then(|slice| {
-                    let bytes: [u8; SERIALIZED_BYTES] =
-                        slice.try_into().map_err(|_| ProofError::FailedDeserialization)?;
-
-                    CompressedRistretto::from_slice(&bytes)
-                        .map_err(|_| ProofError::FailedDeserialization)?
-                        .decompress()
-                        .ok_or(ProofError::FailedDeserialization)
-                })
         };
 
-        // Set up the slice iterator
-        let mut iter = bytes.iter();
-
-        // Parse the encoded vector dimensions and check that `n, m > 1` and that they do not overflow
-        let n_minus_1 = parse_u32(&mut iter)?;
+        // Read the encoded vector dimensions and check that `n, m > 1` and that they do not overflow
+        let n_minus_1 = u32::from_le_bytes(read_array(reader)?);
         if n_minus_1.checked_add(1).ok_or(ProofError::FailedDeserialization)? < 2 {
             return Err(ProofError::FailedDeserialization);
         }
-        let m = parse_u32(&mut iter)?;
-        if m < 2 {
+        let m = u32::from_le_bytes(read_array(reader)?);
+        if !(2..=MAX_EXPONENT).contains(&m) {
             return Err(ProofError::FailedDeserialization);
         }
 
-        // The rest of the serialization is of encoded proof elements
-        let mut chunks = iter.as_slice().chunks_exact(SERIALIZED_BYTES);
-
-        // Extract the fixed proof elements
-        let A = parse_point(&mut chunks)?;
-        let B = parse_point(&mut chunks)?;
-        let C = parse_point(&mut chunks)?;
-        let D = parse_point(&mut chunks)?;
-        let z_A = parse_scalar(&mut chunks)?;
-        let z_C = parse_scalar(&mut chunks)?;
-        let z = parse_scalar(&mut chunks)?;
-
-        // Extract the `X` and `Y` vectors
+        // Read the fixed proof elements
+        let A = read_point(reader)?;
+        let B = read_point(reader)?;
+        let C = read_point(reader)?;
+        let D = read_point(reader)?;
+        let z_A = read_scalar(reader)?;
+        let z_C = read_scalar(reader)?;
+        let z = read_scalar(reader)?;
+
+        // Read the `X` and `Y` vectors and the `f` matrix; these are not preallocated, since the dimensions are not
+        // trusted
         let X = (0..m)
-            .map(|_| parse_point(&mut chunks))
+            .map(|_| read_point(reader))
             .collect::<Result<Vec<RistrettoPoint>, ProofError>>()?;
         let Y = (0..m)
-            .map(|_| parse_point(&mut chunks))
+            .map(|_| read_point(reader))
             .collect::<Result<Vec<RistrettoPoint>, ProofError>>()?;
-
-        // Extract the `f` matrix
         let f = (0..m)
             .map(|_| {
                 (0..n_minus_1)
-                    .map(|_| parse_scalar(&mut chunks))
+                    .map(|_| read_scalar(reader))
+                    .collect::<Result<Vec<Scalar>, ProofError>>()
+            })
+            .collect::<Result<Vec<Vec<Scalar>>, ProofError>>()?;
+
+        Ok(TriptychProof {
+            A,
+            B,
+            C,
+            D,
+            X,
+            Y,
+            f,
+            z_A,
+            z_C,
+            z,
+        })
+    }
+}
+
+/// A Triptych proof whose group elements have not yet been decompressed.
+///
+/// Deserializing a [`TriptychProof`] is dominated by group element decompression. When many proofs are deserialized
+/// but only some are verified, a [`CompressedTriptychProof`] defers this cost until
+/// [`CompressedTriptychProof::decompress`] is called. Scalars are still checked for canonical encoding when
+/// deserializing.
+///
+/// Deserialization without decompression is typically more than an order of magnitude faster; you can examine this
+/// using the `deserialize_proof` benchmark.
+#[allow(non_snake_case)]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CompressedTriptychProof {
+    A: CompressedRistretto,
+    B: CompressedRistretto,
+    C: CompressedRistretto,
+    D: CompressedRistretto,
+    X: Vec<CompressedRistretto>,
+    Y: Vec<CompressedRistretto>,
+    f: Vec<Vec<Scalar>>,
+    z_A: Scalar,
+    z_C: Scalar,
+    z: Scalar,
+}
+
+impl CompressedTriptychProof {
+    /// Parse the encoded vector dimensions `n - 1` and `m` from the start of a serialized proof.
+    ///
+    /// This checks that `n, m > 1` and that they do not overflow, and returns the remaining bytes.
+    fn parse_dimensions(bytes: &[u8]) -> Result<(u32, u32, &[u8]), ProofError> {
+        // Helper to parse a `u32` from the start of a byte slice
+        fn parse_u32(bytes: &[u8]) -> Result<(u32, &[u8]), ProofError> {
+            let (value, rest) = bytes
+                .split_first_chunk::<4>()
+                .ok_or(ProofError::FailedDeserialization)?;
+
+            Ok((u32::from_le_bytes(*value), rest))
+        }
+
+        let (n_minus_1, bytes) = parse_u32(bytes)?;
+        if n_minus_1.checked_add(1).ok_or(ProofError::FailedDeserialization)? < 2 {
+            return Err(ProofError::FailedDeserialization);
+        }
+        // No valid parameters can have `m` exceeding the maximum exponent, so reject it early
+        let (m, bytes) = parse_u32(bytes)?;
+        if !(2..=MAX_EXPONENT).contains(&m) {
+            return Err(ProofError::FailedDeserialization);
+        }
+
+        Ok((n_minus_1, m, bytes))
+    }
+
+    /// Deserialize a [`CompressedTriptychProof`] from a canonical byte slice.
+    ///
+    /// This performs all the checks of [`TriptychProof::from_bytes`] except group element decompression, which is
+    /// deferred to [`CompressedTriptychProof::decompress`]. In particular, non-canonical scalar encodings are rejected.
+    ///
+    /// If `bytes` does not represent a canonical encoding, returns a [`ProofError`].
+    #[allow(non_snake_case)]
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, ProofError> {
+        // Helper to parse a scalar from a chunk iterator
+        let parse_scalar = |chunks: &mut ChunksExact<'_, u8>| -> Result<Scalar, ProofError> {
+            chunks
+                .next()
+                .ok_or(ProofError::FailedDeserialization)
+                .and_then(|slice| {
+                    let bytes: [u8; SERIALIZED_BYTES] =
+                        slice.try_into().map_err(|_| ProofError::FailedDeserialization)?;
+                    Option::<Scalar>::from(Scalar::from_canonical_bytes(bytes)).ok_or(ProofError::FailedDeserialization)
+                })
+        };
+
+        // Helper to parse a compressed point from a chunk iterator, without decompressing it
+        let parse_point = |chunks: &mut ChunksExact<'_, u8>| -> Result<CompressedRistretto, ProofError> {
+            chunks
+                .next()
+                .ok_or(ProofError::FailedDeserialization)
+                .and_then(|slice| CompressedRistretto::from_slice(slice).map_err(|_| ProofError::FailedDeserialization))
+        };
+
+        // Parse and check the encoded vector dimensions
+        let (n_minus_1, m, bytes) = Self::parse_dimensions(bytes)?;
+
+        // Check that the remaining length matches the encoded dimensions before parsing any elements
+        let expected_len = (m as usize)
+            .checked_mul(n_minus_1 as usize) // `f`
+            .and_then(|len| len.checked_add((m as usize).checked_mul(2)?)) // `X, Y`
+            .and_then(|len| len.checked_add(7)) // `A, B, C, D, z_A, z_C, z`
+            .and_then(|len| len.checked_mul(SERIALIZED_BYTES))
+            .ok_or(ProofError::FailedDeserialization)?;
+        if bytes.len() != expected_len {
+            return Err(ProofError::FailedDeserialization);
+        }
+
+        // The rest of the serialization is of encoded proof elements
+        let mut chunks = bytes.chunks_exact(SERIALIZED_BYTES);
+
+        // Extract the fixed proof elements
+        let A = parse_point(&mut chunks)?;
+        let B = parse_point(&mut chunks)?;
+        let C = parse_point(&mut chunks)?;
+        let D = parse_point(&mut chunks)?;
+        let z_A = parse_scalar(&mut chunks)?;
+        let z_C = parse_scalar(&mut chunks)?;
+        let z = parse_scalar(&mut chunks)?;
+
+        // Extract the `X` and `Y` vectors
+        let X = (0..m)
+            .map(|_| parse_point(&mut chunks))
+            .collect::<Result<Vec<CompressedRistretto>, ProofError>>()?;
+        let Y = (0..m)
+            .map(|_| parse_point(&mut chunks))
+            .collect::<Result<Vec<CompressedRistretto>, ProofError>>()?;
+
+        // Extract the `f` matrix
+        let f = (0..m)
+            .map(|_| {
+                (0..n_minus_1)
+                    .map(|_| parse_scalar(&mut chunks))
                     .collect::<Result<Vec<Scalar>, ProofError>>()
             })
             .collect::<Result<Vec<Vec<Scalar>>, ProofError>>()?;
@@ -962,7 +2724,7 @@ impl TriptychProof {
             }
         }
 
-        Ok(TriptychProof {
+        Ok(CompressedTriptychProof {
             A,
             B,
             C,
@@ -975,6 +2737,112 @@ impl TriptychProof {
             z,
         })
     }
+
+    /// Decompress this [`CompressedTriptychProof`] into a [`TriptychProof`] that can be verified.
+    ///
+    /// If any group element is not a valid encoding, returns a [`ProofError`].
+    pub fn decompress(&self) -> Result<TriptychProof, ProofError> {
+        let decompress = |point: &CompressedRistretto| point.decompress().ok_or(ProofError::FailedDeserialization);
+
+        Ok(TriptychProof {
+            A: decompress(&self.A)?,
+            B: decompress(&self.B)?,
+            C: decompress(&self.C)?,
+            D: decompress(&self.D)?,
+            X: self
+                .X
+                .iter()
+                .map(decompress)
+                .collect::<Result<Vec<RistrettoPoint>, ProofError>>()?,
+            Y: self
+                .Y
+                .iter()
+                .map(decompress)
+                .collect::<Result<Vec<RistrettoPoint>, ProofError>>()?,
+            f: self.f.clone(),
+            z_A: self.z_A,
+            z_C: self.z_C,
+            z: self.z,
+        })
+    }
+
+    /// Decompress and verify this [`CompressedTriptychProof`].
+    ///
+    /// This is equivalent to calling [`CompressedTriptychProof::decompress`] followed by [`TriptychProof::verify`].
+    /// If decompression fails, or if the proof is invalid, returns a [`ProofError`].
+    pub fn verify(&self, statement: &TriptychStatement, transcript: &mut Transcript) -> Result<(), ProofError> {
+        self.decompress()?.verify(statement, transcript)
+    }
+}
+
+impl ConstantTimeEq for TriptychProof {
+    /// Compare two [`TriptychProofs`](`TriptychProof`) in constant time.
+    ///
+    /// Proof dimensions are not secret, so proofs with different dimensions are unequal without further comparison.
+    /// Otherwise, the comparison runs in time independent of the proof contents.
+    fn ct_eq(&self, other: &Self) -> Choice {
+        // Check the dimensions in variable time
+        if self.X.len() != other.X.len() ||
+            self.Y.len() != other.Y.len() ||
+            self.f.len() != other.f.len() ||
+            self.f
+                .iter()
+                .zip(other.f.iter())
+                .any(|(f_row, other_f_row)| f_row.len() != other_f_row.len())
+        {
+            return Choice::from(0);
+        }
+
+        // Fold the element comparisons together
+        let points = izip!(
+            once(&self.A)
+                .chain(once(&self.B))
+                .chain(once(&self.C))
+                .chain(once(&self.D))
+                .chain(self.X.iter())
+                .chain(self.Y.iter()),
+            once(&other.A)
+                .chain(once(&other.B))
+                .chain(once(&other.C))
+                .chain(once(&other.D))
+                .chain(other.X.iter())
+                .chain(other.Y.iter())
+        )
+        .fold(Choice::from(1), |result, (point, other_point)| {
+            result & point.ct_eq(other_point)
+        });
+        let scalars = izip!(
+            once(&self.z_A)
+                .chain(once(&self.z_C))
+                .chain(once(&self.z))
+                .chain(self.f.iter().flatten()),
+            once(&other.z_A)
+                .chain(once(&other.z_C))
+                .chain(once(&other.z))
+                .chain(other.f.iter().flatten())
+        )
+        .fold(Choice::from(1), |result, (scalar, other_scalar)| {
+            result & scalar.ct_eq(other_scalar)
+        });
+
+        points & scalars
+    }
+}
+
+impl TryFrom<&[u8]> for TriptychProof {
+    type Error = ProofError;
+
+    /// Deserialize a [`TriptychProof`] using [`TriptychProof::from_bytes`].
+    fn try_from(bytes: &[u8]) -> Result<Self, Self::Error> {
+        Self::from_bytes(bytes)
+    }
+}
+
+impl From<&TriptychProof> for Vec<u8> {
+    /// Serialize a [`TriptychProof`] using [`TriptychProof::to_bytes`].
+    fn from(proof: &TriptychProof) -> Self {
+        proof.to_bytes()
+    }
 }
 
 #[cfg(feature = "borsh")]
@@ -996,15 +2864,25 @@ impl BorshDeserialize for TriptychProof {
 
 #[cfg(test)]
 mod test {
-    use alloc::vec::Vec;
+    use alloc::{vec, vec::Vec};
+    use core::iter::once;
 
-    use curve25519_dalek::{traits::Identity, RistrettoPoint, Scalar};
+    use curve25519_dalek::{
+        traits::{Identity, VartimeMultiscalarMul},
+        RistrettoPoint,
+        Scalar,
+    };
     use itertools::izip;
     use rand_chacha::ChaCha12Rng;
     use rand_core::{CryptoRngCore, SeedableRng};
 
+    #[cfg(feature = "rand")]
+    use crate::proof::self_test;
     use crate::{
-        proof::{ProofError, SERIALIZED_BYTES},
+        parameters::MAX_EXPONENT,
+        proof::{transcript_fingerprint, verify_serialized, CompressedTriptychProof, ProofError, SERIALIZED_BYTES},
+        transcript::ProofTranscript,
+        util::NullRng,
         Transcript,
         TriptychInputSet,
         TriptychParameters,
@@ -1013,178 +2891,1710 @@ mod test {
         TriptychWitness,
     };
 
-    // Check that the serialized proof element size constant is correct
-    #[test]
-    fn test_serialized_bytes() {
-        // Check the scalar encoding size
-        assert_eq!(Scalar::ZERO.as_bytes().len(), SERIALIZED_BYTES);
+    // Check that the serialized proof element size constant is correct
+    #[test]
+    fn test_serialized_bytes() {
+        // Check the scalar encoding size
+        assert_eq!(Scalar::ZERO.as_bytes().len(), SERIALIZED_BYTES);
+
+        // Check the group element encoding size
+        assert_eq!(RistrettoPoint::identity().compress().as_bytes().len(), SERIALIZED_BYTES);
+    }
+
+    // Generate a batch of witnesses, statements, and transcripts
+    #[allow(non_snake_case)]
+    #[allow(clippy::arithmetic_side_effects)]
+    fn generate_data<R: CryptoRngCore>(
+        n: u32,
+        m: u32,
+        b: usize,
+        rng: &mut R,
+    ) -> (Vec<TriptychWitness>, Vec<TriptychStatement>, Vec<Transcript>) {
+        // Generate parameters
+        let params = TriptychParameters::new(n, m).unwrap();
+
+        // Generate witnesses; for this test, we use adjacent indexes for simplicity
+        // This means the batch size must not exceed the input set size!
+        assert!(b <= params.get_N() as usize);
+        let mut witnesses = Vec::with_capacity(b);
+        witnesses.push(TriptychWitness::random(&params, rng));
+        for _ in 1..b {
+            let r = Scalar::random(rng);
+            let l = (witnesses.last().unwrap().get_l() + 1) % params.get_N();
+            witnesses.push(TriptychWitness::new(&params, l, &r).unwrap());
+        }
+
+        // Generate input set from all witnesses
+        let mut M = (0..params.get_N())
+            .map(|_| RistrettoPoint::random(rng))
+            .collect::<Vec<RistrettoPoint>>();
+        for witness in &witnesses {
+            M[witness.get_l() as usize] = witness.compute_verification_key();
+        }
+        let input_set = TriptychInputSet::new(&M).unwrap();
+
+        // Generate statements
+        let mut statements = Vec::with_capacity(b);
+        for witness in &witnesses {
+            let J = witness.compute_linking_tag();
+            statements.push(TriptychStatement::new(&params, &input_set, &J).unwrap());
+        }
+
+        // Generate transcripts
+        let transcripts = (0..b)
+            .map(|i| {
+                let mut transcript = Transcript::new(b"Test transcript");
+                transcript.append_u64(b"index", i as u64);
+
+                transcript
+            })
+            .collect::<Vec<Transcript>>();
+
+        (witnesses, statements, transcripts)
+    }
+
+    #[test]
+    #[cfg(feature = "rand")]
+    #[allow(non_snake_case, non_upper_case_globals)]
+    fn test_prove_verify() {
+        // Generate data
+        const n: u32 = 2;
+        const m: u32 = 4;
+        let mut rng = ChaCha12Rng::seed_from_u64(8675309);
+        let (witnesses, statements, mut transcripts) = generate_data(n, m, 1, &mut rng);
+
+        // Generate and verify a proof
+        let proof = TriptychProof::prove(&witnesses[0], &statements[0], &mut transcripts[0].clone()).unwrap();
+        assert!(proof.verify(&statements[0], &mut transcripts[0]).is_ok());
+    }
+
+    #[test]
+    #[cfg(feature = "rand")]
+    fn test_self_test() {
+        assert!(self_test().is_ok());
+    }
+
+    #[test]
+    #[allow(non_snake_case, non_upper_case_globals)]
+    fn test_prove_verify_with_rng() {
+        // Generate data
+        const n: u32 = 2;
+        const m: u32 = 4;
+        let mut rng = ChaCha12Rng::seed_from_u64(8675309);
+        let (witnesses, statements, mut transcripts) = generate_data(n, m, 1, &mut rng);
+
+        // Generate and verify a proof
+        let proof = TriptychProof::prove_with_rng(&witnesses[0], &statements[0], &mut rng, &mut transcripts[0].clone())
+            .unwrap();
+        assert!(proof.verify(&statements[0], &mut transcripts[0]).is_ok());
+    }
+
+    #[test]
+    #[cfg(feature = "rand")]
+    #[allow(non_snake_case, non_upper_case_globals)]
+    fn test_verify_bool() {
+        // Generate data
+        const n: u32 = 2;
+        const m: u32 = 4;
+        let mut rng = ChaCha12Rng::seed_from_u64(8675309);
+        let (witnesses, statements, transcripts) = generate_data(n, m, 1, &mut rng);
+
+        // A valid proof verifies
+        let proof = TriptychProof::prove(&witnesses[0], &statements[0], &mut transcripts[0].clone()).unwrap();
+        assert!(proof.verify_bool(&statements[0], &mut transcripts[0].clone()));
+
+        // A transcript mismatch fails verification
+        let mut evil_transcript = Transcript::new(b"Evil transcript");
+        assert!(proof.verify(&statements[0], &mut evil_transcript.clone()).is_err());
+        assert!(!proof.verify_bool(&statements[0], &mut evil_transcript));
+
+        // A statement with mismatched parameters is rejected as a parameter error
+        let (_, evil_statements, _) = generate_data(n, m - 1, 1, &mut rng);
+        assert!(matches!(
+            proof.verify(&evil_statements[0], &mut transcripts[0].clone()),
+            Err(ProofError::InvalidParameter { .. })
+        ));
+        assert!(!proof.verify_bool(&evil_statements[0], &mut transcripts[0].clone()));
+    }
+
+    #[test]
+    #[cfg(all(feature = "rand", feature = "hazmat"))]
+    #[allow(non_snake_case, non_upper_case_globals)]
+    fn test_prove_verify_vartime() {
+        // Generate data
+        const n: u32 = 2;
+        const m: u32 = 4;
+        let mut rng = ChaCha12Rng::seed_from_u64(8675309);
+        let (witnesses, statements, mut transcripts) = generate_data(n, m, 1, &mut rng);
+
+        // Generate and verify a proof
+        let proof = TriptychProof::prove_vartime(&witnesses[0], &statements[0], &mut transcripts[0].clone()).unwrap();
+        assert!(proof.verify(&statements[0], &mut transcripts[0]).is_ok());
+    }
+
+    #[test]
+    #[cfg(feature = "hazmat")]
+    #[allow(non_snake_case, non_upper_case_globals)]
+    fn test_prove_verify_with_timing() {
+        use crate::OperationTiming;
+
+        // Generate data
+        const n: u32 = 2;
+        const m: u32 = 4;
+        let mut rng = ChaCha12Rng::seed_from_u64(8675309);
+        let (witnesses, statements, transcripts) = generate_data(n, m, 1, &mut rng);
+
+        // Each timing mode produces the same proof as the corresponding named prover, and the proof verifies
+        for timing in [OperationTiming::Constant, OperationTiming::Variable] {
+            let mut named_rng = rng.clone();
+            let proof = TriptychProof::prove_with_timing(
+                &witnesses[0],
+                &statements[0],
+                &mut rng,
+                &mut transcripts[0].clone(),
+                timing,
+            )
+            .unwrap();
+            let named_proof = match timing {
+                OperationTiming::Constant => TriptychProof::prove_with_rng(
+                    &witnesses[0],
+                    &statements[0],
+                    &mut named_rng,
+                    &mut transcripts[0].clone(),
+                ),
+                OperationTiming::Variable => TriptychProof::prove_with_rng_vartime(
+                    &witnesses[0],
+                    &statements[0],
+                    &mut named_rng,
+                    &mut transcripts[0].clone(),
+                ),
+            }
+            .unwrap();
+
+            assert_eq!(proof, named_proof);
+            assert!(proof.verify(&statements[0], &mut transcripts[0].clone()).is_ok());
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "hazmat")]
+    #[allow(non_snake_case, non_upper_case_globals)]
+    fn test_prove_verify_vartime_with_rng() {
+        // Generate data
+        const n: u32 = 2;
+        const m: u32 = 4;
+        let mut rng = ChaCha12Rng::seed_from_u64(8675309);
+        let (witnesses, statements, mut transcripts) = generate_data(n, m, 1, &mut rng);
+
+        // Generate and verify a proof
+        let proof =
+            TriptychProof::prove_with_rng_vartime(&witnesses[0], &statements[0], &mut rng, &mut transcripts[0].clone())
+                .unwrap();
+        assert!(proof.verify(&statements[0], &mut transcripts[0]).is_ok());
+    }
+
+    #[test]
+    #[cfg(feature = "hazmat")]
+    #[allow(non_snake_case, non_upper_case_globals)]
+    fn test_prove_deterministic() {
+        // Generate data
+        const n: u32 = 2;
+        const m: u32 = 4;
+        let mut rng = ChaCha12Rng::seed_from_u64(8675309);
+        let (witnesses, statements, mut transcripts) = generate_data(n, m, 1, &mut rng);
+
+        // Proofs with the same seed are identical, and verify
+        let seed = [1u8; 32];
+        let proof =
+            TriptychProof::prove_deterministic(&witnesses[0], &statements[0], seed, &mut transcripts[0].clone())
+                .unwrap();
+        let other =
+            TriptychProof::prove_deterministic(&witnesses[0], &statements[0], seed, &mut transcripts[0].clone())
+                .unwrap();
+        assert_eq!(proof.to_bytes(), other.to_bytes());
+        assert!(proof.verify(&statements[0], &mut transcripts[0].clone()).is_ok());
+
+        // Proofs with a different seed differ
+        let other =
+            TriptychProof::prove_deterministic(&witnesses[0], &statements[0], [2u8; 32], &mut transcripts[0].clone())
+                .unwrap();
+        assert_ne!(proof.to_bytes(), other.to_bytes());
+        assert!(other.verify(&statements[0], &mut transcripts[0]).is_ok());
+    }
+
+    #[test]
+    #[allow(non_snake_case, non_upper_case_globals)]
+    fn test_prove_and_transcript() {
+        // Generate data
+        const n: u32 = 2;
+        const m: u32 = 4;
+        let mut rng = ChaCha12Rng::seed_from_u64(8675309);
+        let (witnesses, statements, transcripts) = generate_data(n, m, 1, &mut rng);
+
+        // The returned transcript is unmodified and verifies the proof
+        let (proof, mut transcript) = TriptychProof::prove_and_transcript_with_rng(
+            &witnesses[0],
+            &statements[0],
+            &mut rng,
+            transcripts[0].clone(),
+        )
+        .unwrap();
+        assert_eq!(
+            transcript_fingerprint(&transcript),
+            transcript_fingerprint(&transcripts[0])
+        );
+        assert!(proof.verify(&statements[0], &mut transcript).is_ok());
+    }
+
+    #[test]
+    #[allow(non_snake_case, non_upper_case_globals)]
+    fn test_sign_verify_signature() {
+        // Generate data
+        const n: u32 = 2;
+        const m: u32 = 4;
+        let mut rng = ChaCha12Rng::seed_from_u64(8675309);
+        let (witnesses, statements, _) = generate_data(n, m, 1, &mut rng);
+
+        // The signature verifies only against the signed message
+        let signature = TriptychProof::sign_with_rng(&witnesses[0], &statements[0], b"message", &mut rng).unwrap();
+        assert!(signature.verify_signature(&statements[0], b"message").is_ok());
+        assert!(signature.verify_signature(&statements[0], b"other message").is_err());
+    }
+
+    #[test]
+    #[allow(non_snake_case, non_upper_case_globals)]
+    fn test_prove_bundle_verify() {
+        // Generate data
+        const n: u32 = 2;
+        const m: u32 = 4;
+        let mut rng = ChaCha12Rng::seed_from_u64(8675309);
+        let (witnesses, statements, mut transcripts) = generate_data(n, m, 1, &mut rng);
+
+        // Generate a proof and its statement from the verification keys
+        let (statement, proof) = TriptychProof::prove_bundle_with_rng(
+            &witnesses[0],
+            statements[0].get_input_set().get_keys(),
+            &mut rng,
+            &mut transcripts[0].clone(),
+        )
+        .unwrap();
+
+        // The statement should match the one built externally, and the proof should verify against it
+        assert!(statement == statements[0]);
+        assert!(proof.verify(&statement, &mut transcripts[0]).is_ok());
+
+        // The reason the statement cannot be built is reported
+        assert!(matches!(
+            TriptychProof::prove_bundle_with_rng(
+                &witnesses[0],
+                &statements[0].get_input_set().get_keys()[1..],
+                &mut rng,
+                &mut transcripts[0].clone(),
+            ),
+            Err(ProofError::InvalidStatement { .. })
+        ));
+    }
+
+    #[test]
+    #[allow(non_snake_case, non_upper_case_globals)]
+    fn test_prove_errors() {
+        // Generate data
+        const n: u32 = 2;
+        const m: u32 = 4;
+        let mut rng = ChaCha12Rng::seed_from_u64(8675309);
+        let (witnesses, statements, transcripts) = generate_data(n, m, 1, &mut rng);
+        let params = statements[0].get_params();
+
+        // The witness parameters must match the statement
+        let other_params = TriptychParameters::new(n, m + 1).unwrap();
+        let witness = TriptychWitness::random(&other_params, &mut rng);
+        assert!(matches!(
+            TriptychProof::prove_with_rng(&witness, &statements[0], &mut rng, &mut transcripts[0].clone()),
+            Err(ProofError::ParameterMismatch)
+        ));
+
+        // The witness signing key must match the verification key at its index
+        let witness = TriptychWitness::new(params, witnesses[0].get_l(), &Scalar::random(&mut rng)).unwrap();
+        assert!(matches!(
+            TriptychProof::prove_with_rng(&witness, &statements[0], &mut rng, &mut transcripts[0].clone()),
+            Err(ProofError::WitnessKeyMismatch)
+        ));
+
+        // The witness signing key must match the linking tag
+        let J = TriptychWitness::random(params, &mut rng).compute_linking_tag();
+        let statement = TriptychStatement::new(params, statements[0].get_input_set(), &J).unwrap();
+        assert!(matches!(
+            TriptychProof::prove_with_rng(&witnesses[0], &statement, &mut rng, &mut transcripts[0].clone()),
+            Err(ProofError::LinkingTagMismatch)
+        ));
+    }
+
+    #[test]
+    #[allow(non_snake_case, non_upper_case_globals)]
+    fn test_prove_batch() {
+        // Generate data
+        const n: u32 = 2;
+        const m: u32 = 4;
+        const batch: usize = 3;
+        let mut rng = ChaCha12Rng::seed_from_u64(8675309);
+        let (witnesses, statements, transcripts) = generate_data(n, m, batch, &mut rng);
+        let input_set = statements[0].get_input_set();
+
+        // Batch proving matches individual proving with the same random number generator stream
+        let mut batch_rng = rng.clone();
+        let (batch_statements, batch_proofs) =
+            TriptychProof::prove_batch_with_rng(&witnesses, input_set, &mut batch_rng, &mut transcripts.clone())
+                .unwrap();
+        for (witness, statement, transcript, batch_statement, batch_proof) in izip!(
+            witnesses.iter(),
+            statements.iter(),
+            transcripts.iter(),
+            batch_statements.iter(),
+            batch_proofs.iter()
+        ) {
+            let proof = TriptychProof::prove_with_rng(witness, statement, &mut rng, &mut transcript.clone()).unwrap();
+            assert!(batch_statement == statement);
+            assert_eq!(batch_proof, &proof);
+        }
+
+        // The batch proofs verify
+        assert!(TriptychProof::verify_batch(&batch_statements, &batch_proofs, &mut transcripts.clone()).is_ok());
+
+        // The number of transcripts must match
+        assert!(
+            TriptychProof::prove_batch_with_rng(&witnesses, input_set, &mut rng, &mut transcripts[1..].to_vec())
+                .is_err()
+        );
+
+        // Witnesses must share parameters
+        let other_params = TriptychParameters::new(n, m + 1).unwrap();
+        let mixed_witnesses = [
+            TriptychWitness::random(witnesses[0].get_params(), &mut rng),
+            TriptychWitness::random(&other_params, &mut rng),
+        ];
+        assert!(TriptychProof::prove_batch_with_rng(
+            &mixed_witnesses,
+            input_set,
+            &mut rng,
+            &mut transcripts[1..].to_vec()
+        )
+        .is_err());
+
+        // The reason a statement cannot be built is reported
+        let other_witnesses = [
+            TriptychWitness::random(&other_params, &mut rng),
+            TriptychWitness::random(&other_params, &mut rng),
+        ];
+        assert!(matches!(
+            TriptychProof::prove_batch_with_rng(&other_witnesses, input_set, &mut rng, &mut transcripts[1..].to_vec()),
+            Err(ProofError::InvalidStatement { .. })
+        ));
+    }
+
+    #[test]
+    #[allow(non_snake_case, non_upper_case_globals)]
+    fn test_verify_and_challenge() {
+        // Generate data
+        const n: u32 = 2;
+        const m: u32 = 4;
+        let mut rng = ChaCha12Rng::seed_from_u64(8675309);
+        let (witnesses, statements, transcripts) = generate_data(n, m, 1, &mut rng);
+
+        // Generate a proof
+        let proof = TriptychProof::prove_with_rng(&witnesses[0], &statements[0], &mut rng, &mut transcripts[0].clone())
+            .unwrap();
+
+        // The challenge is deterministic for the transcript
+        let xi = proof
+            .verify_and_challenge(&statements[0], &mut transcripts[0].clone())
+            .unwrap();
+        assert_eq!(
+            proof
+                .verify_and_challenge(&statements[0], &mut transcripts[0].clone())
+                .unwrap(),
+            xi
+        );
+
+        // An invalid proof does not produce a challenge
+        assert!(proof
+            .verify_and_challenge(&statements[0], &mut Transcript::new(b"Evil transcript"))
+            .is_err());
+    }
+
+    #[test]
+    #[allow(non_snake_case, non_upper_case_globals)]
+    fn test_challenge() {
+        // Generate data
+        const n: u32 = 2;
+        const m: u32 = 4;
+        let mut rng = ChaCha12Rng::seed_from_u64(8675309);
+        let (witnesses, statements, transcripts) = generate_data(n, m, 1, &mut rng);
+
+        // Generate a proof
+        let proof = TriptychProof::prove_with_rng(&witnesses[0], &statements[0], &mut rng, &mut transcripts[0].clone())
+            .unwrap();
+
+        // The challenge matches the one used in verification, and does not modify the transcript
+        let fingerprint = transcript_fingerprint(&transcripts[0]);
+        let xi = proof.challenge(&statements[0], &transcripts[0]).unwrap();
+        assert_eq!(transcript_fingerprint(&transcripts[0]), fingerprint);
+        assert_eq!(
+            proof
+                .verify_and_challenge(&statements[0], &mut transcripts[0].clone())
+                .unwrap(),
+            xi
+        );
+
+        // A different transcript produces a different challenge
+        assert_ne!(
+            proof
+                .challenge(&statements[0], &Transcript::new(b"Evil transcript"))
+                .unwrap(),
+            xi
+        );
+    }
+
+    #[test]
+    #[allow(non_snake_case, non_upper_case_globals)]
+    fn test_verify_against_window() {
+        // Generate data
+        const n: u32 = 2;
+        const m: u32 = 4;
+        const batch: usize = 3; // batch size
+        let mut rng = ChaCha12Rng::seed_from_u64(8675309);
+        let (witnesses, statements, transcripts) = generate_data(n, m, batch, &mut rng);
+
+        // Generate a proof for the last statement
+        let proof = TriptychProof::prove_with_rng(&witnesses[2], &statements[2], &mut rng, &mut transcripts[2].clone())
+            .unwrap();
+
+        // The matching statement is found
+        assert_eq!(
+            proof
+                .verify_against_window(&mut transcripts[2].clone(), &statements)
+                .unwrap(),
+            2
+        );
+
+        // Statements with other parameters are skipped
+        let (_, other_statements, _) = generate_data(n, m + 1, 1, &mut rng);
+        let window = [other_statements[0].clone(), statements[2].clone()];
+        assert_eq!(
+            proof
+                .verify_against_window(&mut transcripts[2].clone(), &window)
+                .unwrap(),
+            1
+        );
+
+        // The proof fails if its statement is not in the window
+        assert!(proof
+            .verify_against_window(&mut transcripts[2].clone(), &statements[..2])
+            .is_err());
+        assert!(proof.verify_against_window(&mut transcripts[2].clone(), &[]).is_err());
+    }
+
+    #[test]
+    #[allow(non_snake_case, non_upper_case_globals)]
+    fn test_verify_min_anonymity() {
+        // Generate data
+        const n: u32 = 2;
+        const m: u32 = 4;
+        let mut rng = ChaCha12Rng::seed_from_u64(8675309);
+        let (witnesses, statements, transcripts) = generate_data(n, m, 1, &mut rng);
+
+        // Generate and verify a proof against an input set with distinct keys
+        let proof = TriptychProof::prove_with_rng(&witnesses[0], &statements[0], &mut rng, &mut transcripts[0].clone())
+            .unwrap();
+        let N = statements[0].get_params().get_N();
+        assert!(proof
+            .verify_min_anonymity(&statements[0], &mut transcripts[0].clone(), N)
+            .is_ok());
+
+        // A larger minimum is rejected
+        assert!(matches!(
+            proof.verify_min_anonymity(&statements[0], &mut transcripts[0].clone(), N + 1),
+            Err(ProofError::InsufficientAnonymity { size, minimum }) if size == N && minimum == N + 1
+        ));
+
+        // An invalid proof is rejected even if the minimum is met
+        assert!(proof
+            .verify_min_anonymity(&statements[0], &mut Transcript::new(b"Evil transcript"), N)
+            .is_err());
+    }
+
+    #[test]
+    #[allow(non_snake_case, non_upper_case_globals)]
+    fn test_verify_with_allowed_tags() {
+        // Generate data
+        const n: u32 = 2;
+        const m: u32 = 4;
+        let mut rng = ChaCha12Rng::seed_from_u64(8675309);
+        let (witnesses, statements, transcripts) = generate_data(n, m, 1, &mut rng);
+
+        // Generate and verify a proof whose linking tag is allowed
+        let proof = TriptychProof::prove_with_rng(&witnesses[0], &statements[0], &mut rng, &mut transcripts[0].clone())
+            .unwrap();
+        let other_tag = RistrettoPoint::random(&mut rng);
+        let allowed = [other_tag, *statements[0].get_J()];
+        assert!(proof
+            .verify_with_allowed_tags(&statements[0], &mut transcripts[0].clone(), &allowed)
+            .is_ok());
+
+        // A linking tag that is not allowed is rejected
+        assert!(matches!(
+            proof.verify_with_allowed_tags(&statements[0], &mut transcripts[0].clone(), &[other_tag]),
+            Err(ProofError::TagNotAllowed)
+        ));
+        assert!(matches!(
+            proof.verify_with_allowed_tags(&statements[0], &mut transcripts[0].clone(), &[]),
+            Err(ProofError::TagNotAllowed)
+        ));
+
+        // An invalid proof is rejected even if the linking tag is allowed
+        assert!(matches!(
+            proof.verify_with_allowed_tags(&statements[0], &mut Transcript::new(b"Evil transcript"), &allowed),
+            Err(ProofError::FailedVerification)
+        ));
+    }
+
+    #[test]
+    #[cfg(feature = "rand")]
+    #[allow(non_snake_case, non_upper_case_globals)]
+    fn test_prove_verify_with_context() {
+        use crate::ProofContext;
+
+        // Generate data
+        const n: u32 = 2;
+        const m: u32 = 4;
+        let mut rng = ChaCha12Rng::seed_from_u64(8675309);
+        let (witnesses, statements, _) = generate_data(n, m, 1, &mut rng);
+
+        // Build the context
+        let mut context = ProofContext::new(b"Test context");
+        context.bind_u64(b"height", 1).bind_bytes(b"message", b"hello");
+
+        // Generate and verify a proof
+        let proof = TriptychProof::prove_with_context(&witnesses[0], &statements[0], &context).unwrap();
+        assert!(proof.verify_with_context(&statements[0], &context).is_ok());
+
+        // A different context fails
+        let mut evil_context = ProofContext::new(b"Test context");
+        evil_context.bind_u64(b"height", 2).bind_bytes(b"message", b"hello");
+        assert!(proof.verify_with_context(&statements[0], &evil_context).is_err());
+    }
+
+    #[test]
+    #[cfg(feature = "rand")]
+    #[allow(non_snake_case, non_upper_case_globals)]
+    fn test_prove_verify_with_domain() {
+        use crate::ProofContext;
+
+        // Generate data
+        const n: u32 = 2;
+        const m: u32 = 4;
+        let mut rng = ChaCha12Rng::seed_from_u64(8675309);
+        let (witnesses, statements, _) = generate_data(n, m, 1, &mut rng);
+
+        // Generate and verify a proof with an application domain separator
+        let context = ProofContext::new_with_domain(b"Test protocol", 1);
+        let proof = TriptychProof::prove_with_context(&witnesses[0], &statements[0], &context).unwrap();
+        assert!(proof.verify_with_context(&statements[0], &context).is_ok());
+
+        // A different domain separator fails
+        let evil_context = ProofContext::new_with_domain(b"Evil protocol", 1);
+        assert!(proof.verify_with_context(&statements[0], &evil_context).is_err());
+
+        // A different version fails
+        let evil_context = ProofContext::new_with_domain(b"Test protocol", 2);
+        assert!(proof.verify_with_context(&statements[0], &evil_context).is_err());
+    }
+
+    #[test]
+    #[cfg(feature = "hazmat")]
+    #[allow(non_snake_case, non_upper_case_globals)]
+    fn test_corrupt_field() {
+        use crate::proof::ProofField;
+
+        // Generate data
+        const n: u32 = 2;
+        const m: u32 = 4;
+        let mut rng = ChaCha12Rng::seed_from_u64(8675309);
+        let (witnesses, statements, transcripts) = generate_data(n, m, 1, &mut rng);
+
+        // Generate a valid proof
+        let proof = TriptychProof::prove_with_rng(&witnesses[0], &statements[0], &mut rng, &mut transcripts[0].clone())
+            .unwrap();
+        assert!(proof.verify(&statements[0], &mut transcripts[0].clone()).is_ok());
+
+        // Corrupting any field causes verification to fail
+        for field in [
+            ProofField::A,
+            ProofField::B,
+            ProofField::C,
+            ProofField::D,
+            ProofField::X(0),
+            ProofField::Y(m as usize - 1),
+            ProofField::f(0, 0),
+            ProofField::f(m as usize - 1, n as usize - 2),
+            ProofField::z_A,
+            ProofField::z_C,
+            ProofField::z,
+        ] {
+            let mut evil_proof = proof.clone();
+            evil_proof.corrupt_field(field).unwrap();
+            assert_ne!(evil_proof, proof);
+            assert!(evil_proof.verify(&statements[0], &mut transcripts[0].clone()).is_err());
+
+            // The corrupted proof is still well-formed
+            assert_eq!(TriptychProof::from_bytes(&evil_proof.to_bytes()).unwrap(), evil_proof);
+        }
+
+        // Out-of-range indexes are rejected
+        let mut evil_proof = proof.clone();
+        assert!(evil_proof.corrupt_field(ProofField::X(m as usize)).is_err());
+        assert!(evil_proof.corrupt_field(ProofField::f(0, n as usize - 1)).is_err());
+        assert_eq!(evil_proof, proof);
+    }
+
+    #[test]
+    #[allow(non_snake_case, non_upper_case_globals)]
+    fn test_verify_metered() {
+        // Generate data
+        const n: u32 = 2;
+        const m: u32 = 4;
+        let mut rng = ChaCha12Rng::seed_from_u64(8675309);
+        let (witnesses, statements, transcripts) = generate_data(n, m, 1, &mut rng);
+
+        // Generate a proof
+        let proof = TriptychProof::prove_with_rng(&witnesses[0], &statements[0], &mut rng, &mut transcripts[0].clone())
+            .unwrap();
+
+        // The count includes `G, CommitmentG, CommitmentH, M, U` and `A, B, C, D, J, X, Y`
+        let N = statements[0].get_params().get_N();
+        assert_eq!(
+            proof
+                .verify_metered(&statements[0], &mut transcripts[0].clone())
+                .unwrap(),
+            (3 + n * m + N + 5 + 2 * m) as usize
+        );
+
+        // An invalid proof fails
+        assert!(proof
+            .verify_metered(&statements[0], &mut Transcript::new(b"Evil transcript"))
+            .is_err());
+    }
+
+    #[test]
+    #[allow(non_snake_case, non_upper_case_globals)]
+    fn test_verify_with_ring_commitment() {
+        // Generate data
+        const n: u32 = 2;
+        const m: u32 = 4;
+        let mut rng = ChaCha12Rng::seed_from_u64(8675309);
+        let (witnesses, statements, transcripts) = generate_data(n, m, 1, &mut rng);
+
+        // Generate a proof
+        let proof = TriptychProof::prove_with_rng(&witnesses[0], &statements[0], &mut rng, &mut transcripts[0].clone())
+            .unwrap();
+
+        // Extract the statement components
+        let params = statements[0].get_params();
+        let input_set = statements[0].get_input_set();
+        let M = input_set.get_keys();
+        let J = statements[0].get_J();
+
+        // The proof verifies against the correct hash
+        assert!(proof
+            .verify_with_ring_commitment(params, M, input_set.get_hash(), J, &mut transcripts[0].clone())
+            .is_ok());
+
+        // A mismatched hash is reported distinctly
+        assert!(matches!(
+            proof.verify_with_ring_commitment(params, M, &[0u8; 32], J, &mut transcripts[0].clone()),
+            Err(ProofError::InputSetHashMismatch)
+        ));
+
+        // Keys that don't match the hash are reported distinctly
+        let mut evil_M = M.to_vec();
+        evil_M.swap(0, 1);
+        assert!(matches!(
+            proof.verify_with_ring_commitment(params, &evil_M, input_set.get_hash(), J, &mut transcripts[0].clone()),
+            Err(ProofError::InputSetHashMismatch)
+        ));
+
+        // An invalid proof fails
+        assert!(matches!(
+            proof.verify_with_ring_commitment(
+                params,
+                M,
+                input_set.get_hash(),
+                J,
+                &mut Transcript::new(b"Evil transcript")
+            ),
+            Err(ProofError::FailedVerification)
+        ));
+    }
+
+    #[test]
+    #[cfg(feature = "hazmat")]
+    #[allow(non_snake_case, non_upper_case_globals)]
+    fn test_verify_equations_separately() {
+        // Generate data
+        const n: u32 = 2;
+        const m: u32 = 4;
+        let mut rng = ChaCha12Rng::seed_from_u64(8675309);
+        let (witnesses, statements, transcripts) = generate_data(n, m, 1, &mut rng);
+
+        // Generate a proof, for which all equations hold
+        let proof = TriptychProof::prove_with_rng(&witnesses[0], &statements[0], &mut rng, &mut transcripts[0].clone())
+            .unwrap();
+        assert_eq!(
+            proof
+                .verify_equations_separately(&statements[0], &mut transcripts[0].clone())
+                .unwrap(),
+            [true; 4]
+        );
+
+        // Each modified response violates only its equations
+        let mut evil_proof = proof.clone();
+        evil_proof.z_A += Scalar::ONE;
+        assert_eq!(
+            evil_proof
+                .verify_equations_separately(&statements[0], &mut transcripts[0].clone())
+                .unwrap(),
+            [false, true, true, true]
+        );
+
+        let mut evil_proof = proof.clone();
+        evil_proof.z_C += Scalar::ONE;
+        assert_eq!(
+            evil_proof
+                .verify_equations_separately(&statements[0], &mut transcripts[0].clone())
+                .unwrap(),
+            [true, false, true, true]
+        );
+
+        let mut evil_proof = proof;
+        evil_proof.z += Scalar::ONE;
+        assert_eq!(
+            evil_proof
+                .verify_equations_separately(&statements[0], &mut transcripts[0].clone())
+                .unwrap(),
+            [true, true, false, false]
+        );
+    }
+
+    #[test]
+    #[allow(non_snake_case, non_upper_case_globals)]
+    fn test_shared_weight_forgery() {
+        // Generate data
+        const n: u32 = 2;
+        const m: u32 = 4;
+        let mut rng = ChaCha12Rng::seed_from_u64(8675309);
+        let (witnesses, statements, transcripts) = generate_data(n, m, 1, &mut rng);
+
+        // Generate a proof, and forge it by shifting the commitment responses by opposite amounts
+        let proof = TriptychProof::prove_with_rng(&witnesses[0], &statements[0], &mut rng, &mut transcripts[0].clone())
+            .unwrap();
+        let delta = Scalar::random(&mut rng);
+        let mut forged = proof;
+        forged.z_A += delta;
+        forged.z_C -= delta;
+
+        // The forgery is rejected by the verifier
+        assert!(forged.verify(&statements[0], &mut transcripts[0].clone()).is_err());
+
+        // Get the challenge and reconstruct the `f` matrix
+        let params = statements[0].get_params();
+        let mut transcript = transcripts[0].clone();
+        let mut null_rng = NullRng;
+        let mut proof_transcript = ProofTranscript::new(&mut transcript, &statements[0], &mut null_rng, None);
+        let xi = proof_transcript
+            .commit(params, &forged.A, &forged.B, &forged.C, &forged.D, &forged.X, &forged.Y)
+            .unwrap()[1];
+        let f = forged
+            .f
+            .iter()
+            .flat_map(|f_row| once(xi - f_row.iter().sum::<Scalar>()).chain(f_row.iter().copied()))
+            .collect::<Vec<Scalar>>();
+
+        // Each of the first two equations fails on its own
+        let commit = |scalars: &[Scalar], z: &Scalar| {
+            RistrettoPoint::vartime_multiscalar_mul(
+                scalars.iter().chain(once(z)),
+                params.get_CommitmentG().iter().chain(once(params.get_CommitmentH())),
+            )
+        };
+        let f_xi_f = f.iter().map(|f| f * (xi - f)).collect::<Vec<Scalar>>();
+        assert_ne!(commit(&f, &forged.z_A), forged.A + xi * forged.B);
+        assert_ne!(commit(&f_xi_f, &forged.z_C), xi * forged.C + forged.D);
+
+        // But if they shared a weight, their combination would hold, so the forgery would pass
+        let f_sum = f.iter().zip(f_xi_f.iter()).map(|(a, b)| a + b).collect::<Vec<Scalar>>();
+        assert_eq!(
+            commit(&f_sum, &(forged.z_A + forged.z_C)),
+            forged.A + xi * forged.B + xi * forged.C + forged.D
+        );
+    }
+
+    #[test]
+    #[allow(non_snake_case, non_upper_case_globals)]
+    fn test_serialize_deserialize() {
+        // Generate data
+        const n: u32 = 2;
+        const m: u32 = 4;
+        let mut rng = ChaCha12Rng::seed_from_u64(8675309);
+        let (witnesses, statements, mut transcripts) = generate_data(n, m, 1, &mut rng);
+
+        // Generate and verify a proof
+        let proof = TriptychProof::prove_with_rng(&witnesses[0], &statements[0], &mut rng, &mut transcripts[0].clone())
+            .unwrap();
+        assert!(proof.verify(&statements[0], &mut transcripts[0]).is_ok());
+
+        // Serialize the proof
+        let serialized = proof.to_bytes();
+
+        // Deserialize the proof
+        let deserialized = TriptychProof::from_bytes(&serialized).unwrap();
+        assert_eq!(deserialized, proof);
+    }
+
+    #[test]
+    #[allow(non_snake_case, non_upper_case_globals)]
+    fn test_serialize_deserialize_versioned() {
+        // Generate data
+        const n: u32 = 2;
+        const m: u32 = 4;
+        let mut rng = ChaCha12Rng::seed_from_u64(8675309);
+        let (witnesses, statements, transcripts) = generate_data(n, m, 1, &mut rng);
+
+        // Generate a proof
+        let proof = TriptychProof::prove_with_rng(&witnesses[0], &statements[0], &mut rng, &mut transcripts[0].clone())
+            .unwrap();
+
+        // The versioned serialization prepends the version byte to the canonical serialization
+        let serialized = proof.to_bytes_versioned();
+        let (version, canonical) = serialized.split_first().unwrap();
+        assert_eq!(*version, 0);
+        assert_eq!(canonical, proof.to_bytes());
+
+        // The proof round-trips
+        assert_eq!(TriptychProof::from_bytes_versioned(&serialized).unwrap(), proof);
+
+        // An unknown version fails, even if the canonical serialization that follows it is valid
+        for bad_version in [1u8, 0xFF] {
+            let mut evil = vec![bad_version];
+            evil.extend_from_slice(canonical);
+            assert!(matches!(
+                TriptychProof::from_bytes_versioned(&evil),
+                Err(ProofError::FailedDeserialization)
+            ));
+        }
+
+        // An empty serialization fails
+        assert!(matches!(
+            TriptychProof::from_bytes_versioned(&[]),
+            Err(ProofError::FailedDeserialization)
+        ));
+    }
+
+    #[test]
+    #[allow(non_snake_case, non_upper_case_globals)]
+    fn test_peek_dimensions() {
+        // Generate data
+        const n: u32 = 3;
+        const m: u32 = 2;
+        let mut rng = ChaCha12Rng::seed_from_u64(8675309);
+        let (witnesses, statements, transcripts) = generate_data(n, m, 1, &mut rng);
+
+        // The dimensions of a valid proof are recovered
+        let proof = TriptychProof::prove_with_rng(&witnesses[0], &statements[0], &mut rng, &mut transcripts[0].clone())
+            .unwrap();
+        let serialized = proof.to_bytes();
+        assert_eq!(TriptychProof::peek_dimensions(&serialized).unwrap(), (n, m));
+
+        // Only the header is parsed
+        assert_eq!(TriptychProof::peek_dimensions(&serialized[..8]).unwrap(), (n, m));
+
+        // A truncated header fails
+        assert!(TriptychProof::peek_dimensions(&serialized[..7]).is_err());
+        assert!(TriptychProof::peek_dimensions(&[]).is_err());
+
+        // Invalid dimensions fail
+        let header = |n_minus_1: u32, exponent: u32| [n_minus_1.to_le_bytes(), exponent.to_le_bytes()].concat();
+        assert!(TriptychProof::peek_dimensions(&header(0, m)).is_err());
+        assert!(TriptychProof::peek_dimensions(&header(u32::MAX, m)).is_err());
+        assert!(TriptychProof::peek_dimensions(&header(n, 1)).is_err());
+        assert!(TriptychProof::peek_dimensions(&header(n, MAX_EXPONENT.checked_add(1).unwrap())).is_err());
+    }
+
+    #[test]
+    #[cfg(feature = "hex")]
+    #[allow(non_snake_case, non_upper_case_globals)]
+    fn test_hex() {
+        // Generate data
+        const n: u32 = 2;
+        const m: u32 = 4;
+        let mut rng = ChaCha12Rng::seed_from_u64(8675309);
+        let (witnesses, statements, transcripts) = generate_data(n, m, 1, &mut rng);
+
+        // Generate a proof
+        let proof = TriptychProof::prove_with_rng(&witnesses[0], &statements[0], &mut rng, &mut transcripts[0].clone())
+            .unwrap();
+
+        // Round trip the proof
+        let encoded = proof.to_hex();
+        assert_eq!(TriptychProof::from_hex(&encoded).unwrap(), proof);
+
+        // Malformed strings are rejected
+        assert!(TriptychProof::from_hex(&encoded[1..]).is_err());
+        assert!(TriptychProof::from_hex(&encoded.replacen(|c: char| c.is_ascii_hexdigit(), "x", 1)).is_err());
+        assert!(TriptychProof::from_hex(&encoded[2..]).is_err());
+    }
+
+    #[test]
+    #[cfg(feature = "base64")]
+    #[allow(non_snake_case, non_upper_case_globals)]
+    fn test_base64() {
+        // Generate data
+        const n: u32 = 2;
+        const m: u32 = 4;
+        let mut rng = ChaCha12Rng::seed_from_u64(8675309);
+        let (witnesses, statements, transcripts) = generate_data(n, m, 1, &mut rng);
+
+        // Generate a proof
+        let proof = TriptychProof::prove_with_rng(&witnesses[0], &statements[0], &mut rng, &mut transcripts[0].clone())
+            .unwrap();
+
+        // Round trip the proof
+        let encoded = proof.to_base64();
+        assert_eq!(TriptychProof::from_base64(&encoded).unwrap(), proof);
+
+        // Malformed strings are rejected
+        assert!(TriptychProof::from_base64(&encoded[1..]).is_err());
+        assert!(TriptychProof::from_base64(&encoded.replacen('A', "*", 1)).is_err());
+        assert!(TriptychProof::from_base64(encoded.trim_end_matches('=')).is_err());
+    }
+
+    #[test]
+    #[allow(non_snake_case, non_upper_case_globals)]
+    fn test_conversions() {
+        // Generate data
+        const n: u32 = 2;
+        const m: u32 = 4;
+        let mut rng = ChaCha12Rng::seed_from_u64(8675309);
+        let (witnesses, statements, transcripts) = generate_data(n, m, 2, &mut rng);
+
+        // Generate proofs
+        let proofs = izip!(witnesses.iter(), statements.iter(), transcripts.iter())
+            .map(|(witness, statement, transcript)| {
+                TriptychProof::prove_with_rng(witness, statement, &mut rng, &mut transcript.clone()).unwrap()
+            })
+            .collect::<Vec<TriptychProof>>();
+
+        // Conversions agree with the explicit methods
+        let serialized = proofs.iter().map(Vec::<u8>::from).collect::<Vec<Vec<u8>>>();
+        for (proof, bytes) in proofs.iter().zip(serialized.iter()) {
+            assert_eq!(bytes, &proof.to_bytes());
+        }
+        let deserialized = serialized
+            .iter()
+            .map(|bytes| TriptychProof::try_from(bytes.as_slice()))
+            .collect::<Result<Vec<TriptychProof>, ProofError>>()
+            .unwrap();
+        assert_eq!(deserialized, proofs);
+
+        // Invalid encodings fail
+        let truncated = serialized[0].split_last().unwrap().1;
+        assert!(TriptychProof::from_bytes(truncated).is_err());
+        assert!(TriptychProof::try_from(truncated).is_err());
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    #[allow(non_snake_case, non_upper_case_globals)]
+    fn test_write_read() {
+        extern crate std;
+        use std::io::{Cursor, Read};
+
+        // Generate data
+        const n: u32 = 2;
+        const m: u32 = 4;
+        let mut rng = ChaCha12Rng::seed_from_u64(8675309);
+        let (witnesses, statements, transcripts) = generate_data(n, m, 1, &mut rng);
+
+        // Generate a proof
+        let proof = TriptychProof::prove_with_rng(&witnesses[0], &statements[0], &mut rng, &mut transcripts[0].clone())
+            .unwrap();
+
+        // Writing produces the canonical encoding
+        let mut written = Vec::new();
+        proof.write_to(&mut written).unwrap();
+        assert_eq!(written, proof.to_bytes());
+
+        // Reading consumes only the proof, leaving trailing data
+        let mut stream = written.clone();
+        stream.extend_from_slice(b"trailing");
+        let mut cursor = Cursor::new(stream);
+        assert_eq!(TriptychProof::read_from(&mut cursor).unwrap(), proof);
+        let mut trailing = Vec::new();
+        cursor.read_to_end(&mut trailing).unwrap();
+        assert_eq!(trailing, b"trailing");
+
+        // Truncated data fails
+        assert!(TriptychProof::read_from(&mut Cursor::new(&written[..written.len() - 1])).is_err());
+
+        // A non-canonical scalar encoding fails
+        let mut evil = written;
+        evil[136..168].copy_from_slice(&[0xFF; 32]);
+        assert!(TriptychProof::read_from(&mut Cursor::new(evil)).is_err());
+    }
+
+    #[test]
+    #[allow(non_snake_case, non_upper_case_globals)]
+    fn test_deserialize_compressed() {
+        // Generate data
+        const n: u32 = 2;
+        const m: u32 = 4;
+        let mut rng = ChaCha12Rng::seed_from_u64(8675309);
+        let (witnesses, statements, transcripts) = generate_data(n, m, 1, &mut rng);
+
+        // Generate a proof and serialize it
+        let proof = TriptychProof::prove_with_rng(&witnesses[0], &statements[0], &mut rng, &mut transcripts[0].clone())
+            .unwrap();
+        let serialized = proof.to_bytes();
+
+        // Deferred decompression produces the same proof, which verifies
+        let compressed = TriptychProof::from_bytes_unchecked_points(&serialized).unwrap();
+        assert_eq!(compressed, CompressedTriptychProof::from_bytes(&serialized).unwrap());
+        assert_eq!(compressed.decompress().unwrap(), proof);
+        assert!(compressed.verify(&statements[0], &mut transcripts[0].clone()).is_ok());
+
+        // An invalid point encoding is only detected on decompression
+        let mut evil = serialized.clone();
+        evil[8..40].copy_from_slice(&[0xFF; 32]);
+        let compressed = TriptychProof::from_bytes_unchecked_points(&evil).unwrap();
+        assert!(compressed.decompress().is_err());
+        assert!(compressed.verify(&statements[0], &mut transcripts[0].clone()).is_err());
+        assert!(TriptychProof::from_bytes(&evil).is_err());
+
+        // A non-canonical scalar encoding is detected immediately
+        let mut evil = serialized;
+        evil[136..168].copy_from_slice(&[0xFF; 32]);
+        assert!(TriptychProof::from_bytes_unchecked_points(&evil).is_err());
+    }
+
+    #[test]
+    #[allow(non_snake_case)]
+    fn test_serialized_size() {
+        let mut rng = ChaCha12Rng::seed_from_u64(8675309);
+
+        // The sizes match the serialization for several parameters
+        for (n, m) in [(2, 2), (2, 4), (3, 3), (4, 2)] {
+            let (witnesses, statements, transcripts) = generate_data(n, m, 1, &mut rng);
+            let proof =
+                TriptychProof::prove_with_rng(&witnesses[0], &statements[0], &mut rng, &mut transcripts[0].clone())
+                    .unwrap();
+
+            assert_eq!(proof.to_bytes().len(), proof.serialized_size());
+            assert_eq!(TriptychProof::serialized_size_for(n, m), Some(proof.serialized_size()));
+        }
+
+        // Invalid parameters have no size
+        assert!(TriptychProof::serialized_size_for(1, 4).is_none());
+        assert!(TriptychProof::serialized_size_for(0, 4).is_none());
+
+        // The size can be used at compile time
+        const SIZE: Option<usize> = TriptychProof::serialized_size_for(2, 4);
+        let (witnesses, statements, transcripts) = generate_data(2, 4, 1, &mut rng);
+        let proof = TriptychProof::prove_with_rng(&witnesses[0], &statements[0], &mut rng, &mut transcripts[0].clone())
+            .unwrap();
+        let mut buffer = [0u8; SIZE.unwrap()];
+        buffer.copy_from_slice(&proof.to_bytes());
+        assert_eq!(TriptychProof::from_bytes(&buffer).unwrap(), proof);
+    }
+
+    #[test]
+    #[allow(non_snake_case, non_upper_case_globals)]
+    fn test_ct_eq() {
+        use subtle::ConstantTimeEq;
+
+        // Generate data
+        const n: u32 = 2;
+        const m: u32 = 4;
+        let mut rng = ChaCha12Rng::seed_from_u64(8675309);
+        let (witnesses, statements, transcripts) = generate_data(n, m, 1, &mut rng);
+
+        // Generate a proof
+        let proof = TriptychProof::prove_with_rng(&witnesses[0], &statements[0], &mut rng, &mut transcripts[0].clone())
+            .unwrap();
+        let serialized = proof.to_bytes();
+
+        // Equal proofs are equal
+        assert!(bool::from(proof.ct_eq(&proof.clone())));
+        assert!(bool::from(
+            proof.ct_eq(&TriptychProof::from_bytes(&serialized).unwrap())
+        ));
+
+        // A single-byte difference in a scalar is unequal
+        let mut evil_serialized = serialized.clone();
+        let z_index = 8 + 6 * SERIALIZED_BYTES; // first byte of `z`
+        evil_serialized[z_index] ^= 1;
+        let evil_proof = TriptychProof::from_bytes(&evil_serialized).unwrap();
+        assert!(!bool::from(proof.ct_eq(&evil_proof)));
+
+        // A different proof is unequal
+        let other_proof =
+            TriptychProof::prove_with_rng(&witnesses[0], &statements[0], &mut rng, &mut transcripts[0].clone())
+                .unwrap();
+        assert!(!bool::from(proof.ct_eq(&other_proof)));
+
+        // A proof with different dimensions is unequal
+        let (witnesses, statements, transcripts) = generate_data(n, m + 1, 1, &mut rng);
+        let large_proof =
+            TriptychProof::prove_with_rng(&witnesses[0], &statements[0], &mut rng, &mut transcripts[0].clone())
+                .unwrap();
+        assert!(!bool::from(proof.ct_eq(&large_proof)));
+    }
+
+    #[test]
+    #[allow(non_snake_case, non_upper_case_globals)]
+    fn test_serialize_deserialize_compact_header() {
+        // Generate data
+        const n: u32 = 2;
+        const m: u32 = 4;
+        let mut rng = ChaCha12Rng::seed_from_u64(8675309);
+        let (witnesses, statements, mut transcripts) = generate_data(n, m, 1, &mut rng);
+
+        // Generate a proof
+        let proof =
+            TriptychProof::prove_with_rng(&witnesses[0], &statements[0], &mut rng, &mut transcripts[0]).unwrap();
+
+        // The compact header saves six bytes for small dimensions, and round-trips
+        let serialized = proof.to_bytes_compact_header();
+        assert_eq!(serialized.len() + 6, proof.to_bytes().len());
+        assert_eq!(TriptychProof::from_bytes_compact_header(&serialized).unwrap(), proof);
+
+        // A non-minimal dimension encoding fails
+        let mut evil = vec![serialized[0] | 0x80, 0x00];
+        evil.extend_from_slice(&serialized[1..]);
+        assert!(TriptychProof::from_bytes_compact_header(&evil).is_err());
+
+        // Truncated encodings fail
+        assert!(TriptychProof::from_bytes_compact_header(&serialized[..serialized.len() - 1]).is_err());
+        assert!(TriptychProof::from_bytes_compact_header(&serialized[..1]).is_err());
+    }
+
+    #[test]
+    #[allow(non_snake_case, non_upper_case_globals)]
+    fn test_deserialize_invalid_length() {
+        // Generate data
+        const n: u32 = 2;
+        const m: u32 = 4;
+        let mut rng = ChaCha12Rng::seed_from_u64(8675309);
+        let (witnesses, statements, mut transcripts) = generate_data(n, m, 1, &mut rng);
+
+        // Generate a proof and serialize it
+        let proof =
+            TriptychProof::prove_with_rng(&witnesses[0], &statements[0], &mut rng, &mut transcripts[0]).unwrap();
+        let serialized = proof.to_bytes();
+
+        // Truncated and extended encodings fail
+        assert!(TriptychProof::from_bytes(&serialized[..serialized.len() - SERIALIZED_BYTES]).is_err());
+        assert!(TriptychProof::from_bytes(&serialized[..serialized.len() - 1]).is_err());
+        let mut extended = serialized.clone();
+        extended.extend_from_slice(&[0u8; SERIALIZED_BYTES]);
+        assert!(TriptychProof::from_bytes(&extended).is_err());
+
+        // Encoded dimensions that don't match the length fail
+        let mut evil_m = serialized.clone();
+        evil_m[4..8].copy_from_slice(&(m + 1).to_le_bytes());
+        assert!(TriptychProof::from_bytes(&evil_m).is_err());
+        let mut evil_m = serialized;
+        evil_m[4..8].copy_from_slice(&u32::MAX.to_le_bytes());
+        assert!(TriptychProof::from_bytes(&evil_m).is_err());
+
+        // An `m` exceeding the maximum exponent fails, even with a matching length and valid elements
+        let mut evil_m = Vec::new();
+        evil_m.extend(1u32.to_le_bytes());
+        evil_m.extend((MAX_EXPONENT + 1).to_le_bytes());
+        evil_m.resize(8 + SERIALIZED_BYTES * ((MAX_EXPONENT as usize + 1) * 3 + 7), 0);
+        assert!(TriptychProof::from_bytes(&evil_m).is_err());
+    }
+
+    #[test]
+    #[allow(non_snake_case, non_upper_case_globals)]
+    fn test_prove_verify_split() {
+        // Generate data
+        const n: u32 = 2;
+        const m: u32 = 4;
+        let mut rng = ChaCha12Rng::seed_from_u64(8675309);
+        let (witnesses, statements, transcripts) = generate_data(n, m, 1, &mut rng);
+
+        // Generate a proof using the split prover
+        let mut split_rng = rng.clone();
+        let mut transcript = transcripts[0].clone();
+        let (commitments, state) =
+            TriptychProof::commit_phase(&witnesses[0], &statements[0], &mut split_rng, &mut transcript).unwrap();
+        let proof = TriptychProof::response_phase(state, &mut transcript).unwrap();
+
+        // The proof contains the commitments and verifies
+        assert_eq!(commitments.get_A(), &proof.A);
+        assert_eq!(commitments.get_X(), proof.X.as_slice());
+        assert!(proof.verify(&statements[0], &mut transcripts[0].clone()).is_ok());
+
+        // It matches a proof from the monolithic prover with the same randomness
+        let monolithic =
+            TriptychProof::prove_with_rng(&witnesses[0], &statements[0], &mut rng, &mut transcripts[0].clone())
+                .unwrap();
+        assert_eq!(proof, monolithic);
+    }
+
+    #[test]
+    #[allow(non_snake_case, non_upper_case_globals)]
+    fn test_validate() {
+        // Generate data
+        const n: u32 = 2;
+        const m: u32 = 4;
+        let mut rng = ChaCha12Rng::seed_from_u64(8675309);
+        let (witnesses, statements, mut transcripts) = generate_data(n, m, 1, &mut rng);
+
+        // Generate a proof, which has valid dimensions
+        let proof =
+            TriptychProof::prove_with_rng(&witnesses[0], &statements[0], &mut rng, &mut transcripts[0]).unwrap();
+        let params = statements[0].get_params();
+        assert!(proof.validate(params).is_ok());
+
+        // Dimensions for other parameters fail
+        assert!(proof.validate(&TriptychParameters::new(n + 1, m).unwrap()).is_err());
+        assert!(proof.validate(&TriptychParameters::new(n, m + 1).unwrap()).is_err());
+
+        // Malformed vectors fail
+        let mut evil = proof.clone();
+        evil.X.pop();
+        assert!(evil.validate(params).is_err());
+        let mut evil = proof.clone();
+        evil.Y.pop();
+        assert!(evil.validate(params).is_err());
+        let mut evil = proof.clone();
+        evil.f.pop();
+        assert!(evil.validate(params).is_err());
+        let mut evil = proof;
+        evil.f[0].pop();
+        assert!(evil.validate(params).is_err());
+    }
+
+    #[test]
+    #[allow(non_snake_case, non_upper_case_globals)]
+    fn test_verify_serialized() {
+        // Generate data
+        const n: u32 = 2;
+        const m: u32 = 4;
+        let mut rng = ChaCha12Rng::seed_from_u64(8675309);
+        let (witnesses, statements, _) = generate_data(n, m, 1, &mut rng);
+        // The label need not be static
+        let label = b"Test transcript".to_vec();
+
+        // Generate a proof and serialize everything
+        let proof = TriptychProof::sign_with_rng(&witnesses[0], &statements[0], &label, &mut rng).unwrap();
+        let params = statements[0].get_params();
+        let keys = statements[0]
+            .get_input_set()
+            .get_keys()
+            .iter()
+            .map(|key| key.compress().to_bytes())
+            .collect::<Vec<[u8; SERIALIZED_BYTES]>>();
+        let tag = statements[0].get_J().compress().to_bytes();
+        let proof_bytes = proof.to_bytes();
+
+        // The proof should verify
+        assert!(verify_serialized(params, &keys, tag, &proof_bytes, &label).is_ok());
+
+        // Each failure stage is reported distinctly
+        let mut evil_keys = keys.clone();
+        evil_keys[1] = [0xFF; SERIALIZED_BYTES];
+        assert!(matches!(
+            verify_serialized(params, &evil_keys, tag, &proof_bytes, &label),
+            Err(ProofError::InvalidVerificationKey { index: 1 })
+        ));
+        assert!(matches!(
+            verify_serialized(params, &keys, [0xFF; SERIALIZED_BYTES], &proof_bytes, &label),
+            Err(ProofError::InvalidLinkingTag)
+        ));
+        assert!(matches!(
+            verify_serialized(params, &keys[1..], tag, &proof_bytes, &label),
+            Err(ProofError::InvalidStatement { .. })
+        ));
+        assert!(matches!(
+            verify_serialized(params, &keys, tag, &proof_bytes[1..], &label),
+            Err(ProofError::FailedDeserialization)
+        ));
+
+        // The proof length is checked before any verification keys are decompressed
+        assert!(matches!(
+            verify_serialized(params, &evil_keys, tag, &proof_bytes[1..], &label),
+            Err(ProofError::FailedDeserialization)
+        ));
+        assert!(matches!(
+            verify_serialized(params, &keys, tag, &proof_bytes, b"Evil transcript"),
+            Err(ProofError::FailedVerification)
+        ));
+    }
+
+    #[test]
+    #[cfg(feature = "borsh")]
+    #[allow(non_snake_case, non_upper_case_globals)]
+    fn test_borsh() {
+        // Generate data
+        const n: u32 = 2;
+        const m: u32 = 4;
+        let mut rng = ChaCha12Rng::seed_from_u64(8675309);
+        let (witnesses, statements, mut transcripts) = generate_data(n, m, 1, &mut rng);
+
+        // Generate and verify a proof
+        let proof = TriptychProof::prove_with_rng(&witnesses[0], &statements[0], &mut rng, &mut transcripts[0].clone())
+            .unwrap();
+        assert!(proof.verify(&statements[0], &mut transcripts[0]).is_ok());
+
+        // Serialize the proof
+        let serialized = borsh::to_vec(&proof).unwrap();
 
-        // Check the group element encoding size
-        assert_eq!(RistrettoPoint::identity().compress().as_bytes().len(), SERIALIZED_BYTES);
+        // Deserialize the proof
+        let deserialized: TriptychProof = borsh::from_slice(&serialized).unwrap();
+        assert_eq!(deserialized, proof);
     }
 
-    // Generate a batch of witnesses, statements, and transcripts
+    #[test]
     #[allow(non_snake_case)]
-    #[allow(clippy::arithmetic_side_effects)]
-    fn generate_data<R: CryptoRngCore>(
-        n: u32,
-        m: u32,
-        b: usize,
-        rng: &mut R,
-    ) -> (Vec<TriptychWitness>, Vec<TriptychStatement>, Vec<Transcript>) {
-        // Generate parameters
-        let params = TriptychParameters::new(n, m).unwrap();
+    fn test_prove_verify_larger_bases() {
+        // Test against several parameters with `n > 2`
+        for (n, m) in [(3, 2), (3, 3), (4, 2), (4, 3), (5, 2)] {
+            let mut rng = ChaCha12Rng::seed_from_u64(8675309);
+            let (witnesses, statements, transcripts) = generate_data(n, m, 2, &mut rng);
 
-        // Generate witnesses; for this test, we use adjacent indexes for simplicity
-        // This means the batch size must not exceed the input set size!
-        assert!(b <= params.get_N() as usize);
-        let mut witnesses = Vec::with_capacity(b);
-        witnesses.push(TriptychWitness::random(&params, rng));
-        for _ in 1..b {
-            let r = Scalar::random(rng);
-            let l = (witnesses.last().unwrap().get_l() + 1) % params.get_N();
-            witnesses.push(TriptychWitness::new(&params, l, &r).unwrap());
-        }
+            // Generate the proofs
+            let proofs = izip!(witnesses.iter(), statements.iter(), transcripts.clone().iter_mut())
+                .map(|(w, s, t)| TriptychProof::prove_with_rng(w, s, &mut rng, t).unwrap())
+                .collect::<Vec<TriptychProof>>();
 
-        // Generate input set from all witnesses
-        let mut M = (0..params.get_N())
-            .map(|_| RistrettoPoint::random(rng))
-            .collect::<Vec<RistrettoPoint>>();
-        for witness in &witnesses {
-            M[witness.get_l() as usize] = witness.compute_verification_key();
-        }
-        let input_set = TriptychInputSet::new(&M).unwrap();
+            // Serialization should round-trip, and the proofs should verify singly and as a batch
+            for (statement, proof, transcript) in izip!(statements.iter(), proofs.iter(), transcripts.iter()) {
+                assert_eq!(&TriptychProof::from_bytes(&proof.to_bytes()).unwrap(), proof);
+                assert!(proof.verify(statement, &mut transcript.clone()).is_ok());
+            }
+            assert!(TriptychProof::verify_batch(&statements, &proofs, &mut transcripts.clone()).is_ok());
 
-        // Generate statements
-        let mut statements = Vec::with_capacity(b);
-        for witness in &witnesses {
-            let J = witness.compute_linking_tag();
-            statements.push(TriptychStatement::new(&params, &input_set, &J).unwrap());
+            // Proofs should not verify against the wrong transcript
+            assert!(proofs[0]
+                .verify(&statements[0], &mut Transcript::new(b"Evil transcript"))
+                .is_err());
         }
+    }
 
-        // Generate transcripts
-        let transcripts = (0..b)
-            .map(|i| {
-                let mut transcript = Transcript::new(b"Test transcript");
-                transcript.append_u64(b"index", i as u64);
+    #[test]
+    #[allow(non_snake_case, non_upper_case_globals)]
+    fn test_prove_verify_batch() {
+        // Generate data
+        const n: u32 = 2;
+        const m: u32 = 4;
+        const batch: usize = 3; // batch size
+        let mut rng = ChaCha12Rng::seed_from_u64(8675309);
+        let (witnesses, statements, mut transcripts) = generate_data(n, m, batch, &mut rng);
 
-                transcript
-            })
-            .collect::<Vec<Transcript>>();
+        // Generate the proofs
+        let proofs = izip!(witnesses.iter(), statements.iter(), transcripts.clone().iter_mut())
+            .map(|(w, s, t)| TriptychProof::prove_with_rng(w, s, &mut rng, t).unwrap())
+            .collect::<Vec<TriptychProof>>();
 
-        (witnesses, statements, transcripts)
+        // Verify the batch with and without blame
+        assert!(TriptychProof::verify_batch(&statements, &proofs, &mut transcripts.clone()).is_ok());
+        assert!(TriptychProof::verify_batch_with_single_blame(&statements, &proofs, &mut transcripts.clone()).is_ok());
+        assert!(TriptychProof::verify_batch_with_full_blame(&statements, &proofs, &mut transcripts).is_ok());
     }
 
     #[test]
-    #[cfg(feature = "rand")]
     #[allow(non_snake_case, non_upper_case_globals)]
-    fn test_prove_verify() {
+    fn test_prove_verify_batch_cloned() {
         // Generate data
         const n: u32 = 2;
         const m: u32 = 4;
+        const batch: usize = 3; // batch size
         let mut rng = ChaCha12Rng::seed_from_u64(8675309);
-        let (witnesses, statements, mut transcripts) = generate_data(n, m, 1, &mut rng);
+        let (witnesses, statements, transcripts) = generate_data(n, m, batch, &mut rng);
 
-        // Generate and verify a proof
-        let proof = TriptychProof::prove(&witnesses[0], &statements[0], &mut transcripts[0].clone()).unwrap();
-        assert!(proof.verify(&statements[0], &mut transcripts[0]).is_ok());
+        // Generate the proofs
+        let proofs = izip!(witnesses.iter(), statements.iter(), transcripts.clone().iter_mut())
+            .map(|(w, s, t)| TriptychProof::prove_with_rng(w, s, &mut rng, t).unwrap())
+            .collect::<Vec<TriptychProof>>();
+
+        // Verifying does not modify the transcripts, so it succeeds repeatedly
+        let fingerprints = transcripts.iter().map(transcript_fingerprint).collect::<Vec<_>>();
+        assert!(TriptychProof::verify_batch_cloned(&statements, &proofs, &transcripts).is_ok());
+        assert!(TriptychProof::verify_batch_cloned(&statements, &proofs, &transcripts).is_ok());
+        assert_eq!(
+            transcripts.iter().map(transcript_fingerprint).collect::<Vec<_>>(),
+            fingerprints
+        );
     }
 
     #[test]
     #[allow(non_snake_case, non_upper_case_globals)]
-    fn test_prove_verify_with_rng() {
+    fn test_prove_verify_batch_iter() {
         // Generate data
         const n: u32 = 2;
         const m: u32 = 4;
+        const batch: usize = 3; // batch size
         let mut rng = ChaCha12Rng::seed_from_u64(8675309);
-        let (witnesses, statements, mut transcripts) = generate_data(n, m, 1, &mut rng);
+        let (witnesses, statements, transcripts) = generate_data(n, m, batch, &mut rng);
 
-        // Generate and verify a proof
-        let proof = TriptychProof::prove_with_rng(&witnesses[0], &statements[0], &mut rng, &mut transcripts[0].clone())
-            .unwrap();
-        assert!(proof.verify(&statements[0], &mut transcripts[0]).is_ok());
+        // Generate the proofs, storing each with its statement as a caller might
+        let records = izip!(witnesses.iter(), statements.iter(), transcripts.iter())
+            .map(|(w, s, t)| {
+                let proof = TriptychProof::prove_with_rng(w, s, &mut rng, &mut t.clone()).unwrap();
+                (s.clone(), proof)
+            })
+            .collect::<Vec<(TriptychStatement, TriptychProof)>>();
+        let proofs = records.iter().map(|(_, p)| p.clone()).collect::<Vec<TriptychProof>>();
+
+        // Iterators are accepted directly, and agree with the slice-based verifier
+        assert!(TriptychProof::verify_batch_iter(
+            records.iter().map(|(s, _)| s),
+            records.iter().map(|(_, p)| p),
+            transcripts.clone().iter_mut()
+        )
+        .is_ok());
+        assert!(TriptychProof::verify_batch(&statements, &proofs, &mut transcripts.clone()).is_ok());
+
+        // Both fail on mismatched proofs
+        assert!(TriptychProof::verify_batch_iter(
+            records.iter().map(|(s, _)| s),
+            records.iter().rev().map(|(_, p)| p),
+            transcripts.clone().iter_mut()
+        )
+        .is_err());
+        assert!(TriptychProof::verify_batch(
+            &statements,
+            &proofs.iter().rev().cloned().collect::<Vec<TriptychProof>>(),
+            &mut transcripts.clone()
+        )
+        .is_err());
+
+        // Both fail on mismatched lengths
+        assert!(TriptychProof::verify_batch_iter(
+            records.iter().map(|(s, _)| s),
+            records.iter().skip(1).map(|(_, p)| p),
+            transcripts.clone().iter_mut()
+        )
+        .is_err());
     }
 
     #[test]
-    #[cfg(all(feature = "rand", feature = "hazmat"))]
     #[allow(non_snake_case, non_upper_case_globals)]
-    fn test_prove_verify_vartime() {
+    fn test_prove_verify_batch_precomputed() {
         // Generate data
         const n: u32 = 2;
         const m: u32 = 4;
+        const batch: usize = 3; // batch size
         let mut rng = ChaCha12Rng::seed_from_u64(8675309);
-        let (witnesses, statements, mut transcripts) = generate_data(n, m, 1, &mut rng);
+        let (witnesses, statements, transcripts) = generate_data(n, m, batch, &mut rng);
 
-        // Generate and verify a proof
-        let proof = TriptychProof::prove_vartime(&witnesses[0], &statements[0], &mut transcripts[0].clone()).unwrap();
-        assert!(proof.verify(&statements[0], &mut transcripts[0]).is_ok());
+        // Generate the proofs
+        let mut proofs = izip!(witnesses.iter(), statements.iter(), transcripts.clone().iter_mut())
+            .map(|(w, s, t)| TriptychProof::prove_with_rng(w, s, &mut rng, t).unwrap())
+            .collect::<Vec<TriptychProof>>();
+
+        // Verify the batch using a precomputation
+        let params = statements[0].get_params();
+        let precomputation = params.build_precomputation();
+        assert!(TriptychProof::verify_batch_precomputed(
+            &statements,
+            &proofs,
+            &mut transcripts.clone(),
+            &precomputation
+        )
+        .is_ok());
+
+        // A precomputation for other parameters fails
+        let other_precomputation = TriptychParameters::new(n, m + 1).unwrap().build_precomputation();
+        assert!(TriptychProof::verify_batch_precomputed(
+            &statements,
+            &proofs,
+            &mut transcripts.clone(),
+            &other_precomputation
+        )
+        .is_err());
+
+        // An invalid proof fails
+        proofs[0].z += Scalar::ONE;
+        assert!(TriptychProof::verify_batch_precomputed(
+            &statements,
+            &proofs,
+            &mut transcripts.clone(),
+            &precomputation
+        )
+        .is_err());
     }
 
     #[test]
-    #[cfg(feature = "hazmat")]
     #[allow(non_snake_case, non_upper_case_globals)]
-    fn test_prove_verify_vartime_with_rng() {
+    fn test_prove_verify_batch_collect_tags() {
         // Generate data
         const n: u32 = 2;
         const m: u32 = 4;
+        const batch: usize = 3; // batch size
         let mut rng = ChaCha12Rng::seed_from_u64(8675309);
-        let (witnesses, statements, mut transcripts) = generate_data(n, m, 1, &mut rng);
+        let (witnesses, statements, mut transcripts) = generate_data(n, m, batch, &mut rng);
 
-        // Generate and verify a proof
-        let proof =
-            TriptychProof::prove_with_rng_vartime(&witnesses[0], &statements[0], &mut rng, &mut transcripts[0].clone())
-                .unwrap();
-        assert!(proof.verify(&statements[0], &mut transcripts[0]).is_ok());
+        // Generate the proofs
+        let proofs = izip!(witnesses.iter(), statements.iter(), transcripts.clone().iter_mut())
+            .map(|(w, s, t)| TriptychProof::prove_with_rng(w, s, &mut rng, t).unwrap())
+            .collect::<Vec<TriptychProof>>();
+
+        // A valid batch collects all tags in order
+        let mut tags = Vec::new();
+        assert!(
+            TriptychProof::verify_batch_collect_tags(&statements, &proofs, &mut transcripts.clone(), &mut tags).is_ok()
+        );
+        assert_eq!(
+            tags,
+            statements.iter().map(|s| s.get_J().compress()).collect::<Vec<_>>()
+        );
+
+        // An invalid batch collects nothing
+        let mut tags = Vec::new();
+        transcripts[0] = Transcript::new(b"Evil transcript");
+        assert!(TriptychProof::verify_batch_collect_tags(&statements, &proofs, &mut transcripts, &mut tags).is_err());
+        assert!(tags.is_empty());
     }
 
     #[test]
+    #[cfg(feature = "rayon")]
     #[allow(non_snake_case, non_upper_case_globals)]
-    fn test_serialize_deserialize() {
+    fn test_prove_verify_batch_parallel() {
+        use crate::batch::BatchScalars;
+
         // Generate data
         const n: u32 = 2;
         const m: u32 = 4;
+        const batch: usize = 5; // batch size
         let mut rng = ChaCha12Rng::seed_from_u64(8675309);
-        let (witnesses, statements, mut transcripts) = generate_data(n, m, 1, &mut rng);
+        let (witnesses, statements, mut transcripts) = generate_data(n, m, batch, &mut rng);
 
-        // Generate and verify a proof
-        let proof = TriptychProof::prove_with_rng(&witnesses[0], &statements[0], &mut rng, &mut transcripts[0].clone())
-            .unwrap();
-        assert!(proof.verify(&statements[0], &mut transcripts[0]).is_ok());
+        // Generate the proofs
+        let proofs = izip!(witnesses.iter(), statements.iter(), transcripts.clone().iter_mut())
+            .map(|(w, s, t)| TriptychProof::prove_with_rng(w, s, &mut rng, t).unwrap())
+            .collect::<Vec<TriptychProof>>();
 
-        // Serialize the proof
-        let serialized = proof.to_bytes();
+        // The scalars are identical to those from serial processing
+        let params = statements[0].get_params();
+        let N = params.get_N() as usize;
+        let xi_powers_all = (0..batch)
+            .map(|_| (0..=m).map(|_| Scalar::random(&mut rng)).collect::<Vec<Scalar>>())
+            .collect::<Vec<Vec<Scalar>>>();
+        let weights = (0..batch)
+            .map(|_| [(); 4].map(|_| Scalar::random(&mut rng)))
+            .collect::<Vec<[Scalar; 4]>>();
+        let serial = TriptychProof::batch_scalars(params, N, 0, &proofs, &xi_powers_all, &weights).unwrap();
+        let parallel = TriptychProof::batch_scalars_parallel(params, N, 0, &proofs, &xi_powers_all, &weights).unwrap();
+        assert_eq!(BatchScalars::into_scalars(serial), BatchScalars::into_scalars(parallel));
+
+        // Verify the batch
+        assert!(TriptychProof::verify_batch_parallel(&statements, &proofs, &mut transcripts.clone()).is_ok());
+
+        // An invalid batch fails
+        transcripts[1] = Transcript::new(b"Evil transcript");
+        assert!(TriptychProof::verify_batch_parallel(&statements, &proofs, &mut transcripts).is_err());
+    }
 
-        // Deserialize the proof
-        let deserialized = TriptychProof::from_bytes(&serialized).unwrap();
-        assert_eq!(deserialized, proof);
+    #[test]
+    #[allow(non_snake_case, non_upper_case_globals)]
+    fn test_prove_verify_batch_grouped() {
+        // Generate data for two input sets of different sizes
+        const n: u32 = 2;
+        const m: u32 = 4;
+        let mut rng = ChaCha12Rng::seed_from_u64(8675309);
+        let (witnesses_1, statements_1, transcripts_1) = generate_data(n, m, 2, &mut rng);
+        let (witnesses_2, statements_2, transcripts_2) = generate_data(n, m + 1, 2, &mut rng);
+
+        // Interleave the groups
+        let witnesses = [&witnesses_1[0], &witnesses_2[0], &witnesses_1[1], &witnesses_2[1]];
+        let statements = vec![
+            statements_1[0].clone(),
+            statements_2[0].clone(),
+            statements_1[1].clone(),
+            statements_2[1].clone(),
+        ];
+        let mut transcripts = vec![
+            transcripts_1[0].clone(),
+            transcripts_2[0].clone(),
+            transcripts_1[1].clone(),
+            transcripts_2[1].clone(),
+        ];
+
+        // Generate the proofs
+        let proofs = izip!(witnesses, statements.iter(), transcripts.clone().iter_mut())
+            .map(|(w, s, t)| TriptychProof::prove_with_rng(w, s, &mut rng, t).unwrap())
+            .collect::<Vec<TriptychProof>>();
+
+        // The ordinary batch verifier rejects the mixed batch, but grouping accepts it
+        assert!(TriptychProof::verify_batch(&statements, &proofs, &mut transcripts.clone()).is_err());
+        assert!(TriptychProof::verify_batch_grouped(&statements, &proofs, &mut transcripts.clone()).is_ok());
+        assert!(TriptychProof::verify_batch_grouped(&[], &[], &mut []).is_ok());
+
+        // An invalid proof in either group fails
+        let mut evil_transcripts = transcripts.clone();
+        evil_transcripts[3] = Transcript::new(b"Evil transcript");
+        assert!(TriptychProof::verify_batch_grouped(&statements, &proofs, &mut evil_transcripts).is_err());
+
+        // Mismatched lengths fail
+        assert!(TriptychProof::verify_batch_grouped(&statements[1..], &proofs, &mut transcripts).is_err());
     }
 
     #[test]
-    #[cfg(feature = "borsh")]
     #[allow(non_snake_case, non_upper_case_globals)]
-    fn test_borsh() {
+    fn test_prove_verify_batch_detailed() {
         // Generate data
         const n: u32 = 2;
         const m: u32 = 4;
+        const batch: usize = 3; // batch size
         let mut rng = ChaCha12Rng::seed_from_u64(8675309);
-        let (witnesses, statements, mut transcripts) = generate_data(n, m, 1, &mut rng);
+        let (witnesses, statements, mut transcripts) = generate_data(n, m, batch, &mut rng);
 
-        // Generate and verify a proof
-        let proof = TriptychProof::prove_with_rng(&witnesses[0], &statements[0], &mut rng, &mut transcripts[0].clone())
-            .unwrap();
-        assert!(proof.verify(&statements[0], &mut transcripts[0]).is_ok());
+        // Generate the proofs
+        let proofs = izip!(witnesses.iter(), statements.iter(), transcripts.clone().iter_mut())
+            .map(|(w, s, t)| TriptychProof::prove_with_rng(w, s, &mut rng, t).unwrap())
+            .collect::<Vec<TriptychProof>>();
 
-        // Serialize the proof
-        let serialized = borsh::to_vec(&proof).unwrap();
+        // A valid batch has all valid results, and leaves the transcripts as verification would
+        let mut valid_transcripts = transcripts.clone();
+        let results = TriptychProof::verify_batch_detailed(&statements, &proofs, &mut valid_transcripts);
+        assert_eq!(results.len(), batch);
+        assert!(results.iter().all(|result| result.is_ok()));
+        let mut expected_transcripts = transcripts.clone();
+        assert!(TriptychProof::verify_batch(&statements, &proofs, &mut expected_transcripts).is_ok());
+        for (transcript, expected_transcript) in valid_transcripts.iter().zip(expected_transcripts.iter()) {
+            assert_eq!(
+                transcript_fingerprint(transcript),
+                transcript_fingerprint(expected_transcript)
+            );
+        }
 
-        // Deserialize the proof
-        let deserialized: TriptychProof = borsh::from_slice(&serialized).unwrap();
-        assert_eq!(deserialized, proof);
+        // Only the invalid proof has an invalid result
+        transcripts[1] = Transcript::new(b"Evil transcript");
+        let results = TriptychProof::verify_batch_detailed(&statements, &proofs, &mut transcripts.clone());
+        assert!(results[0].is_ok());
+        assert!(results[1].is_err());
+        assert!(results[2].is_ok());
+
+        // Mismatched lengths have all invalid results
+        let results = TriptychProof::verify_batch_detailed(&statements[1..], &proofs, &mut transcripts);
+        assert_eq!(results.len(), batch);
+        assert!(results.iter().all(|result| result.is_err()));
     }
 
     #[test]
     #[allow(non_snake_case, non_upper_case_globals)]
-    fn test_prove_verify_batch() {
+    fn test_prove_verify_batch_progressive() {
         // Generate data
         const n: u32 = 2;
         const m: u32 = 4;
@@ -1197,10 +4607,57 @@ mod test {
             .map(|(w, s, t)| TriptychProof::prove_with_rng(w, s, &mut rng, t).unwrap())
             .collect::<Vec<TriptychProof>>();
 
-        // Verify the batch with and without blame
+        // Each proof in a valid batch is reported as valid, in order
+        let results =
+            TriptychProof::verify_batch_progressive(&statements, &proofs, &mut transcripts.clone()).collect::<Vec<_>>();
+        assert_eq!(results.len(), batch);
+        for (expected, (index, result)) in results.into_iter().enumerate() {
+            assert_eq!(index, expected);
+            assert!(result.is_ok());
+        }
+
+        // Only the invalid proof is reported as invalid
+        transcripts[1] = Transcript::new(b"Evil transcript");
+        let results =
+            TriptychProof::verify_batch_progressive(&statements, &proofs, &mut transcripts.clone()).collect::<Vec<_>>();
+        assert!(results[0].1.is_ok());
+        assert!(results[1].1.is_err());
+        assert!(results[2].1.is_ok());
+
+        // Mismatched lengths yield a single error
+        let results =
+            TriptychProof::verify_batch_progressive(&statements[1..], &proofs, &mut transcripts).collect::<Vec<_>>();
+        assert_eq!(results.len(), 1);
+        assert!(results[0].1.is_err());
+    }
+
+    #[test]
+    #[allow(non_snake_case, non_upper_case_globals)]
+    fn test_prove_verify_batch_distinct_tags() {
+        // Generate data
+        const n: u32 = 2;
+        const m: u32 = 4;
+        const batch: usize = 3; // batch size
+        let mut rng = ChaCha12Rng::seed_from_u64(8675309);
+        let (witnesses, mut statements, mut transcripts) = generate_data(n, m, batch, &mut rng);
+
+        // Generate the proofs
+        let mut proofs = izip!(witnesses.iter(), statements.iter(), transcripts.clone().iter_mut())
+            .map(|(w, s, t)| TriptychProof::prove_with_rng(w, s, &mut rng, t).unwrap())
+            .collect::<Vec<TriptychProof>>();
+
+        // Distinct tags are accepted
+        assert!(TriptychProof::verify_batch_distinct_tags(&statements, &proofs, &mut transcripts.clone()).is_ok());
+
+        // A repeated statement is detected, even though the batch is valid
+        statements[2] = statements[0].clone();
+        proofs[2] = proofs[0].clone();
+        transcripts[2] = transcripts[0].clone();
         assert!(TriptychProof::verify_batch(&statements, &proofs, &mut transcripts.clone()).is_ok());
-        assert!(TriptychProof::verify_batch_with_single_blame(&statements, &proofs, &mut transcripts.clone()).is_ok());
-        assert!(TriptychProof::verify_batch_with_full_blame(&statements, &proofs, &mut transcripts).is_ok());
+        assert!(matches!(
+            TriptychProof::verify_batch_distinct_tags(&statements, &proofs, &mut transcripts.clone()),
+            Err(ProofError::DuplicateLinkingTag { first: 0, second: 2 })
+        ));
     }
 
     #[test]
@@ -1318,6 +4775,60 @@ mod test {
         assert!(proof.verify(&statements[0], &mut evil_transcript).is_err());
     }
 
+    #[test]
+    #[allow(non_snake_case, non_upper_case_globals)]
+    fn test_audit_digest() {
+        // Generate data
+        const n: u32 = 2;
+        const m: u32 = 4;
+        const batch: usize = 2; // batch size
+        let mut rng = ChaCha12Rng::seed_from_u64(8675309);
+        let (witnesses, statements, transcripts) = generate_data(n, m, batch, &mut rng);
+
+        // Generate the proofs
+        let proofs = izip!(witnesses.iter(), statements.iter(), transcripts.clone().iter_mut())
+            .map(|(w, s, t)| TriptychProof::prove_with_rng(w, s, &mut rng, t).unwrap())
+            .collect::<Vec<TriptychProof>>();
+
+        // The digest is deterministic
+        let digest = proofs[0].audit_digest(&statements[0], &transcripts[0]);
+        assert_eq!(digest, proofs[0].audit_digest(&statements[0], &transcripts[0]));
+
+        // Changing any component changes the digest
+        assert_ne!(digest, proofs[1].audit_digest(&statements[0], &transcripts[0]));
+        assert_ne!(digest, proofs[0].audit_digest(&statements[1], &transcripts[0]));
+        assert_ne!(
+            digest,
+            proofs[0].audit_digest(&statements[0], &Transcript::new(b"Evil transcript"))
+        );
+    }
+
+    #[test]
+    #[allow(non_snake_case, non_upper_case_globals)]
+    fn test_transcript_fingerprint() {
+        // Generate data
+        const n: u32 = 2;
+        const m: u32 = 4;
+        let mut rng = ChaCha12Rng::seed_from_u64(8675309);
+        let (witnesses, statements, transcripts) = generate_data(n, m, 1, &mut rng);
+
+        // Fingerprint the transcript before proving
+        let mut transcript = transcripts[0].clone();
+        let fingerprint = transcript_fingerprint(&transcript);
+        let proof = TriptychProof::prove_with_rng(&witnesses[0], &statements[0], &mut rng, &mut transcript).unwrap();
+
+        // A matching verifier transcript has the same fingerprint, and computing it does not affect verification
+        let mut transcript = transcripts[0].clone();
+        assert_eq!(transcript_fingerprint(&transcript), fingerprint);
+        assert!(proof.verify(&statements[0], &mut transcript).is_ok());
+
+        // A mismatched transcript has a different fingerprint
+        let mut evil_transcript = transcripts[0].clone();
+        evil_transcript.append_message(b"evil", b"message");
+        assert_ne!(transcript_fingerprint(&evil_transcript), fingerprint);
+        assert!(proof.verify(&statements[0], &mut evil_transcript).is_err());
+    }
+
     #[test]
     #[allow(non_snake_case, non_upper_case_globals)]
     fn test_evil_input_set() {
@@ -1367,4 +4878,39 @@ mod test {
         // Attempt to verify the proof against the new statement, which should fail
         assert!(proof.verify(&evil_statement, &mut transcripts[0]).is_err());
     }
+
+    #[test]
+    #[allow(non_snake_case, non_upper_case_globals)]
+    fn test_evil_zero_f() {
+        // Generate data
+        const n: u32 = 2;
+        const m: u32 = 4;
+        let mut rng = ChaCha12Rng::seed_from_u64(8675309);
+        let (witnesses, statements, transcripts) = generate_data(n, m, 1, &mut rng);
+
+        // Generate a proof
+        let proof = TriptychProof::prove_with_rng(&witnesses[0], &statements[0], &mut rng, &mut transcripts[0].clone())
+            .unwrap();
+
+        // Set an `f` entry to zero, so the reconstructed `f` matrix contains zero
+        let mut evil = proof.clone();
+        evil.f[0][0] = Scalar::ZERO;
+
+        // Verification rejects the malformed matrix, both alone and in a batch
+        assert!(matches!(
+            evil.verify(&statements[0], &mut transcripts[0].clone()),
+            Err(ProofError::InvalidParameter {
+                reason: "proof `f` matrix contained 0"
+            })
+        ));
+        assert!(matches!(
+            TriptychProof::verify_batch(&[statements[0].clone(), statements[0].clone()], &[proof, evil], &mut [
+                transcripts[0].clone(),
+                transcripts[0].clone()
+            ],),
+            Err(ProofError::InvalidParameter {
+                reason: "proof `f` matrix contained 0"
+            })
+        ));
+    }
 }
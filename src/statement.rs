@@ -1,11 +1,25 @@
 // Copyright (c) 2024, The Tari Project
 // SPDX-License-Identifier: BSD-3-Clause
 
-use alloc::{sync::Arc, vec, vec::Vec};
+use alloc::{
+    collections::{BTreeMap, BTreeSet, VecDeque},
+    sync::Arc,
+    vec,
+    vec::Vec,
+};
+use core::hash::Hash;
 
-use curve25519_dalek::{traits::Identity, RistrettoPoint};
+#[cfg(feature = "borsh")]
+use borsh::{io, BorshDeserialize, BorshSerialize};
+use curve25519_dalek::{ristretto::CompressedRistretto, traits::Identity, RistrettoPoint};
+#[cfg(feature = "serde")]
+use serde::{ser::SerializeStruct, Deserialize, Deserializer, Serialize, Serializer};
 use snafu::prelude::*;
 
+#[cfg(feature = "merkle")]
+use crate::merkle::MerkleTree;
+#[cfg(feature = "borsh")]
+use crate::util::deserialize_point;
 use crate::{domains, Transcript, TriptychParameters};
 
 /// A Triptych input set.
@@ -16,7 +30,10 @@ use crate::{domains, Transcript, TriptychParameters};
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct TriptychInputSet {
     M: Arc<Vec<RistrettoPoint>>,
+    unpadded_size: u32,
     hash: Vec<u8>,
+    #[cfg(feature = "merkle")]
+    tree: Option<Arc<MerkleTree>>,
 }
 
 impl TriptychInputSet {
@@ -26,15 +43,131 @@ impl TriptychInputSet {
         Self::new_internal(M, M.len())
     }
 
+    /// Generate a new [`TriptychInputSet`] from a slice `M` of verification keys, rejecting duplicate keys.
+    ///
+    /// A repeated verification key reduces the effective anonymity set without changing the input set size. If `M`
+    /// contains any verification key more than once, returns a [`StatementError`]; otherwise, this is the same as
+    /// [`TriptychInputSet::new`].
+    ///
+    /// Padding intentionally repeats verification keys, so this check only makes sense for keys that have not been
+    /// padded. For a padded input set, compare [`TriptychInputSet::effective_anonymity_set`] against
+    /// [`TriptychInputSet::get_unpadded_size`] instead.
+    #[allow(non_snake_case)]
+    pub fn new_deduplicated(M: &[RistrettoPoint]) -> Result<Self, StatementError> {
+        let mut seen = BTreeSet::new();
+        for (index, item) in M.iter().enumerate() {
+            if !seen.insert(item.compress().to_bytes()) {
+                return Err(StatementError::DuplicateKey {
+                    index: u32::try_from(index).map_err(|_| StatementError::InvalidParameter {
+                        reason: "input vector was too large",
+                    })?,
+                });
+            }
+        }
+
+        Self::new(M)
+    }
+
+    /// Generate a new [`TriptychInputSet`] from a slice `M` of compressed verification key encodings.
+    ///
+    /// This is useful for callers that store verification keys as byte arrays. If any encoding is not a canonical
+    /// encoding of a group element, or is an encoding of the identity group element, returns a [`StatementError`].
+    #[allow(non_snake_case)]
+    pub fn from_compressed(M: &[[u8; 32]]) -> Result<Self, StatementError> {
+        let M_decompressed = M
+            .iter()
+            .map(|bytes| {
+                CompressedRistretto(*bytes)
+                    .decompress()
+                    .filter(|point| point != &RistrettoPoint::identity())
+                    .ok_or(StatementError::InvalidParameter {
+                        reason: "verification key decompression failed",
+                    })
+            })
+            .collect::<Result<Vec<RistrettoPoint>, StatementError>>()?;
+
+        Self::new(&M_decompressed)
+    }
+
+    /// Generate a new [`TriptychInputSet`] from a slice `M` of compressed verification keys, using a
+    /// [`DecompressionCache`] `cache`.
+    ///
+    /// Any key already in the cache is reused instead of being decompressed again, and any newly-decompressed key is
+    /// added to the cache. This is useful when many input sets share keys.
+    ///
+    /// This performs the same checks as [`TriptychInputSet::from_compressed`]. If any key is not a canonical encoding
+    /// of a group element, or is an encoding of the identity group element, returns a [`StatementError`].
+    #[allow(non_snake_case)]
+    pub fn from_compressed_cached(
+        M: &[CompressedRistretto],
+        cache: &mut DecompressionCache,
+    ) -> Result<Self, StatementError> {
+        let M_decompressed = M
+            .iter()
+            .map(|item| cache.decompress(item))
+            .collect::<Result<Vec<RistrettoPoint>, StatementError>>()?;
+
+        Self::new_internal(&M_decompressed, M_decompressed.len())
+    }
+
+    /// Generate a new [`TriptychInputSet`] from a slice `M` of verification keys, the unpadded size `unpadded_size`,
+    /// and a previously-computed hash `hash`, without recomputing the hash.
+    ///
+    /// This is intended only for loading an input set from trusted storage, where `hash` was obtained from
+    /// [`TriptychInputSet::get_hash`] on an input set with the same keys and unpadded size. The hash is not checked,
+    /// and an incorrect hash will cause proofs to fail to verify or to bind to the wrong data.
+    ///
+    /// If `hash` has the wrong length or `unpadded_size` is larger than the number of keys, returns a
+    /// [`StatementError`].
+    #[allow(non_snake_case)]
+    pub fn new_trusting(M: &[RistrettoPoint], unpadded_size: u32, hash: &[u8]) -> Result<Self, StatementError> {
+        if hash.len() != domains::TRANSCRIPT_HASH_BYTES {
+            return Err(StatementError::InvalidParameter {
+                reason: "hash had an invalid length",
+            });
+        }
+        if unpadded_size as usize > M.len() {
+            return Err(StatementError::InvalidParameter {
+                reason: "unpadded size exceeded the number of keys",
+            });
+        }
+
+        Ok(Self {
+            M: Arc::new(M.to_vec()),
+            unpadded_size,
+            hash: hash.to_vec(),
+            #[cfg(feature = "merkle")]
+            tree: None,
+        })
+    }
+
     /// Generate a new padded [`TriptychInputSet`] from a slice `M` of verification keys and [`TriptychParameters`]
     /// `params`.
     ///
     /// If the verification key vector is shorter than specified by `params`, it will be padded by repeating the last
     /// element. If your use case cannot safely allow this, use [`TriptychInputSet::new`] instead.
+    /// To use a different kind of padding, use [`TriptychInputSet::new_with_padding_strategy`].
     ///
     /// If the verification key vector is empty or longer than specified by `params`, returns a [`StatementError`].
     #[allow(non_snake_case)]
     pub fn new_with_padding(M: &[RistrettoPoint], params: &TriptychParameters) -> Result<Self, StatementError> {
+        Self::new_with_padding_strategy(M, params, PaddingStrategy::RepeatLast)
+    }
+
+    /// Generate a new padded [`TriptychInputSet`] from a slice `M` of verification keys, [`TriptychParameters`]
+    /// `params`, and a [`PaddingStrategy`] `strategy`.
+    ///
+    /// If the verification key vector is shorter than specified by `params`, it will be padded according to
+    /// `strategy`. The unpadded size is bound into the input set hash, so padded and unpadded input sets with the same
+    /// keys are distinguishable.
+    ///
+    /// If the verification key vector is empty or longer than specified by `params`, returns a [`StatementError`].
+    #[allow(non_snake_case)]
+    pub fn new_with_padding_strategy(
+        M: &[RistrettoPoint],
+        params: &TriptychParameters,
+        strategy: PaddingStrategy,
+    ) -> Result<Self, StatementError> {
         // Get the unpadded size
         let unpadded_size = M.len();
 
@@ -45,40 +178,181 @@ impl TriptychInputSet {
             });
         }
 
-        // Get the last element, which also ensures the vector is nonempty
-        let last = M
-            .last()
+        // Get the first and last elements, which also ensures the vector is nonempty
+        let (first, last) = M
+            .first()
+            .zip(M.last())
             .ok_or(StatementError::InvalidParameter { reason: "`M` is empty" })?;
 
-        // Pad the vector with the last element
-        let mut M_padded = M.to_vec();
-        M_padded.resize(params.get_N() as usize, *last);
+        // Pad the vector as requested
+        let M_padded = match strategy {
+            PaddingStrategy::RepeatLast => {
+                let mut M_padded = M.to_vec();
+                M_padded.resize(params.get_N() as usize, *last);
+                M_padded
+            },
+            PaddingStrategy::RepeatFirst => {
+                let mut M_padded = M.to_vec();
+                M_padded.resize(params.get_N() as usize, *first);
+                M_padded
+            },
+            PaddingStrategy::Fixed(padding) => {
+                let mut M_padded = M.to_vec();
+                M_padded.resize(params.get_N() as usize, padding);
+                M_padded
+            },
+        };
 
         Self::new_internal(&M_padded, unpadded_size)
     }
 
+    /// Generate a new [`TriptychInputSet`] from an iterator `M` of verification keys.
+    ///
+    /// The keys are hashed and collected in a single pass, so there is no need to first collect them into an
+    /// intermediate vector. The result is identical to that of [`TriptychInputSet::new`] for the same keys in the same
+    /// order.
+    ///
+    /// The iterator must report its exact length, since this is bound into the hash before any keys. If it produces a
+    /// different number of keys than it reports, returns a [`StatementError`].
+    #[allow(non_snake_case)]
+    pub fn from_key_iter<I>(M: I) -> Result<Self, StatementError>
+    where
+        I: IntoIterator<Item = RistrettoPoint>,
+        I::IntoIter: ExactSizeIterator,
+    {
+        let M = M.into_iter();
+        let size = M.len();
+
+        Self::new_internal_from_iter(M, size, size)
+    }
+
     // Helper function to do the actual generation
     #[allow(non_snake_case)]
     fn new_internal(M: &[RistrettoPoint], unpadded_size: usize) -> Result<Self, StatementError> {
-        // Ensure the verification key vector length doesn't overflow
-        let unpadded_size = u32::try_from(unpadded_size).map_err(|_| StatementError::InvalidParameter {
+        Self::new_internal_from_iter(M.iter().copied(), M.len(), unpadded_size)
+    }
+
+    // Helper function to do the actual generation from an iterator of a given size
+    #[allow(non_snake_case)]
+    fn new_internal_from_iter<I: Iterator<Item = RistrettoPoint>>(
+        M: I,
+        size: usize,
+        unpadded_size: usize,
+    ) -> Result<Self, StatementError> {
+        // Hash and collect keys as we go
+        let mut hasher = InputSetHasher::new_internal(size, unpadded_size)?;
+        for item in M {
+            hasher.append(&item).map_err(|_| StatementError::InvalidParameter {
+                reason: "verification key iterator length did not match its reported length",
+            })?;
+        }
+
+        hasher.finalize().map_err(|_| StatementError::InvalidParameter {
+            reason: "verification key iterator length did not match its reported length",
+        })
+    }
+
+    /// Generate a new [`TriptychInputSet`] from a slice `M` of verification keys, using a Merkle tree for its hash.
+    ///
+    /// Proofs using this input set bind the Merkle root of the verification keys, so it is not interchangeable with an
+    /// input set from [`TriptychInputSet::new`]. Unlike other input sets, its verification keys can be efficiently
+    /// replaced using [`TriptychInputSet::replace_key`], and new verification keys appended using
+    /// [`TriptychInputSet::append_key`]. Both are efficient only while the input set is not shared with a clone.
+    ///
+    /// If the verification key vector is empty, returns a [`StatementError`].
+    #[cfg(feature = "merkle")]
+    #[allow(non_snake_case)]
+    pub fn new_merkle(M: &[RistrettoPoint]) -> Result<Self, StatementError> {
+        let unpadded_size = u32::try_from(M.len()).map_err(|_| StatementError::InvalidParameter {
             reason: "unpadded size overflowed `u32`",
         })?;
+        let tree = MerkleTree::new(M).ok_or(StatementError::InvalidParameter { reason: "`M` is empty" })?;
+
+        Ok(Self {
+            M: Arc::new(M.to_vec()),
+            unpadded_size,
+            hash: Self::merkle_hash(unpadded_size, &tree),
+            tree: Some(Arc::new(tree)),
+        })
+    }
+
+    /// Replace the verification key at `index` with `key`.
+    ///
+    /// This is only supported for input sets generated using [`TriptychInputSet::new_merkle`], and only updates the
+    /// Merkle tree nodes affected by the change. The resulting input set is identical to one generated from the
+    /// updated verification keys.
+    ///
+    /// This takes `O(log N)` time only while this input set does not share its keys. Cloning an input set, including
+    /// into a [`TriptychStatement`], shares its keys and Merkle tree, so the next update copies them both in `O(N)`
+    /// time. To update keys repeatedly, keep a separate input set for updates and build statements from clones of it.
+    ///
+    /// If this input set does not use a Merkle tree, or if `index` is out of range, returns a [`StatementError`].
+    #[cfg(feature = "merkle")]
+    pub fn replace_key(&mut self, index: u32, key: &RistrettoPoint) -> Result<(), StatementError> {
+        let tree = self.tree.as_mut().ok_or(StatementError::InvalidParameter {
+            reason: "input set does not use a Merkle tree",
+        })?;
+        let item = Arc::make_mut(&mut self.M)
+            .get_mut(index as usize)
+            .ok_or(StatementError::InvalidParameter {
+                reason: "index was out of range",
+            })?;
 
+        *item = *key;
+        Arc::make_mut(tree)
+            .update(index as usize, key)
+            .ok_or(StatementError::InvalidParameter {
+                reason: "index was out of range",
+            })?;
+        self.hash = Self::merkle_hash(self.unpadded_size, tree);
+
+        Ok(())
+    }
+
+    /// Append a verification key `key` to the end of this [`TriptychInputSet`].
+    ///
+    /// This is only supported for input sets generated using [`TriptychInputSet::new_merkle`], and only hashes the
+    /// Merkle tree nodes on the path from the new leaf to the root. The resulting input set is identical to one
+    /// generated from the extended verification keys, so an append-only set of keys can be extended over time without
+    /// rehashing it. As with [`TriptychInputSet::replace_key`], an input set that shares its keys with a clone copies
+    /// them first.
+    ///
+    /// If this input set does not use a Merkle tree, or if its size would overflow [`prim@u32`], returns a
+    /// [`StatementError`].
+    #[cfg(feature = "merkle")]
+    pub fn append_key(&mut self, key: &RistrettoPoint) -> Result<(), StatementError> {
+        let tree = self.tree.as_mut().ok_or(StatementError::InvalidParameter {
+            reason: "input set does not use a Merkle tree",
+        })?;
+        let unpadded_size = self
+            .unpadded_size
+            .checked_add(1)
+            .ok_or(StatementError::InvalidParameter {
+                reason: "unpadded size overflowed `u32`",
+            })?;
+
+        Arc::make_mut(tree).push(key).ok_or(StatementError::InvalidParameter {
+            reason: "unpadded size overflowed `u32`",
+        })?;
+        Arc::make_mut(&mut self.M).push(*key);
+        self.unpadded_size = unpadded_size;
+        self.hash = Self::merkle_hash(self.unpadded_size, tree);
+
+        Ok(())
+    }
+
+    // Helper function to compute the hash for an input set using a Merkle tree
+    #[cfg(feature = "merkle")]
+    fn merkle_hash(unpadded_size: u32, tree: &MerkleTree) -> Vec<u8> {
         // Use Merlin for the transcript hash
-        let mut transcript = Transcript::new(domains::TRANSCRIPT_INPUT_SET.as_bytes());
+        let mut transcript = Transcript::new(domains::TRANSCRIPT_MERKLE_INPUT_SET.as_bytes());
         transcript.append_u64(b"version", domains::VERSION);
         transcript.append_message(b"unpadded_size", &unpadded_size.to_le_bytes());
-        for item in M {
-            transcript.append_message(b"M", item.compress().as_bytes());
-        }
+        transcript.append_message(b"root", tree.root());
         let mut hash = vec![0u8; domains::TRANSCRIPT_HASH_BYTES];
         transcript.challenge_bytes(b"hash", &mut hash);
 
-        Ok(Self {
-            M: Arc::new(M.to_vec()),
-            hash,
-        })
+        hash
     }
 
     /// Get the verification keys for this [`TriptychInputSet`].
@@ -86,10 +360,310 @@ impl TriptychInputSet {
         &self.M
     }
 
+    /// Get the number of verification keys for this [`TriptychInputSet`] before any padding was applied.
+    pub fn get_unpadded_size(&self) -> u32 {
+        self.unpadded_size
+    }
+
+    /// Determine if a verification key `key` appears in this [`TriptychInputSet`].
+    ///
+    /// This runs in variable time, since the input set is public.
+    pub fn contains_key(&self, key: &RistrettoPoint) -> bool {
+        self.M.contains(key)
+    }
+
+    /// Find the first index of a verification key `key` in this [`TriptychInputSet`].
+    ///
+    /// If the key does not appear, returns `None`. This runs in variable time, since the input set is public. If you
+    /// need to find an index from a signing key in constant time, use
+    /// [`TriptychWitness::locate`](`crate::TriptychWitness::locate`) instead.
+    pub fn index_of(&self, key: &RistrettoPoint) -> Option<u32> {
+        self.M
+            .iter()
+            .position(|item| item == key)
+            .and_then(|index| u32::try_from(index).ok())
+    }
+
+    /// Check that this [`TriptychInputSet`] is valid for [`TriptychParameters`] `params`.
+    ///
+    /// This performs the same input set checks as [`TriptychStatement::new`], so an input set can be validated before
+    /// a linking tag is available. The verification key vector must have the size `N` specified by `params`, and must
+    /// not contain the identity group element. If either of these conditions is not met, returns a [`StatementError`].
+    pub fn validate_for(&self, params: &TriptychParameters) -> Result<(), StatementError> {
+        if self.M.len() != params.get_N() as usize {
+            return Err(StatementError::InvalidParameter {
+                reason: "input vector length was not `N`",
+            });
+        }
+        if self.M.contains(&RistrettoPoint::identity()) {
+            return Err(StatementError::InvalidParameter {
+                reason: "input vector contained the identity point",
+            });
+        }
+
+        Ok(())
+    }
+
+    /// Get the effective anonymity set size for this [`TriptychInputSet`].
+    ///
+    /// This is the number of distinct verification keys, ignoring any keys added by padding. Padding always follows
+    /// the unpadded verification keys, so only the first [`TriptychInputSet::get_unpadded_size`] keys are counted.
+    pub fn effective_anonymity_set(&self) -> u32 {
+        let distinct = self
+            .M
+            .iter()
+            .take(self.unpadded_size as usize)
+            .map(|item| item.compress().to_bytes())
+            .collect::<BTreeSet<[u8; 32]>>();
+
+        // This can't truncate since the distinct count is bounded by the unpadded size
+        #[allow(clippy::cast_possible_truncation)]
+        let result = distinct.len() as u32;
+
+        result
+    }
+
     /// Get a cryptographic hash representation of this [`TriptychInputSet`], suitable for transcripting.
-    pub(crate) fn get_hash(&self) -> &[u8] {
+    ///
+    /// The hash commits to the unpadded size and to the verification keys in order, or to the Merkle root of the keys
+    /// when the `merkle` feature is used. It is bound into the hash of any [`TriptychStatement`] using this input set,
+    /// so comparing `statement.get_input_set().get_hash()` against a known value is a cheap way to check which input
+    /// set a statement claims before loading the keys themselves. For an input set built with
+    /// [`TriptychInputSet::new_trusting`], this returns the supplied hash without checking it against the keys.
+    pub fn get_hash(&self) -> &[u8] {
         &self.hash
     }
+
+    /// Determine if this [`TriptychInputSet`] uses a Merkle tree, which affects its hash.
+    #[cfg(any(feature = "borsh", feature = "serde"))]
+    fn is_merkle(&self) -> bool {
+        #[cfg(feature = "merkle")]
+        let is_merkle = self.tree.is_some();
+        #[cfg(not(feature = "merkle"))]
+        let is_merkle = false;
+
+        is_merkle
+    }
+
+    /// Rebuild a [`TriptychInputSet`] from its serialized components, recomputing the hash.
+    ///
+    /// Returns `None` if the components do not describe a valid input set.
+    #[cfg(any(feature = "borsh", feature = "serde"))]
+    #[allow(non_snake_case)]
+    fn from_serialized(is_merkle: bool, unpadded_size: u32, M_bytes: Vec<[u8; 32]>) -> Option<Self> {
+        let M = M_bytes
+            .into_iter()
+            .map(|bytes| CompressedRistretto(bytes).decompress())
+            .collect::<Option<Vec<RistrettoPoint>>>()?;
+        if M.contains(&RistrettoPoint::identity()) || unpadded_size as usize > M.len() {
+            return None;
+        }
+
+        if is_merkle {
+            #[cfg(feature = "merkle")]
+            if unpadded_size as usize == M.len() {
+                return Self::new_merkle(&M).ok();
+            }
+
+            return None;
+        }
+
+        Self::new_internal(&M, unpadded_size as usize).ok()
+    }
+}
+
+/// Strategies for padding a [`TriptychInputSet`] to the size specified by [`TriptychParameters`].
+///
+/// Every strategy pads at the end, so the first [`TriptychInputSet::get_unpadded_size`] verification keys are always
+/// those that were provided, at their original indexes.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum PaddingStrategy {
+    /// Pad at the end by repeating the last verification key.
+    RepeatLast,
+    /// Pad at the end by repeating the first verification key.
+    RepeatFirst,
+    /// Pad at the end using a fixed verification key, such as a protocol-agreed dummy key.
+    Fixed(RistrettoPoint),
+}
+
+/// A cache of decompressed verification keys.
+///
+/// Decompressing a verification key is relatively expensive. If the same keys appear in many
+/// [`TriptychInputSets`](`TriptychInputSet`), a cache can be used with [`TriptychInputSet::from_compressed_cached`] so
+/// each key is only decompressed once.
+///
+/// A cache from [`DecompressionCache::new`] grows with every distinct key it sees. If keys come from untrusted input
+/// sets, use [`DecompressionCache::with_capacity`] to bound its memory use.
+#[derive(Clone, Debug, Default)]
+pub struct DecompressionCache {
+    points: BTreeMap<[u8; 32], RistrettoPoint>,
+    order: VecDeque<[u8; 32]>,
+    capacity: Option<usize>,
+}
+
+impl DecompressionCache {
+    /// Generate a new empty [`DecompressionCache`] with no limit on the number of keys.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Generate a new empty [`DecompressionCache`] that holds at most `capacity` keys.
+    ///
+    /// When a key is added to a full cache, the key that was added to it earliest is evicted. A capacity of zero
+    /// disables caching.
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            capacity: Some(capacity),
+            ..Self::default()
+        }
+    }
+
+    /// Decompress a key, using the cache if possible.
+    ///
+    /// If the key is not a canonical encoding of a group element, or is an encoding of the identity group element,
+    /// returns a [`StatementError`].
+    pub fn decompress(&mut self, key: &CompressedRistretto) -> Result<RistrettoPoint, StatementError> {
+        if let Some(point) = self.points.get(key.as_bytes()) {
+            return Ok(*point);
+        }
+
+        let point = key
+            .decompress()
+            .filter(|point| point != &RistrettoPoint::identity())
+            .ok_or(StatementError::InvalidParameter {
+                reason: "verification key decompression failed",
+            })?;
+
+        // Evict the earliest key if the cache is bounded and full
+        if let Some(capacity) = self.capacity {
+            if capacity == 0 {
+                return Ok(point);
+            }
+            if self.points.len() >= capacity {
+                if let Some(earliest) = self.order.pop_front() {
+                    self.points.remove(&earliest);
+                }
+            }
+            self.order.push_back(key.to_bytes());
+        }
+        self.points.insert(key.to_bytes(), point);
+
+        Ok(point)
+    }
+
+    /// Get the number of keys in this [`DecompressionCache`].
+    pub fn len(&self) -> usize {
+        self.points.len()
+    }
+
+    /// Determine if this [`DecompressionCache`] is empty.
+    pub fn is_empty(&self) -> bool {
+        self.points.is_empty()
+    }
+
+    /// Remove all keys from this [`DecompressionCache`].
+    pub fn clear(&mut self) {
+        self.points.clear();
+        self.order.clear();
+    }
+}
+
+/// An incremental hasher for building a [`TriptychInputSet`] one verification key at a time.
+///
+/// This produces the same [`TriptychInputSet`] as [`TriptychInputSet::new`] for the same verification keys appended in
+/// the same order. The hash binds the number of verification keys before any of the keys themselves, so the final size
+/// must be known when the hasher is created; it is not possible to extend an input set that has already been finalized.
+/// To extend an existing input set without rehashing it, use [`TriptychInputSet::new_merkle`] and
+/// [`TriptychInputSet::append_key`] instead (requires the `merkle` feature).
+///
+/// The hasher can be cloned to checkpoint its state, for example to resume hashing after the last verification key
+/// that is known to be final.
+#[allow(non_snake_case)]
+#[derive(Clone)]
+pub struct InputSetHasher {
+    transcript: Transcript,
+    M: Vec<RistrettoPoint>,
+    size: usize,
+    unpadded_size: u32,
+}
+
+impl InputSetHasher {
+    /// Generate a new [`InputSetHasher`] for an input set that will contain exactly `size` verification keys.
+    pub fn new(size: u32) -> Self {
+        let mut transcript = Transcript::new(domains::TRANSCRIPT_INPUT_SET.as_bytes());
+        transcript.append_u64(b"version", domains::VERSION);
+        transcript.append_message(b"unpadded_size", &size.to_le_bytes());
+
+        Self {
+            transcript,
+            // The size is supplied by the caller, so let the vector grow rather than preallocating it
+            M: Vec::new(),
+            size: size as usize,
+            unpadded_size: size,
+        }
+    }
+
+    // Helper function to set up a hasher for a possibly-padded input set
+    fn new_internal(size: usize, unpadded_size: usize) -> Result<Self, StatementError> {
+        // Ensure the verification key vector length doesn't overflow
+        let unpadded_size = u32::try_from(unpadded_size).map_err(|_| StatementError::InvalidParameter {
+            reason: "unpadded size overflowed `u32`",
+        })?;
+
+        let mut hasher = Self::new(unpadded_size);
+        hasher.size = size;
+
+        Ok(hasher)
+    }
+
+    /// Append a verification key `key` to this [`InputSetHasher`].
+    ///
+    /// If all verification keys have already been appended, returns a [`StatementError`].
+    pub fn append(&mut self, key: &RistrettoPoint) -> Result<(), StatementError> {
+        if self.M.len() >= self.size {
+            return Err(StatementError::InvalidParameter {
+                reason: "too many verification keys were appended",
+            });
+        }
+
+        self.transcript.append_message(b"M", key.compress().as_bytes());
+        self.M.push(*key);
+
+        Ok(())
+    }
+
+    /// Get the number of verification keys appended to this [`InputSetHasher`] so far.
+    pub fn len(&self) -> usize {
+        self.M.len()
+    }
+
+    /// Determine if no verification keys have been appended to this [`InputSetHasher`].
+    pub fn is_empty(&self) -> bool {
+        self.M.is_empty()
+    }
+
+    /// Produce a [`TriptychInputSet`] from this [`InputSetHasher`].
+    ///
+    /// If fewer verification keys were appended than the size given when the hasher was created, returns a
+    /// [`StatementError`].
+    pub fn finalize(mut self) -> Result<TriptychInputSet, StatementError> {
+        if self.M.len() != self.size {
+            return Err(StatementError::InvalidParameter {
+                reason: "too few verification keys were appended",
+            });
+        }
+
+        let mut hash = vec![0u8; domains::TRANSCRIPT_HASH_BYTES];
+        self.transcript.challenge_bytes(b"hash", &mut hash);
+
+        Ok(TriptychInputSet {
+            M: Arc::new(self.M),
+            unpadded_size: self.unpadded_size,
+            hash,
+            #[cfg(feature = "merkle")]
+            tree: None,
+        })
+    }
 }
 
 /// A Triptych proof statement.
@@ -114,6 +688,12 @@ pub enum StatementError {
         /// The reason for the parameter error.
         reason: &'static str,
     },
+    /// A verification key appeared more than once.
+    #[snafu(display("A verification key was repeated at index {index}"))]
+    DuplicateKey {
+        /// The index of the repeated verification key.
+        index: u32,
+    },
 }
 
 impl TriptychStatement {
@@ -133,16 +713,7 @@ impl TriptychStatement {
         J: &RistrettoPoint,
     ) -> Result<Self, StatementError> {
         // Check that the input vector is valid against the parameters
-        if input_set.get_keys().len() != params.get_N() as usize {
-            return Err(StatementError::InvalidParameter {
-                reason: "input vector length was not `N`",
-            });
-        }
-        if input_set.get_keys().contains(&RistrettoPoint::identity()) {
-            return Err(StatementError::InvalidParameter {
-                reason: "input vector contained the identity point",
-            });
-        }
+        input_set.validate_for(params)?;
 
         // Use Merlin for the transcript hash
         let mut transcript = Transcript::new(domains::TRANSCRIPT_STATEMENT.as_bytes());
@@ -161,6 +732,81 @@ impl TriptychStatement {
         })
     }
 
+    /// Generate a new [`TriptychStatement`] from compressed encodings.
+    ///
+    /// This is identical to [`TriptychStatement::new`], except that the verification keys `M` and linking tag `J` are
+    /// supplied as compressed encodings, and the [`TriptychInputSet`] is built using
+    /// [`TriptychInputSet::from_compressed`]. If any encoding is invalid, or if any other condition is not met, returns
+    /// a [`StatementError`].
+    #[allow(non_snake_case)]
+    pub fn new_from_compressed(
+        params: &TriptychParameters,
+        M: &[[u8; 32]],
+        J: &[u8; 32],
+    ) -> Result<Self, StatementError> {
+        let input_set = TriptychInputSet::from_compressed(M)?;
+        let J = CompressedRistretto(*J)
+            .decompress()
+            .ok_or(StatementError::InvalidParameter {
+                reason: "linking tag decompression failed",
+            })?;
+
+        Self::new(params, &input_set, &J)
+    }
+
+    /// Generate a new [`TriptychStatement`] whose [`TriptychParameters`] do not exceed a maximum size.
+    ///
+    /// This is identical to [`TriptychStatement::new`], except that it additionally checks that the size `N` specified
+    /// by the [`TriptychParameters`] `params` does not exceed `max_N`. This can be used to enforce a policy that bounds
+    /// verification cost. If any condition is not met, returns a [`StatementError`].
+    #[allow(non_snake_case)]
+    pub fn new_with_max_size(
+        params: &TriptychParameters,
+        input_set: &TriptychInputSet,
+        J: &RistrettoPoint,
+        max_N: u32,
+    ) -> Result<Self, StatementError> {
+        if params.get_N() > max_N {
+            return Err(StatementError::InvalidParameter {
+                reason: "`N` exceeded the maximum size",
+            });
+        }
+
+        Self::new(params, input_set, J)
+    }
+
+    /// Generate a new [`TriptychStatement`] from its components and a previously-computed hash `hash`, without
+    /// recomputing the hash.
+    ///
+    /// This is intended only for loading a statement from trusted storage, where `hash` was obtained from
+    /// [`TriptychStatement::get_hash`] on a statement with the same components. The hash is not checked, and an
+    /// incorrect hash will cause proofs to fail to verify or to bind to the wrong data.
+    ///
+    /// The [`TriptychInputSet`] `input_set` is checked against the [`TriptychParameters`] `params` exactly as in
+    /// [`TriptychStatement::new`]. If this check fails or `hash` has the wrong length, returns a [`StatementError`].
+    #[allow(non_snake_case)]
+    pub fn new_trusting(
+        params: &TriptychParameters,
+        input_set: &TriptychInputSet,
+        J: &RistrettoPoint,
+        hash: &[u8],
+    ) -> Result<Self, StatementError> {
+        input_set.validate_for(params)?;
+
+        if hash.len() != domains::TRANSCRIPT_HASH_BYTES {
+            return Err(StatementError::InvalidParameter {
+                reason: "hash had an invalid length",
+            });
+        }
+
+        Ok(Self {
+            params: params.clone(),
+            input_set: input_set.clone(),
+            J: *J,
+            hash: hash.to_vec(),
+        })
+    }
+
     /// Get the parameters for this [`TriptychStatement`].
     pub fn get_params(&self) -> &TriptychParameters {
         &self.params
@@ -178,20 +824,174 @@ impl TriptychStatement {
     }
 
     /// Get a cryptographic hash representation of this [`TriptychStatement`], suitable for transcripting.
-    pub(crate) fn get_hash(&self) -> &[u8] {
+    ///
+    /// The hash is computed once when the statement is constructed, from the already-computed hashes of its
+    /// [`TriptychParameters`] and [`TriptychInputSet`] along with its linking tag. Proof transcripts bind only this
+    /// hash, so statements that share parameters and an input set do not rehash either of them during verification.
+    pub fn get_hash(&self) -> &[u8] {
         &self.hash
     }
 }
 
+impl Hash for TriptychInputSet {
+    /// Hash only the precomputed input set hash, which binds all of its data.
+    ///
+    /// This is consistent with equality, and relies on the collision resistance of the precomputed hash.
+    fn hash<H: core::hash::Hasher>(&self, state: &mut H) {
+        self.hash.hash(state);
+    }
+}
+
+impl Hash for TriptychStatement {
+    /// Hash only the precomputed statement hash, which binds all of its data.
+    ///
+    /// This is consistent with equality, and relies on the collision resistance of the precomputed hash.
+    fn hash<H: core::hash::Hasher>(&self, state: &mut H) {
+        self.hash.hash(state);
+    }
+}
+
+#[cfg(feature = "borsh")]
+impl BorshSerialize for TriptychInputSet {
+    fn serialize<W: io::Write>(&self, writer: &mut W) -> io::Result<()> {
+        BorshSerialize::serialize(&self.is_merkle(), writer)?;
+        BorshSerialize::serialize(&self.unpadded_size, writer)?;
+        BorshSerialize::serialize(
+            &self
+                .M
+                .iter()
+                .map(|item| item.compress().to_bytes())
+                .collect::<Vec<[u8; 32]>>(),
+            writer,
+        )
+    }
+}
+
+#[cfg(feature = "borsh")]
+impl BorshDeserialize for TriptychInputSet {
+    #[allow(non_snake_case)]
+    fn deserialize_reader<R: io::Read>(reader: &mut R) -> io::Result<Self> {
+        let is_merkle: bool = BorshDeserialize::deserialize_reader(reader)?;
+        let unpadded_size: u32 = BorshDeserialize::deserialize_reader(reader)?;
+        let M_bytes: Vec<[u8; 32]> = BorshDeserialize::deserialize_reader(reader)?;
+
+        Self::from_serialized(is_merkle, unpadded_size, M_bytes)
+            .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, "Invalid Triptych input set"))
+    }
+}
+
+#[cfg(feature = "serde")]
+impl Serialize for TriptychInputSet {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut state = serializer.serialize_struct("TriptychInputSet", 3)?;
+        state.serialize_field("merkle", &self.is_merkle())?;
+        state.serialize_field("unpadded_size", &self.unpadded_size)?;
+        state.serialize_field(
+            "M",
+            &self
+                .M
+                .iter()
+                .map(|item| item.compress().to_bytes())
+                .collect::<Vec<[u8; 32]>>(),
+        )?;
+        state.end()
+    }
+}
+
+/// The serialized form of a [`TriptychInputSet`], which omits the hash.
+#[cfg(feature = "serde")]
+#[allow(non_snake_case)]
+#[derive(Deserialize)]
+#[serde(rename = "TriptychInputSet")]
+struct SerializedInputSet {
+    merkle: bool,
+    unpadded_size: u32,
+    M: Vec<[u8; 32]>,
+}
+
+#[cfg(feature = "serde")]
+impl<'de> Deserialize<'de> for TriptychInputSet {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let serialized = SerializedInputSet::deserialize(deserializer)?;
+
+        Self::from_serialized(serialized.merkle, serialized.unpadded_size, serialized.M)
+            .ok_or_else(|| serde::de::Error::custom("Invalid Triptych input set"))
+    }
+}
+
+#[cfg(feature = "borsh")]
+impl BorshSerialize for TriptychStatement {
+    fn serialize<W: io::Write>(&self, writer: &mut W) -> io::Result<()> {
+        BorshSerialize::serialize(&self.params, writer)?;
+        BorshSerialize::serialize(&self.input_set, writer)?;
+        BorshSerialize::serialize(self.J.compress().as_bytes(), writer)
+    }
+}
+
+#[cfg(feature = "borsh")]
+impl BorshDeserialize for TriptychStatement {
+    #[allow(non_snake_case)]
+    fn deserialize_reader<R: io::Read>(reader: &mut R) -> io::Result<Self> {
+        let params: TriptychParameters = BorshDeserialize::deserialize_reader(reader)?;
+        let input_set: TriptychInputSet = BorshDeserialize::deserialize_reader(reader)?;
+        let J = deserialize_point(reader)?;
+
+        // The hash is recomputed, not read
+        TriptychStatement::new(&params, &input_set, &J)
+            .map_err(|_| io::Error::new(io::ErrorKind::InvalidInput, "Invalid Triptych statement"))
+    }
+}
+
+#[cfg(feature = "serde")]
+impl Serialize for TriptychStatement {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut state = serializer.serialize_struct("TriptychStatement", 3)?;
+        state.serialize_field("params", &self.params)?;
+        state.serialize_field("input_set", &self.input_set)?;
+        state.serialize_field("J", self.J.compress().as_bytes())?;
+        state.end()
+    }
+}
+
+/// The serialized form of a [`TriptychStatement`], which omits the hash.
+#[cfg(feature = "serde")]
+#[allow(non_snake_case)]
+#[derive(Deserialize)]
+#[serde(rename = "TriptychStatement")]
+struct SerializedStatement {
+    params: TriptychParameters,
+    input_set: TriptychInputSet,
+    J: [u8; 32],
+}
+
+#[cfg(feature = "serde")]
+impl<'de> Deserialize<'de> for TriptychStatement {
+    #[allow(non_snake_case)]
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let serialized = SerializedStatement::deserialize(deserializer)?;
+
+        let error = || serde::de::Error::custom("Invalid Triptych statement");
+        let J = CompressedRistretto(serialized.J).decompress().ok_or_else(error)?;
+
+        // The hash is recomputed, not read
+        TriptychStatement::new(&serialized.params, &serialized.input_set, &J).map_err(|_| error())
+    }
+}
+
 #[cfg(test)]
 mod test {
     use alloc::{borrow::ToOwned, vec::Vec};
 
-    use curve25519_dalek::RistrettoPoint;
+    use curve25519_dalek::{ristretto::CompressedRistretto, traits::Identity, RistrettoPoint};
     use rand_chacha::ChaCha12Rng;
     use rand_core::SeedableRng;
 
-    use crate::{TriptychInputSet, TriptychParameters};
+    use crate::{
+        statement::{DecompressionCache, InputSetHasher, PaddingStrategy, StatementError},
+        TriptychInputSet,
+        TriptychParameters,
+        TriptychStatement,
+    };
 
     // Helper function to generate random vectors
     fn random_vector(size: usize) -> Vec<RistrettoPoint> {
@@ -222,6 +1022,12 @@ mod test {
             TriptychInputSet::new_with_padding(&M, &params).unwrap(),
             TriptychInputSet::new(&M).unwrap()
         );
+        assert_eq!(
+            TriptychInputSet::new_with_padding(&M, &params)
+                .unwrap()
+                .get_unpadded_size(),
+            params.get_N()
+        );
 
         // Vector is padded
         let M = random_vector(N - 1);
@@ -231,9 +1037,572 @@ mod test {
             TriptychInputSet::new_with_padding(&M, &params).unwrap().get_keys(),
             TriptychInputSet::new(&M_padded).unwrap().get_keys()
         );
+        assert_eq!(
+            TriptychInputSet::new_with_padding(&M, &params)
+                .unwrap()
+                .get_unpadded_size(),
+            params.get_N() - 1
+        );
+        assert_eq!(
+            TriptychInputSet::new(&M_padded).unwrap().get_unpadded_size(),
+            params.get_N()
+        );
         assert_ne!(
             TriptychInputSet::new_with_padding(&M, &params).unwrap().get_hash(),
             TriptychInputSet::new(&M_padded).unwrap().get_hash()
         )
     }
+
+    #[test]
+    #[allow(non_snake_case)]
+    fn test_padding_strategy() {
+        // Generate parameters
+        let params = TriptychParameters::new(2, 4).unwrap();
+        let N = params.get_N() as usize;
+        let M = random_vector(N - 2);
+        let dummy = random_vector(N - 1)[N - 2];
+
+        // Repeating the last key is the default
+        let input_set = TriptychInputSet::new_with_padding_strategy(&M, &params, PaddingStrategy::RepeatLast).unwrap();
+        assert_eq!(input_set, TriptychInputSet::new_with_padding(&M, &params).unwrap());
+        assert_eq!(&input_set.get_keys()[..N - 2], M.as_slice());
+        assert_eq!(input_set.get_keys()[N - 2..], [M[N - 3]; 2]);
+
+        // Repeating the first key pads at the end
+        let input_set = TriptychInputSet::new_with_padding_strategy(&M, &params, PaddingStrategy::RepeatFirst).unwrap();
+        assert_eq!(&input_set.get_keys()[..N - 2], M.as_slice());
+        assert_eq!(input_set.get_keys()[N - 2..], [M[0]; 2]);
+
+        // A fixed key pads at the end
+        let input_set =
+            TriptychInputSet::new_with_padding_strategy(&M, &params, PaddingStrategy::Fixed(dummy)).unwrap();
+        assert_eq!(&input_set.get_keys()[..N - 2], M.as_slice());
+        assert_eq!(input_set.get_keys()[N - 2..], [dummy; 2]);
+
+        // The unpadded size still distinguishes padded and unpadded input sets
+        assert_ne!(
+            input_set.get_hash(),
+            TriptychInputSet::new(input_set.get_keys()).unwrap().get_hash()
+        );
+
+        // Empty and overlong vectors fail for any strategy
+        for strategy in [
+            PaddingStrategy::RepeatLast,
+            PaddingStrategy::RepeatFirst,
+            PaddingStrategy::Fixed(dummy),
+        ] {
+            assert!(TriptychInputSet::new_with_padding_strategy(&[], &params, strategy).is_err());
+            assert!(TriptychInputSet::new_with_padding_strategy(&random_vector(N + 1), &params, strategy).is_err());
+        }
+    }
+
+    #[test]
+    #[allow(non_snake_case)]
+    fn test_from_key_iter() {
+        // An iterator that reports an incorrect length
+        struct LyingIterator(Vec<RistrettoPoint>);
+        impl Iterator for LyingIterator {
+            type Item = RistrettoPoint;
+
+            fn next(&mut self) -> Option<Self::Item> {
+                self.0.pop()
+            }
+        }
+        impl ExactSizeIterator for LyingIterator {
+            fn len(&self) -> usize {
+                self.0.len() + 1
+            }
+        }
+
+        let M = random_vector(8);
+
+        // The input set should match the one generated from a slice
+        let input_set = TriptychInputSet::from_key_iter(M.iter().copied()).unwrap();
+        assert_eq!(input_set.get_hash(), TriptychInputSet::new(&M).unwrap().get_hash());
+        assert_eq!(input_set, TriptychInputSet::new(&M).unwrap());
+
+        // An iterator with an incorrect length fails
+        assert!(TriptychInputSet::from_key_iter(LyingIterator(M)).is_err());
+    }
+
+    #[test]
+    #[allow(non_snake_case)]
+    fn test_input_set_hasher() {
+        let M = random_vector(8);
+        let (last, M_prefix) = M.split_last().unwrap();
+
+        // Incremental hashing matches one-shot hashing, including from a checkpoint
+        let mut hasher = InputSetHasher::new(8);
+        for item in M_prefix {
+            hasher.append(item).unwrap();
+        }
+        assert_eq!(hasher.len(), 7);
+        let checkpoint = hasher.clone();
+        hasher.append(last).unwrap();
+        assert_eq!(hasher.finalize().unwrap(), TriptychInputSet::new(&M).unwrap());
+
+        // A checkpoint can be resumed with a different final key
+        let mut hasher = checkpoint.clone();
+        hasher.append(&M[0]).unwrap();
+        let mut M_other = M.clone();
+        M_other[7] = M[0];
+        assert_eq!(hasher.finalize().unwrap(), TriptychInputSet::new(&M_other).unwrap());
+
+        // Too few keys fail
+        assert!(checkpoint.finalize().is_err());
+
+        // Too many keys fail
+        let mut hasher = InputSetHasher::new(1);
+        assert!(hasher.is_empty());
+        hasher.append(last).unwrap();
+        assert!(hasher.append(last).is_err());
+
+        // A large size does not allocate up front
+        let mut hasher = InputSetHasher::new(u32::MAX);
+        hasher.append(last).unwrap();
+        assert!(hasher.finalize().is_err());
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    #[allow(non_snake_case)]
+    fn test_hash() {
+        extern crate std;
+        use std::collections::{HashMap, HashSet};
+
+        let params = TriptychParameters::new(2, 3).unwrap();
+        let input_set = TriptychInputSet::new(&random_vector(params.get_N() as usize)).unwrap();
+        let J = random_vector(2);
+
+        // Statements can be used as keys
+        let statement = TriptychStatement::new(&params, &input_set, &J[0]).unwrap();
+        let other = TriptychStatement::new(&params, &input_set, &J[1]).unwrap();
+        let mut verified = HashMap::new();
+        verified.insert(statement.clone(), true);
+        assert_eq!(
+            verified.get(&TriptychStatement::new(&params, &input_set, &J[0]).unwrap()),
+            Some(&true)
+        );
+        assert_eq!(verified.get(&other), None);
+
+        // Equal input sets and parameters collapse, and distinct ones do not
+        let input_sets = HashSet::from([
+            input_set.clone(),
+            input_set.clone(),
+            TriptychInputSet::new(&random_vector(params.get_N() as usize + 1)[1..]).unwrap(),
+        ]);
+        assert_eq!(input_sets.len(), 2);
+        let parameters = HashSet::from([params.clone(), params, TriptychParameters::new(2, 4).unwrap()]);
+        assert_eq!(parameters.len(), 2);
+    }
+
+    #[test]
+    #[allow(non_snake_case)]
+    fn test_from_compressed() {
+        let params = TriptychParameters::new(2, 3).unwrap();
+        let M = random_vector(params.get_N() as usize);
+        let M_bytes = M
+            .iter()
+            .map(|item| item.compress().to_bytes())
+            .collect::<Vec<[u8; 32]>>();
+        let J = random_vector(1)[0];
+
+        // Valid encodings produce the same input set and statement
+        let input_set = TriptychInputSet::new(&M).unwrap();
+        assert_eq!(TriptychInputSet::from_compressed(&M_bytes).unwrap(), input_set);
+        assert!(
+            TriptychStatement::new_from_compressed(&params, &M_bytes, &J.compress().to_bytes()).unwrap() ==
+                TriptychStatement::new(&params, &input_set, &J).unwrap()
+        );
+
+        // The field modulus is a non-canonical encoding of zero
+        let mut modulus = [0xFF; 32];
+        modulus[0] = 0xED;
+        modulus[31] = 0x7F;
+
+        // Invalid, non-canonical, and identity verification key encodings fail
+        for evil in [[0xFF; 32], modulus, RistrettoPoint::identity().compress().to_bytes()] {
+            let mut M_evil = M_bytes.clone();
+            M_evil[0] = evil;
+            assert!(TriptychInputSet::from_compressed(&M_evil).is_err());
+            assert!(TriptychStatement::new_from_compressed(&params, &M_evil, &J.compress().to_bytes()).is_err());
+        }
+
+        // An invalid linking tag encoding fails
+        assert!(TriptychStatement::new_from_compressed(&params, &M_bytes, &modulus).is_err());
+    }
+
+    #[test]
+    #[allow(non_snake_case)]
+    fn test_from_compressed_cached() {
+        let M = random_vector(8);
+        let M_compressed = M
+            .iter()
+            .map(|item| item.compress())
+            .collect::<Vec<CompressedRistretto>>();
+
+        // Decompression should populate the cache and produce the same input set
+        let mut cache = DecompressionCache::new();
+        assert_eq!(
+            TriptychInputSet::from_compressed_cached(&M_compressed, &mut cache).unwrap(),
+            TriptychInputSet::new(&M).unwrap()
+        );
+        assert_eq!(cache.len(), M.len());
+
+        // Reusing the cache for an overlapping input set only adds the new key
+        let mut M_overlap = M.clone();
+        M_overlap[0] = random_vector(9)[8];
+        let M_overlap_compressed = M_overlap
+            .iter()
+            .map(|item| item.compress())
+            .collect::<Vec<CompressedRistretto>>();
+        assert_eq!(
+            TriptychInputSet::from_compressed_cached(&M_overlap_compressed, &mut cache).unwrap(),
+            TriptychInputSet::new(&M_overlap).unwrap()
+        );
+        assert_eq!(cache.len(), M.len() + 1);
+
+        // An invalid encoding fails
+        let mut M_evil = M_compressed.clone();
+        M_evil[0] = CompressedRistretto([0xFF; 32]);
+        assert!(TriptychInputSet::from_compressed_cached(&M_evil, &mut cache).is_err());
+
+        // The identity fails, as it does without a cache
+        M_evil[0] = RistrettoPoint::identity().compress();
+        assert!(TriptychInputSet::from_compressed_cached(&M_evil, &mut cache).is_err());
+        assert_eq!(cache.len(), M.len() + 1);
+    }
+
+    #[test]
+    #[allow(non_snake_case)]
+    fn test_decompression_cache_capacity() {
+        let M = random_vector(8);
+        let M_compressed = M
+            .iter()
+            .map(|item| item.compress())
+            .collect::<Vec<CompressedRistretto>>();
+
+        // A bounded cache never exceeds its capacity, and still produces the same input set
+        let mut cache = DecompressionCache::with_capacity(3);
+        assert_eq!(
+            TriptychInputSet::from_compressed_cached(&M_compressed, &mut cache).unwrap(),
+            TriptychInputSet::new(&M).unwrap()
+        );
+        assert_eq!(cache.len(), 3);
+
+        // The earliest keys are evicted first
+        assert_eq!(cache.decompress(&M_compressed[7]).unwrap(), M[7]);
+        assert_eq!(cache.len(), 3);
+        assert_eq!(cache.decompress(&M_compressed[0]).unwrap(), M[0]);
+        assert_eq!(cache.len(), 3);
+
+        // Clearing the cache resets it
+        cache.clear();
+        assert!(cache.is_empty());
+        assert!(TriptychInputSet::from_compressed_cached(&M_compressed, &mut cache).is_ok());
+        assert_eq!(cache.len(), 3);
+
+        // A zero capacity disables caching
+        let mut cache = DecompressionCache::with_capacity(0);
+        assert!(TriptychInputSet::from_compressed_cached(&M_compressed, &mut cache).is_ok());
+        assert!(cache.is_empty());
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    #[allow(non_snake_case)]
+    fn test_serde() {
+        // Generate parameters
+        let params = TriptychParameters::new(2, 4).unwrap();
+        let N = params.get_N();
+
+        // Round trip unpadded and padded input sets
+        let M = random_vector(N as usize);
+        let input_set = TriptychInputSet::new(&M).unwrap();
+        let padded_input_set = TriptychInputSet::new_with_padding(&M[2..], &params).unwrap();
+        for item in [&input_set, &padded_input_set] {
+            let serialized = serde_json::to_string(item).unwrap();
+            let deserialized: TriptychInputSet = serde_json::from_str(&serialized).unwrap();
+            assert_eq!(&deserialized, item);
+        }
+
+        // Round trip a statement
+        let J = random_vector(1)[0];
+        let statement = TriptychStatement::new(&params, &input_set, &J).unwrap();
+        let serialized = serde_json::to_string(&statement).unwrap();
+        let deserialized: TriptychStatement = serde_json::from_str(&serialized).unwrap();
+        assert!(deserialized == statement);
+
+        // Round trip a Merkle input set
+        #[cfg(feature = "merkle")]
+        {
+            let merkle_input_set = TriptychInputSet::new_merkle(&M).unwrap();
+            let serialized = serde_json::to_string(&merkle_input_set).unwrap();
+            let deserialized: TriptychInputSet = serde_json::from_str(&serialized).unwrap();
+            assert_eq!(deserialized, merkle_input_set);
+        }
+
+        // An input set containing the identity is rejected
+        let mut evil_M = M.clone();
+        evil_M[0] = RistrettoPoint::identity();
+        let serialized = serde_json::to_string(&TriptychInputSet::new(&evil_M).unwrap()).unwrap();
+        assert!(serde_json::from_str::<TriptychInputSet>(&serialized).is_err());
+
+        // An input set that is too small for the parameters produces an invalid statement
+        let small_input_set = TriptychInputSet::new(&M[1..]).unwrap();
+        let mut evil_statement = serde_json::to_value(&statement).unwrap();
+        evil_statement["input_set"] = serde_json::to_value(&small_input_set).unwrap();
+        assert!(serde_json::from_value::<TriptychStatement>(evil_statement).is_err());
+
+        // A non-canonical linking tag encoding is rejected
+        let mut evil_statement = serde_json::to_value(&statement).unwrap();
+        evil_statement["J"] = serde_json::to_value([u8::MAX; 32]).unwrap();
+        assert!(serde_json::from_value::<TriptychStatement>(evil_statement).is_err());
+    }
+
+    #[test]
+    #[cfg(feature = "borsh")]
+    #[allow(non_snake_case)]
+    fn test_borsh() {
+        // Generate parameters
+        let params = TriptychParameters::new(2, 4).unwrap();
+        let N = params.get_N();
+
+        // Round trip unpadded and padded input sets
+        let M = random_vector(N as usize);
+        let input_set = TriptychInputSet::new(&M).unwrap();
+        let padded_input_set = TriptychInputSet::new_with_padding(&M[2..], &params).unwrap();
+        for item in [&input_set, &padded_input_set] {
+            let serialized = borsh::to_vec(item).unwrap();
+            let deserialized: TriptychInputSet = borsh::from_slice(&serialized).unwrap();
+            assert_eq!(&deserialized, item);
+        }
+
+        // Round trip a statement
+        let J = random_vector(1)[0];
+        let statement = TriptychStatement::new(&params, &input_set, &J).unwrap();
+        let serialized = borsh::to_vec(&statement).unwrap();
+        let deserialized: TriptychStatement = borsh::from_slice(&serialized).unwrap();
+        assert!(deserialized == statement);
+
+        // Round trip a Merkle input set
+        #[cfg(feature = "merkle")]
+        {
+            let merkle_input_set = TriptychInputSet::new_merkle(&M).unwrap();
+            let serialized = borsh::to_vec(&merkle_input_set).unwrap();
+            let deserialized: TriptychInputSet = borsh::from_slice(&serialized).unwrap();
+            assert_eq!(deserialized, merkle_input_set);
+        }
+
+        // An input set containing the identity is rejected
+        let mut evil_M = M.clone();
+        evil_M[0] = RistrettoPoint::identity();
+        let serialized = borsh::to_vec(&TriptychInputSet::new(&evil_M).unwrap()).unwrap();
+        assert!(borsh::from_slice::<TriptychInputSet>(&serialized).is_err());
+
+        // An input set that is too small for the parameters produces an invalid statement
+        let small_input_set = TriptychInputSet::new(&M[1..]).unwrap();
+        let mut serialized = borsh::to_vec(&params).unwrap();
+        serialized.extend(borsh::to_vec(&small_input_set).unwrap());
+        serialized.extend(J.compress().as_bytes());
+        assert!(borsh::from_slice::<TriptychStatement>(&serialized).is_err());
+    }
+
+    #[test]
+    #[allow(non_snake_case)]
+    fn test_validate_for() {
+        // Generate parameters
+        let params = TriptychParameters::new(2, 4).unwrap();
+        let N = params.get_N();
+
+        // A valid input set
+        let mut M = random_vector(N as usize);
+        assert!(TriptychInputSet::new(&M).unwrap().validate_for(&params).is_ok());
+
+        // An input set of the wrong size
+        assert!(TriptychInputSet::new(&M[1..]).unwrap().validate_for(&params).is_err());
+
+        // An input set containing the identity
+        M[0] = RistrettoPoint::identity();
+        assert!(TriptychInputSet::new(&M).unwrap().validate_for(&params).is_err());
+    }
+
+    #[test]
+    #[allow(non_snake_case)]
+    fn test_index_of() {
+        // Generate parameters
+        let params = TriptychParameters::new(2, 4).unwrap();
+        let N = params.get_N() as usize;
+
+        // Keys that are present are found
+        let mut M = random_vector(N + 1);
+        let absent = M.pop().unwrap();
+        let input_set = TriptychInputSet::new(&M).unwrap();
+        assert!(input_set.contains_key(&M[3]));
+        assert_eq!(input_set.index_of(&M[3]), Some(3));
+
+        // Keys that are absent are not found
+        assert!(!input_set.contains_key(&absent));
+        assert_eq!(input_set.index_of(&absent), None);
+
+        // The first index of a duplicate key is found
+        M[5] = M[2];
+        let input_set = TriptychInputSet::new(&M).unwrap();
+        assert!(input_set.contains_key(&M[5]));
+        assert_eq!(input_set.index_of(&M[5]), Some(2));
+    }
+
+    #[test]
+    #[allow(non_snake_case)]
+    fn test_new_deduplicated() {
+        // Generate parameters
+        let params = TriptychParameters::new(2, 4).unwrap();
+        let N = params.get_N() as usize;
+
+        // Distinct keys are accepted, and match the permissive constructor
+        let mut M = random_vector(N);
+        assert_eq!(
+            TriptychInputSet::new_deduplicated(&M).unwrap(),
+            TriptychInputSet::new(&M).unwrap()
+        );
+
+        // A repeated key is rejected at its second index, but the permissive constructor still accepts it
+        M[5] = M[2];
+        assert!(matches!(
+            TriptychInputSet::new_deduplicated(&M),
+            Err(StatementError::DuplicateKey { index: 5 })
+        ));
+        assert!(TriptychInputSet::new(&M).is_ok());
+    }
+
+    #[test]
+    #[allow(non_snake_case)]
+    fn test_effective_anonymity_set() {
+        // Generate parameters
+        let params = TriptychParameters::new(2, 4).unwrap();
+        let N = params.get_N();
+
+        // All keys are distinct
+        let M = random_vector(N as usize);
+        assert_eq!(TriptychInputSet::new(&M).unwrap().effective_anonymity_set(), N);
+
+        // Padding keys are not counted, for any strategy
+        let M = random_vector(N as usize - 3);
+        let dummy = random_vector(1)[0];
+        for strategy in [
+            PaddingStrategy::RepeatLast,
+            PaddingStrategy::RepeatFirst,
+            PaddingStrategy::Fixed(dummy),
+        ] {
+            assert_eq!(
+                TriptychInputSet::new_with_padding_strategy(&M, &params, strategy)
+                    .unwrap()
+                    .effective_anonymity_set(),
+                N - 3
+            );
+        }
+
+        // Duplicate keys are not counted
+        let mut M = random_vector(N as usize);
+        M[1] = M[0];
+        assert_eq!(TriptychInputSet::new(&M).unwrap().effective_anonymity_set(), N - 1);
+    }
+
+    #[test]
+    #[allow(non_snake_case)]
+    fn test_new_with_max_size() {
+        // Generate parameters and statement components
+        let params = TriptychParameters::new(2, 4).unwrap();
+        let M = random_vector(params.get_N() as usize);
+        let input_set = TriptychInputSet::new(&M).unwrap();
+        let J = random_vector(1)[0];
+
+        // Sizes up to the maximum are accepted, and match unbounded statements
+        let statement = TriptychStatement::new_with_max_size(&params, &input_set, &J, params.get_N()).unwrap();
+        assert!(statement == TriptychStatement::new(&params, &input_set, &J).unwrap());
+
+        // Sizes exceeding the maximum are rejected
+        assert!(TriptychStatement::new_with_max_size(&params, &input_set, &J, params.get_N() - 1).is_err());
+    }
+
+    #[test]
+    #[allow(non_snake_case)]
+    fn test_new_trusting() {
+        // Generate parameters and a statement
+        let params = TriptychParameters::new(2, 4).unwrap();
+        let M = random_vector(params.get_N() as usize - 1);
+        let input_set = TriptychInputSet::new_with_padding(&M, &params).unwrap();
+        let J = random_vector(1)[0];
+        let statement = TriptychStatement::new(&params, &input_set, &J).unwrap();
+
+        // Reconstruct the input set and statement from their components and stored hashes
+        let input_set_trusted =
+            TriptychInputSet::new_trusting(input_set.get_keys(), params.get_N() - 1, input_set.get_hash()).unwrap();
+        assert_eq!(input_set_trusted, input_set);
+        let statement_trusted =
+            TriptychStatement::new_trusting(&params, &input_set_trusted, &J, statement.get_hash()).unwrap();
+        assert!(statement_trusted == statement);
+
+        // Hashes must have the correct length
+        assert!(TriptychInputSet::new_trusting(input_set.get_keys(), params.get_N() - 1, &[0u8; 31]).is_err());
+        assert!(TriptychStatement::new_trusting(&params, &input_set, &J, &[0u8; 31]).is_err());
+    }
+
+    #[test]
+    #[cfg(feature = "merkle")]
+    #[allow(non_snake_case)]
+    fn test_merkle() {
+        let M = random_vector(8);
+        let mut input_set = TriptychInputSet::new_merkle(&M).unwrap();
+
+        // The hash differs from a standard input set
+        assert_ne!(input_set.get_hash(), TriptychInputSet::new(&M).unwrap().get_hash());
+
+        // Replacing a key matches rebuilding the input set
+        let mut M_replaced = M.clone();
+        M_replaced[3] = random_vector(9)[8];
+        input_set.replace_key(3, &M_replaced[3]).unwrap();
+        assert_eq!(input_set, TriptychInputSet::new_merkle(&M_replaced).unwrap());
+
+        // Out-of-range replacement fails
+        assert!(input_set.replace_key(8, &M[0]).is_err());
+
+        // Standard input sets can't replace keys
+        assert!(TriptychInputSet::new(&M).unwrap().replace_key(0, &M[1]).is_err());
+    }
+
+    #[test]
+    #[cfg(feature = "merkle")]
+    #[allow(non_snake_case)]
+    fn test_merkle_append() {
+        let M = random_vector(13);
+        let mut input_set = TriptychInputSet::new_merkle(&M[..1]).unwrap();
+
+        // Appending each key matches rebuilding the input set
+        for size in 2..=M.len() {
+            input_set.append_key(&M[size - 1]).unwrap();
+            assert_eq!(input_set.get_unpadded_size() as usize, size);
+            assert_eq!(input_set, TriptychInputSet::new_merkle(&M[..size]).unwrap());
+        }
+
+        // Standard input sets can't append keys
+        assert!(TriptychInputSet::new(&M).unwrap().append_key(&M[0]).is_err());
+    }
+
+    #[test]
+    #[allow(non_snake_case)]
+    fn test_input_set_hash() {
+        let params = TriptychParameters::new(2, 4).unwrap();
+        let M = random_vector(params.get_N() as usize);
+        let input_set = TriptychInputSet::new(&M).unwrap();
+        let statement = TriptychStatement::new(&params, &input_set, &M[0]).unwrap();
+
+        // The input set hash is available from the statement
+        assert_eq!(statement.get_input_set().get_hash(), input_set.get_hash());
+
+        // Different input sets have different hashes
+        let mut M_evil = M.clone();
+        M_evil.swap(0, 1);
+        assert_ne!(TriptychInputSet::new(&M_evil).unwrap().get_hash(), input_set.get_hash());
+    }
 }
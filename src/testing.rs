@@ -0,0 +1,81 @@
+// Copyright (c) 2024, The Tari Project
+// SPDX-License-Identifier: BSD-3-Clause
+
+use alloc::vec::Vec;
+
+use blake3::Hasher;
+use curve25519_dalek::{RistrettoPoint, Scalar};
+
+use crate::{domains, statement::StatementError, TriptychInputSet, TriptychParameters};
+
+/// Generate a deterministic [`TriptychInputSet`] from a `seed`, for use in tests and benchmarks.
+///
+/// The input set has the size specified by the [`TriptychParameters`] `params`. All verification keys are derived
+/// from `seed` using `BLAKE3`, except that the verification key corresponding to the signing key `own_key` is placed at
+/// index `own_index`. The same arguments always produce the same input set, regardless of platform.
+///
+/// The resulting verification keys are not suitable for anything but testing!
+///
+/// If `own_index` is not valid for `params`, returns a [`StatementError`].
+#[allow(non_snake_case)]
+pub fn deterministic_ring(
+    params: &TriptychParameters,
+    seed: u64,
+    own_index: u32,
+    own_key: &Scalar,
+) -> Result<TriptychInputSet, StatementError> {
+    if own_index >= params.get_N() {
+        return Err(StatementError::InvalidParameter {
+            reason: "`own_index >= N`",
+        });
+    }
+
+    // Use `BLAKE3` for the verification keys
+    let mut hasher = Hasher::new();
+    hasher.update(domains::POINT_DETERMINISTIC_RING.as_bytes());
+    hasher.update(&domains::VERSION.to_le_bytes());
+    hasher.update(&seed.to_le_bytes());
+    let mut hasher_xof = hasher.finalize_xof();
+    let mut M_bytes = [0u8; 64];
+    let M = (0..params.get_N())
+        .map(|i| {
+            hasher_xof.fill(&mut M_bytes);
+            if i == own_index {
+                own_key * params.get_G()
+            } else {
+                RistrettoPoint::from_uniform_bytes(&M_bytes)
+            }
+        })
+        .collect::<Vec<RistrettoPoint>>();
+
+    TriptychInputSet::new(&M)
+}
+
+#[cfg(test)]
+mod test {
+    use curve25519_dalek::Scalar;
+
+    use crate::{testing::deterministic_ring, TriptychParameters};
+
+    #[test]
+    fn test_deterministic_ring() {
+        let params = TriptychParameters::new(2, 4).unwrap();
+        let own_key = Scalar::from(12345u32);
+
+        // The same seed produces the same ring, with our key in place
+        let ring = deterministic_ring(&params, 1, 3, &own_key).unwrap();
+        assert_eq!(ring, deterministic_ring(&params, 1, 3, &own_key).unwrap());
+        assert_eq!(ring.get_keys().len(), params.get_N() as usize);
+        assert_eq!(ring.get_keys()[3], own_key * params.get_G());
+
+        // Moving our key does not change the other keys
+        let moved = deterministic_ring(&params, 1, 4, &own_key).unwrap();
+        assert_eq!(ring.get_keys()[5..], moved.get_keys()[5..]);
+
+        // A different seed produces a different ring
+        assert_ne!(ring, deterministic_ring(&params, 2, 3, &own_key).unwrap());
+
+        // The index must be valid
+        assert!(deterministic_ring(&params, 1, params.get_N(), &own_key).is_err());
+    }
+}
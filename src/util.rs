@@ -1,7 +1,14 @@
 // Copyright (c) 2024, The Tari Project
 // SPDX-License-Identifier: BSD-3-Clause
 
-use curve25519_dalek::Scalar;
+use alloc::vec::Vec;
+
+use blake3::Hasher;
+#[cfg(feature = "borsh")]
+use borsh::{io, BorshDeserialize};
+#[cfg(feature = "borsh")]
+use curve25519_dalek::ristretto::CompressedRistretto;
+use curve25519_dalek::{traits::IsIdentity, RistrettoPoint, Scalar};
 use rand_core::{
     impls::{next_u32_via_fill, next_u64_via_fill},
     CryptoRng,
@@ -10,10 +17,14 @@ use rand_core::{
 use subtle::{ConditionallySelectable, ConstantTimeEq};
 use zeroize::Zeroize;
 
+use crate::domains;
+
 /// Options for constant- or variable-time operations.
-#[derive(Clone, Copy)]
+///
+/// Variable-time operations are faster, but may leak secret data through timing side channels.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
 #[allow(dead_code)]
-pub(crate) enum OperationTiming {
+pub enum OperationTiming {
     /// The operation should attempt to run in constant time
     Constant,
     /// The operation may run in variable time
@@ -38,6 +49,118 @@ pub(crate) fn delta(x: u32, y: u32, timing: OperationTiming) -> Scalar {
     }
 }
 
+/// Append the minimal unsigned LEB128 variable-length encoding of a `u32` to a buffer.
+pub(crate) fn encode_varint(mut value: u32, buffer: &mut Vec<u8>) {
+    while value >= 0x80 {
+        // This can't truncate since only the low seven bits are kept
+        #[allow(clippy::cast_possible_truncation)]
+        buffer.push((value & 0x7F) as u8 | 0x80);
+        value >>= 7;
+    }
+    #[allow(clippy::cast_possible_truncation)]
+    buffer.push(value as u8);
+}
+
+/// Decode a minimal unsigned LEB128 variable-length encoding of a `u32` from the start of a buffer.
+///
+/// Returns the value and the remaining buffer, or `None` if the encoding is truncated, overflows, or is not minimal.
+// The index is at most four, so shifts and offsets cannot overflow
+#[allow(clippy::arithmetic_side_effects)]
+pub(crate) fn decode_varint(buffer: &[u8]) -> Option<(u32, &[u8])> {
+    let mut value = 0u32;
+    for (index, byte) in buffer.iter().enumerate().take(5) {
+        // The final byte of a five-byte encoding may only use the four bits remaining in a `u32`
+        if index == 4 && byte > &0x0F {
+            return None;
+        }
+        value |= u32::from(byte & 0x7F) << (7 * index);
+
+        if byte & 0x80 == 0 {
+            // A trailing zero byte means the encoding was not minimal
+            if index > 0 && byte == &0 {
+                return None;
+            }
+            return Some((value, &buffer[index + 1..]));
+        }
+    }
+
+    None
+}
+
+/// The largest multiple checked by [`small_multiple_relation`].
+const SMALL_MULTIPLE_BOUND: u32 = 16;
+
+/// Determine if two group elements are trivially related.
+///
+/// This is the case if either is the identity, or if either is `k` or `-k` times the other for some `0 < k <= 16`.
+/// This is only a best-effort check for gross misuse, and cannot show that the group elements are independent.
+pub(crate) fn small_multiple_relation(a: &RistrettoPoint, b: &RistrettoPoint) -> bool {
+    if a.is_identity() || b.is_identity() {
+        return true;
+    }
+
+    let mut a_multiple = *a;
+    let mut b_multiple = *b;
+    for _ in 0..SMALL_MULTIPLE_BOUND {
+        if a_multiple == *b || a_multiple == -b || b_multiple == *a || b_multiple == -a {
+            return true;
+        }
+        a_multiple += a;
+        b_multiple += b;
+    }
+
+    false
+}
+
+/// Hash an arbitrary generator derivation seed to a fixed-length prefix.
+pub(crate) fn generator_seed_prefix(seed: &[u8]) -> [u8; 32] {
+    let mut hasher = Hasher::new();
+    hasher.update(domains::GENERATOR_SEED.as_bytes());
+    hasher.update(&domains::VERSION.to_le_bytes());
+    hasher.update(seed);
+
+    hasher.finalize().into()
+}
+
+/// Produce a `BLAKE3` hasher for deriving a group generator with a given `domain`.
+///
+/// If a seed `prefix` is provided, it is hashed before the domain, so derivations with distinct prefixes are
+/// independent. Without a prefix, this produces the default derivation.
+pub(crate) fn generator_hasher(domain: &str, prefix: Option<&[u8; 32]>) -> Hasher {
+    let mut hasher = Hasher::new();
+    if let Some(prefix) = prefix {
+        hasher.update(prefix);
+    }
+    hasher.update(domain.as_bytes());
+    hasher.update(&domains::VERSION.to_le_bytes());
+
+    hasher
+}
+
+/// Derive the group generator `U` for an application identifier `app_id` using `BLAKE3`.
+///
+/// This is shared by the base and parallel parameters, so that an application identifier produces the same linking
+/// tags with either.
+#[allow(non_snake_case)]
+pub(crate) fn derive_application_U(app_id: &[u8]) -> RistrettoPoint {
+    let mut U_bytes = [0u8; 64];
+    let mut hasher = generator_hasher(domains::POINT_U_APPLICATION, None);
+    hasher.update(app_id);
+    hasher.finalize_xof().fill(&mut U_bytes);
+
+    RistrettoPoint::from_uniform_bytes(&U_bytes)
+}
+
+/// Deserialize a canonically-encoded group element using `borsh`.
+#[cfg(feature = "borsh")]
+pub(crate) fn deserialize_point<R: io::Read>(reader: &mut R) -> io::Result<RistrettoPoint> {
+    let bytes: [u8; 32] = BorshDeserialize::deserialize_reader(reader)?;
+
+    CompressedRistretto(bytes)
+        .decompress()
+        .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, "Invalid group element"))
+}
+
 /// A null random number generator that exists only for deterministic transcript-based weight generation.
 /// It only produces zero.
 /// This is DANGEROUS in general, and you almost certainly should not use it elsewhere!
@@ -67,13 +190,64 @@ impl RngCore for NullRng {
 // We only do this so `NullRng` can be used with `TranscriptRng` due to a trait bound.
 impl CryptoRng for NullRng {}
 
+/// A deterministic random number generator that expands a 32-byte seed using the `BLAKE3` extendable output function.
+/// It exists only for producing reproducible proofs for testing and conformance.
+/// This is DANGEROUS in general, since reusing a seed for different proofs can leak the signing key!
+#[cfg(feature = "hazmat")]
+pub(crate) struct SeededRng {
+    reader: blake3::OutputReader,
+}
+
+#[cfg(feature = "hazmat")]
+impl SeededRng {
+    /// Create a new [`SeededRng`] from a `seed`.
+    pub(crate) fn new(seed: &[u8; 32]) -> Self {
+        let mut hasher = blake3::Hasher::new();
+        hasher.update(crate::domains::DETERMINISTIC_PROVER_RNG.as_bytes());
+        hasher.update(&crate::domains::VERSION.to_le_bytes());
+        hasher.update(seed);
+
+        Self {
+            reader: hasher.finalize_xof(),
+        }
+    }
+}
+
+#[cfg(feature = "hazmat")]
+impl RngCore for SeededRng {
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        self.reader.fill(dest);
+    }
+
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), rand_core::Error> {
+        self.fill_bytes(dest);
+
+        Ok(())
+    }
+
+    fn next_u32(&mut self) -> u32 {
+        next_u32_via_fill(self)
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        next_u64_via_fill(self)
+    }
+}
+
+// The output is unpredictable only if the seed is secret and never reused.
+#[cfg(feature = "hazmat")]
+impl CryptoRng for SeededRng {}
+
 #[cfg(test)]
 mod test {
-    use curve25519_dalek::Scalar;
-    use rand_core::RngCore;
+    use alloc::vec::Vec;
+
+    use curve25519_dalek::{constants::RISTRETTO_BASEPOINT_POINT, traits::Identity, RistrettoPoint, Scalar};
+    use rand_chacha::ChaCha12Rng;
+    use rand_core::{RngCore, SeedableRng};
 
     use super::{NullRng, OperationTiming};
-    use crate::util::delta;
+    use crate::util::{decode_varint, delta, encode_varint, small_multiple_relation};
 
     #[test]
     fn test_delta() {
@@ -90,6 +264,59 @@ mod test {
         }
     }
 
+    #[test]
+    #[allow(non_snake_case)]
+    fn test_small_multiple_relation() {
+        let mut rng = ChaCha12Rng::seed_from_u64(8675309);
+        let G = RISTRETTO_BASEPOINT_POINT;
+        let H = RistrettoPoint::random(&mut rng);
+
+        // Random group elements are not related
+        assert!(!small_multiple_relation(&G, &H));
+
+        // Small multiples in either direction and with either sign are related
+        for k in [1u64, 2, 16] {
+            let multiple = Scalar::from(k) * H;
+            assert!(small_multiple_relation(&H, &multiple));
+            assert!(small_multiple_relation(&multiple, &H));
+            assert!(small_multiple_relation(&H, &-multiple));
+        }
+
+        // Larger multiples are not detected
+        assert!(!small_multiple_relation(&H, &(Scalar::from(17u64) * H)));
+
+        // The identity is related to anything
+        assert!(small_multiple_relation(&H, &RistrettoPoint::identity()));
+    }
+
+    #[test]
+    fn test_varint() {
+        // Values round-trip using a minimal number of bytes
+        for (value, length) in [
+            (0, 1),
+            (1, 1),
+            (0x7F, 1),
+            (0x80, 2),
+            (0x3FFF, 2),
+            (0x4000, 3),
+            (u32::MAX, 5),
+        ] {
+            let mut buffer = Vec::new();
+            encode_varint(value, &mut buffer);
+            assert_eq!(buffer.len(), length);
+
+            buffer.push(0xFF);
+            assert_eq!(decode_varint(&buffer), Some((value, [0xFF].as_slice())));
+        }
+
+        // Invalid encodings fail
+        assert!(decode_varint(&[]).is_none());
+        assert!(decode_varint(&[0x80]).is_none());
+        assert!(decode_varint(&[0x80, 0x00]).is_none());
+        assert!(decode_varint(&[0xFF, 0xFF, 0xFF, 0xFF, 0x10]).is_none());
+        assert!(decode_varint(&[0xFF, 0xFF, 0xFF, 0xFF, 0x8F, 0x00]).is_none());
+    }
+
     #[test]
     fn test_null_rng() {
         // Ensure that the null RNG supplies only zero
@@ -0,0 +1,183 @@
+// Copyright (c) 2024, The Tari Project
+// SPDX-License-Identifier: BSD-3-Clause
+
+use core::slice;
+
+use crate::{proof::ProofError, Transcript, TriptychInputSet, TriptychParameters, TriptychProof, TriptychStatement};
+
+/// A reusable verifier for Triptych proofs that share [`TriptychParameters`] and a [`TriptychInputSet`].
+///
+/// The verifier checks once that the input set matches the parameters, and then checks that each statement it is given
+/// uses them. The parameters and input set share their generators and verification keys with those passed to
+/// [`TriptychVerifier::new`], so these are not copied.
+///
+/// Verification results and cost are identical to those of [`TriptychProof::verify`] and
+/// [`TriptychProof::verify_batch`], which also read the common generators and verification keys in place. To speed up
+/// the part of the final check that uses the fixed generators, use [`TriptychProof::verify_batch_precomputed`].
+#[derive(Clone)]
+pub struct TriptychVerifier {
+    params: TriptychParameters,
+    input_set: TriptychInputSet,
+}
+
+impl TriptychVerifier {
+    /// Generate a new [`TriptychVerifier`] for [`TriptychParameters`] `params` and [`TriptychInputSet`] `input_set`.
+    ///
+    /// If the input set does not have size `N` specified by the parameters, returns a [`ProofError`].
+    pub fn new(params: &TriptychParameters, input_set: &TriptychInputSet) -> Result<Self, ProofError> {
+        if input_set.get_keys().len() != params.get_N() as usize {
+            return Err(ProofError::InvalidParameter {
+                reason: "input vector length was not `N`",
+            });
+        }
+
+        Ok(Self {
+            params: params.clone(),
+            input_set: input_set.clone(),
+        })
+    }
+
+    /// Get the parameters for this [`TriptychVerifier`].
+    pub fn get_params(&self) -> &TriptychParameters {
+        &self.params
+    }
+
+    /// Get the input set for this [`TriptychVerifier`].
+    pub fn get_input_set(&self) -> &TriptychInputSet {
+        &self.input_set
+    }
+
+    /// Verify a Triptych [`TriptychProof`].
+    ///
+    /// Verification requires that the `statement` and `transcript` match those used when the proof was generated, and
+    /// that the statement uses the parameters and input set of this verifier.
+    ///
+    /// If these requirements are not met, or if the proof is invalid, returns a [`ProofError`].
+    pub fn verify(
+        &self,
+        statement: &TriptychStatement,
+        proof: &TriptychProof,
+        transcript: &mut Transcript,
+    ) -> Result<(), ProofError> {
+        // Verify as a trivial batch
+        self.verify_batch(
+            slice::from_ref(statement),
+            slice::from_ref(proof),
+            slice::from_mut(transcript),
+        )
+    }
+
+    /// Verify a batch of Triptych [`TriptychProofs`](`TriptychProof`).
+    ///
+    /// An empty batch is valid by definition.
+    ///
+    /// Verification requires that the `statements` and `transcripts` match those used when the `proofs` were generated,
+    /// and that the statements use the parameters and input set of this verifier.
+    ///
+    /// If any of the above requirements are not met, or if any proof is invalid, returns a [`ProofError`].
+    pub fn verify_batch(
+        &self,
+        statements: &[TriptychStatement],
+        proofs: &[TriptychProof],
+        transcripts: &mut [Transcript],
+    ) -> Result<(), ProofError> {
+        // The batch verifier checks that all statements share parameters and an input set, so checking the first
+        // suffices
+        if let Some(statement) = statements.first() {
+            if statement.get_params().get_hash() != self.params.get_hash() {
+                return Err(ProofError::InvalidParameter {
+                    reason: "statement parameters do not match verifier",
+                });
+            }
+            if statement.get_input_set().get_hash() != self.input_set.get_hash() {
+                return Err(ProofError::InvalidParameter {
+                    reason: "statement input set does not match verifier",
+                });
+            }
+        }
+
+        TriptychProof::verify_batch(statements, proofs, transcripts)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use alloc::vec::Vec;
+
+    use curve25519_dalek::{RistrettoPoint, Scalar};
+    use itertools::izip;
+    use rand_chacha::ChaCha12Rng;
+    use rand_core::SeedableRng;
+
+    use crate::{
+        Transcript,
+        TriptychInputSet,
+        TriptychParameters,
+        TriptychProof,
+        TriptychStatement,
+        TriptychVerifier,
+        TriptychWitness,
+    };
+
+    #[test]
+    #[allow(non_snake_case, non_upper_case_globals)]
+    fn test_verifier() {
+        // Generate data
+        const n: u32 = 2;
+        const m: u32 = 4;
+        const batch: u32 = 3; // batch size
+        let mut rng = ChaCha12Rng::seed_from_u64(8675309);
+        let params = TriptychParameters::new(n, m).unwrap();
+        let witnesses = (0..batch)
+            .map(|l| TriptychWitness::new(&params, l, &Scalar::random(&mut rng)).unwrap())
+            .collect::<Vec<TriptychWitness>>();
+        let mut M = (0..params.get_N())
+            .map(|_| RistrettoPoint::random(&mut rng))
+            .collect::<Vec<RistrettoPoint>>();
+        for witness in &witnesses {
+            M[witness.get_l() as usize] = witness.compute_verification_key();
+        }
+        let input_set = TriptychInputSet::new(&M).unwrap();
+        let statements = witnesses
+            .iter()
+            .map(|w| TriptychStatement::new(&params, &input_set, &w.compute_linking_tag()).unwrap())
+            .collect::<Vec<TriptychStatement>>();
+        let mut transcripts = (0..batch)
+            .map(|_| Transcript::new(b"Test transcript"))
+            .collect::<Vec<Transcript>>();
+
+        // Generate the proofs
+        let proofs = izip!(witnesses.iter(), statements.iter(), transcripts.clone().iter_mut())
+            .map(|(w, s, t)| TriptychProof::prove_with_rng(w, s, &mut rng, t).unwrap())
+            .collect::<Vec<TriptychProof>>();
+
+        // The verifier is reusable across single proofs and batches
+        let verifier = TriptychVerifier::new(&params, &input_set).unwrap();
+        assert!(verifier
+            .verify(&statements[0], &proofs[0], &mut transcripts[0].clone())
+            .is_ok());
+        assert!(verifier
+            .verify_batch(&statements, &proofs, &mut transcripts.clone())
+            .is_ok());
+        assert!(verifier.verify_batch(&[], &[], &mut []).is_ok());
+
+        // An invalid proof fails
+        assert!(verifier
+            .verify(&statements[0], &proofs[1], &mut transcripts[0].clone())
+            .is_err());
+
+        // A statement over another input set fails
+        let mut evil_M = M.clone();
+        evil_M[batch as usize] = RistrettoPoint::random(&mut rng);
+        let evil_input_set = TriptychInputSet::new(&evil_M).unwrap();
+        let evil_statement =
+            TriptychStatement::new(&params, &evil_input_set, &witnesses[0].compute_linking_tag()).unwrap();
+        assert!(verifier
+            .verify(&evil_statement, &proofs[0], &mut transcripts[0])
+            .is_err());
+
+        // An input set of the wrong size is rejected
+        let small_input_set = TriptychInputSet::new(&M[1..]).unwrap();
+        assert!(TriptychVerifier::new(&params, &small_input_set).is_err());
+    }
+}
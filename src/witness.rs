@@ -1,12 +1,21 @@
 // Copyright (c) 2024, The Tari Project
 // SPDX-License-Identifier: BSD-3-Clause
 
-use curve25519_dalek::{RistrettoPoint, Scalar};
+use alloc::{boxed::Box, vec::Vec};
+
+use curve25519_dalek::{ristretto::RistrettoBasepointTable, RistrettoPoint, Scalar};
 use rand_core::CryptoRngCore;
 use snafu::prelude::*;
+use subtle::{Choice, ConditionallySelectable, ConstantTimeEq};
 use zeroize::{Zeroize, ZeroizeOnDrop, Zeroizing};
 
-use crate::TriptychParameters;
+use crate::{gray::GrayIterator, linking::linking_tag_for, TriptychInputSet, TriptychParameters};
+
+// Size of the serialized index in bytes
+const SERIALIZED_INDEX_BYTES: usize = 4;
+
+// Size of serialized scalars in bytes
+const SERIALIZED_SCALAR_BYTES: usize = 32;
 
 /// A Triptych proof witness.
 ///
@@ -30,6 +39,9 @@ pub enum WitnessError {
         /// The reason for the parameter error.
         reason: &'static str,
     },
+    /// Witness deserialization failed.
+    #[snafu(display("Witness deserialization failed"))]
+    FailedDeserialization,
 }
 
 impl TriptychWitness {
@@ -55,6 +67,31 @@ impl TriptychWitness {
         })
     }
 
+    /// Generate a new [`TriptychWitness`] by locating the verification key for a signing key `r` in an `input_set`.
+    ///
+    /// The verification key is compared against every key in the input set in constant time, so the running time does
+    /// not depend on which index matched. If the key appears more than once, the last matching index is used.
+    ///
+    /// If the verification key is not in the input set, or if the witness cannot be built for [`TriptychParameters`]
+    /// `params`, returns `None`.
+    pub fn locate(params: &TriptychParameters, r: &Scalar, input_set: &TriptychInputSet) -> Option<Self> {
+        let verification_key = r * params.get_G();
+
+        let mut found = Choice::from(0);
+        let mut l = 0u32;
+        for (index, key) in (0u32..).zip(input_set.get_keys()) {
+            let matches = key.ct_eq(&verification_key);
+            l.conditional_assign(&index, matches);
+            found |= matches;
+        }
+
+        if bool::from(found) {
+            Self::new(params, l, r).ok()
+        } else {
+            None
+        }
+    }
+
     /// Generate a new random [`TriptychWitness`].
     ///
     /// You must provide [`TriptychParameters`] `params` and a [`CryptoRngCore`] random number generator `rng`.
@@ -76,6 +113,58 @@ impl TriptychWitness {
         }
     }
 
+    /// Serialize a [`TriptychWitness`] to a canonical byte vector.
+    ///
+    /// The encoding contains the index and signing key, but not the [`TriptychParameters`].
+    /// Since it contains secret data, the result is zeroized when dropped.
+    pub fn to_bytes(&self) -> Zeroizing<Vec<u8>> {
+        // This cannot overflow
+        #[allow(clippy::arithmetic_side_effects)]
+        let mut result = Zeroizing::new(Vec::with_capacity(SERIALIZED_INDEX_BYTES + SERIALIZED_SCALAR_BYTES));
+        result.extend_from_slice(&self.l.to_le_bytes());
+        result.extend_from_slice(self.r.as_bytes());
+
+        result
+    }
+
+    /// Deserialize a [`TriptychWitness`] from a canonical byte slice and [`TriptychParameters`] `params`.
+    ///
+    /// The buffers this function uses to hold secret data are zeroized when dropped. This cannot cover copies that the
+    /// compiler makes, such as when a scalar encoding is passed by value to the curve library, so secret data may
+    /// remain in stack memory that is later reused. Since `bytes` contains secret data, you should zeroize it yourself
+    /// after use.
+    ///
+    /// If `bytes` does not represent a canonical encoding of a witness that is valid for `params`, returns a
+    /// [`WitnessError`].
+    pub fn from_bytes(params: &TriptychParameters, bytes: &[u8]) -> Result<Self, WitnessError> {
+        // This cannot overflow
+        #[allow(clippy::arithmetic_side_effects)]
+        if bytes.len() != SERIALIZED_INDEX_BYTES + SERIALIZED_SCALAR_BYTES {
+            return Err(WitnessError::FailedDeserialization);
+        }
+        let (l_slice, r_slice) = bytes.split_at(SERIALIZED_INDEX_BYTES);
+
+        // Parse the index
+        let mut l_bytes = Zeroizing::new([0u8; SERIALIZED_INDEX_BYTES]);
+        l_bytes.copy_from_slice(l_slice);
+        let l = Zeroizing::new(u32::from_le_bytes(*l_bytes));
+
+        // Parse the signing key
+        let r = Self::parse_scalar(r_slice)?;
+
+        Self::new(params, *l, &r).map_err(|_| WitnessError::FailedDeserialization)
+    }
+
+    /// Parse a canonical scalar, holding it in zeroizing memory.
+    fn parse_scalar(slice: &[u8]) -> Result<Zeroizing<Scalar>, WitnessError> {
+        let mut bytes = Zeroizing::new([0u8; SERIALIZED_SCALAR_BYTES]);
+        bytes.copy_from_slice(slice);
+
+        Option::<Scalar>::from(Scalar::from_canonical_bytes(*bytes))
+            .map(Zeroizing::new)
+            .ok_or(WitnessError::FailedDeserialization)
+    }
+
     /// Get the [`TriptychParameters`] from this [`TriptychWitness`].
     pub fn get_params(&self) -> &TriptychParameters {
         &self.params
@@ -91,14 +180,199 @@ impl TriptychWitness {
         &self.r
     }
 
+    /// Get the Gray code decomposition of the index from this [`TriptychWitness`].
+    ///
+    /// This is the base-`n` Gray code digit vector of length `m` that corresponds to the index. Since the index is
+    /// secret, the decomposition is computed in constant time and is zeroized when dropped.
+    ///
+    /// If the decomposition fails, returns `None`.
+    pub fn gray_decomposition(&self) -> Option<Zeroizing<Vec<u32>>> {
+        GrayIterator::decompose(self.params.get_n(), self.params.get_m(), self.l).map(Zeroizing::new)
+    }
+
     /// Compute the linking tag for the [`TriptychWitness`] signing key.
     #[allow(non_snake_case)]
     pub fn compute_linking_tag(&self) -> RistrettoPoint {
-        *Zeroizing::new(self.r.invert()) * self.params.get_U()
+        linking_tag_for(&self.params, &self.r)
     }
 
     /// Compute the verification key for the [`TriptychWitness`] signing key.
     pub fn compute_verification_key(&self) -> RistrettoPoint {
         self.r * self.params.get_G()
     }
+
+    /// Compute the verification keys for a slice of [`TriptychWitness`] signing keys `witnesses`.
+    ///
+    /// The result is identical to calling [`TriptychWitness::compute_verification_key`] for each witness, but is more
+    /// efficient for large slices. Consecutive witnesses sharing the same generator reuse a precomputed table.
+    #[allow(non_snake_case)]
+    pub fn compute_verification_keys(witnesses: &[Self]) -> Vec<RistrettoPoint> {
+        let mut table: Option<Box<RistrettoBasepointTable>> = None;
+
+        witnesses
+            .iter()
+            .map(|witness| {
+                let G = witness.params.get_G();
+                let table = match table {
+                    Some(ref table) if &table.basepoint() == G => table,
+                    _ => table.insert(Box::new(RistrettoBasepointTable::create(G))),
+                };
+
+                &witness.r * table.as_ref()
+            })
+            .collect()
+    }
+
+    /// Determine if a [`TriptychInputSet`] contains the verification key for the [`TriptychWitness`] signing key.
+    ///
+    /// The key may appear at any index, not only the witness index. This does not check that the witness could be used
+    /// to generate a proof against the input set.
+    ///
+    /// This requires the signing key. In a wallet design that separates a view key from a spend key, verification keys
+    /// would instead be derived from data that the view key can recompute, such as a shared secret with a sender, so
+    /// that a scanner holding only the view key can compute candidate verification keys and check for them in an input
+    /// set using [`TriptychInputSet::get_keys`]. The signing key would then be derived using the spend key only when
+    /// building a [`TriptychWitness`] to generate a proof.
+    pub fn recognizes(&self, input_set: &TriptychInputSet) -> bool {
+        input_set.get_keys().contains(&self.compute_verification_key())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use alloc::{vec, vec::Vec};
+
+    use curve25519_dalek::RistrettoPoint;
+    use rand_chacha::ChaCha12Rng;
+    use rand_core::SeedableRng;
+
+    use crate::{gray::GrayIterator, TriptychInputSet, TriptychParameters, TriptychWitness};
+
+    #[test]
+    #[allow(non_snake_case)]
+    fn test_recognizes() {
+        let params = TriptychParameters::new(2, 4).unwrap();
+        let mut rng = ChaCha12Rng::seed_from_u64(8675309);
+        let witness = TriptychWitness::random(&params, &mut rng);
+        let other = TriptychWitness::random(&params, &mut rng);
+
+        // Only an input set containing the verification key is recognized, at any index
+        let mut M = vec![other.compute_verification_key(); params.get_N() as usize];
+        assert!(!witness.recognizes(&TriptychInputSet::new(&M).unwrap()));
+        M[0] = witness.compute_verification_key();
+        assert!(witness.recognizes(&TriptychInputSet::new(&M).unwrap()));
+    }
+
+    #[test]
+    #[allow(non_snake_case)]
+    fn test_compute_verification_keys() {
+        let mut rng = ChaCha12Rng::seed_from_u64(8675309);
+
+        // Use witnesses with different generators, including consecutive witnesses sharing a generator
+        let params = TriptychParameters::new(2, 4).unwrap();
+        let G = RistrettoPoint::random(&mut rng);
+        let other_params = TriptychParameters::new_with_generators(2, 4, &G, params.get_U()).unwrap();
+        let witnesses =
+            [&params, &params, &other_params, &params].map(|params| TriptychWitness::random(params, &mut rng));
+
+        // Batched computation matches individual computation
+        assert_eq!(
+            TriptychWitness::compute_verification_keys(&witnesses),
+            witnesses
+                .iter()
+                .map(|witness| witness.compute_verification_key())
+                .collect::<Vec<RistrettoPoint>>()
+        );
+        assert!(TriptychWitness::compute_verification_keys(&[]).is_empty());
+    }
+
+    #[test]
+    fn test_zeroize_on_drop() {
+        use core::{mem::MaybeUninit, ptr};
+
+        use curve25519_dalek::Scalar;
+
+        let params = TriptychParameters::new(2, 4).unwrap();
+        let mut rng = ChaCha12Rng::seed_from_u64(8675309);
+        let witness = TriptychWitness::new(&params, 1, &Scalar::random(&mut rng)).unwrap();
+
+        // Drop the witness in place, leaving its memory allocated so it can be inspected
+        let mut slot = MaybeUninit::new(witness);
+        let witness = slot.as_mut_ptr();
+        // SAFETY: The witness is initialized, and is dropped exactly once. Afterward, only the index and signing key
+        // are read; these have no drop glue, and their memory remains allocated.
+        let (l, r) = unsafe {
+            ptr::drop_in_place(witness);
+            (ptr::addr_of!((*witness).l).read(), ptr::addr_of!((*witness).r).read())
+        };
+
+        // Both the index and signing key are scrubbed
+        assert_eq!(l, 0);
+        assert_eq!(r, Scalar::ZERO);
+    }
+
+    #[test]
+    #[allow(non_snake_case)]
+    fn test_locate() {
+        let params = TriptychParameters::new(2, 4).unwrap();
+        let mut rng = ChaCha12Rng::seed_from_u64(8675309);
+        let witness = TriptychWitness::random(&params, &mut rng);
+        let other = TriptychWitness::random(&params, &mut rng);
+
+        // The witness is recovered from the index of its verification key
+        let mut M = vec![other.compute_verification_key(); params.get_N() as usize];
+        M[witness.get_l() as usize] = witness.compute_verification_key();
+        let located = TriptychWitness::locate(&params, witness.get_r(), &TriptychInputSet::new(&M).unwrap()).unwrap();
+        assert_eq!(located.get_l(), witness.get_l());
+        assert_eq!(located.get_r(), witness.get_r());
+
+        // A missing verification key is not located
+        M[witness.get_l() as usize] = other.compute_verification_key();
+        assert!(TriptychWitness::locate(&params, witness.get_r(), &TriptychInputSet::new(&M).unwrap()).is_none());
+    }
+
+    #[test]
+    fn test_gray_decomposition() {
+        let params = TriptychParameters::new(3, 4).unwrap();
+        let mut rng = ChaCha12Rng::seed_from_u64(8675309);
+        let witness = TriptychWitness::random(&params, &mut rng);
+
+        // The decomposition matches the Gray code position of the index
+        let (index, digits) = GrayIterator::new(params.get_n(), params.get_m())
+            .unwrap()
+            .scan(vec![0; params.get_m() as usize], |digits, (i, _, new)| {
+                digits[i] = new;
+                Some(digits.clone())
+            })
+            .enumerate()
+            .nth(witness.get_l() as usize)
+            .unwrap();
+        assert_eq!(index, witness.get_l() as usize);
+        assert_eq!(*witness.gray_decomposition().unwrap(), digits);
+    }
+
+    #[test]
+    fn test_serialize_deserialize() {
+        let params = TriptychParameters::new(2, 4).unwrap();
+        let mut rng = ChaCha12Rng::seed_from_u64(8675309);
+        let witness = TriptychWitness::random(&params, &mut rng);
+
+        // Serialize and deserialize the witness
+        let serialized = witness.to_bytes();
+        let deserialized = TriptychWitness::from_bytes(&params, &serialized).unwrap();
+        assert_eq!(deserialized.get_l(), witness.get_l());
+        assert_eq!(deserialized.get_r(), witness.get_r());
+
+        // Invalid encodings fail
+        assert!(TriptychWitness::from_bytes(&params, &serialized[1..]).is_err());
+        let mut evil = serialized.clone();
+        evil[0..4].copy_from_slice(&params.get_N().to_le_bytes());
+        assert!(TriptychWitness::from_bytes(&params, &evil).is_err());
+        let mut evil = serialized.clone();
+        evil[4..].copy_from_slice(&[0xFF; 32]);
+        assert!(TriptychWitness::from_bytes(&params, &evil).is_err());
+        let mut evil = serialized;
+        evil[4..].copy_from_slice(&[0u8; 32]);
+        assert!(TriptychWitness::from_bytes(&params, &evil).is_err());
+    }
 }